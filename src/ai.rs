@@ -1,17 +1,36 @@
+/// Classic AI persona imitating the moves of its human opponent.
+pub mod imitator;
 /// Monte Carlo tree-search AI implementation.
 pub mod mcts;
+/// Perfect-play AI using negamax with alpha-beta pruning.
+pub mod minimax;
+/// Hard-coded strong replies for the first moves of Ultimate games.
+pub mod opening_book;
 /// Random move selection.
 pub mod random;
+/// Seedable random number generator shared by the AI engines.
+pub mod rng;
 /// Rule-based classic tic-tac-toe AI.
 pub mod simple;
+/// Exhaustive search for perfect classic tic-tac-toe play.
+pub mod solver;
 
 use crate::ai::Move::{Base, Ultimate};
+use crate::ai::imitator::ImitatorAi;
 use crate::ai::mcts::MCTSAi;
-use crate::ai::random::random_move;
+use crate::ai::minimax::MinimaxAi;
+use crate::ai::random::RandomAI;
 use crate::ai::simple::SimpleAi;
 use crate::game::base::SmallBoard;
 use crate::game::ultimate::BigBoard;
 use crate::game::{GameState, Mark};
+use std::borrow::Cow;
+use std::fmt;
+
+/// Maximum number of moves an AI-vs-AI classic game may take.
+pub const MAX_MOVES_CLASSIC: u32 = 9;
+/// Maximum number of moves an AI-vs-AI Ultimate game may take.
+pub const MAX_MOVES_ULTIMATE: u32 = 81;
 
 /// Available AI strategies for classic and Ultimate tic-tac-toe.
 #[derive(Clone, Debug, PartialEq)]
@@ -24,15 +43,21 @@ pub enum AI {
     StrongTTT(MCTSAi<SmallBoard>),
     /// Uses Monte Carlo tree search on an Ultimate board.
     StrongUTT(MCTSAi<BigBoard>),
+    /// Imitates the human's classic moves, falling back to the Medium AI.
+    Imitator(ImitatorAi),
+    /// Plays perfect classic tic-tac-toe with a full negamax search.
+    Minimax(MinimaxAi),
 }
 
 impl AI {
     /// Chooses a move for a classic tic-tac-toe board.
     pub fn choose_move_ttt(&mut self, board: &SmallBoard) -> Move {
         match self {
-            AI::Weak(_) => random_move(board),
+            AI::Weak(_) => RandomAI.choose_move(board),
             AI::Medium(ai) => ai.choose_move(board),
             AI::StrongTTT(ai) => ai.choose_move(board),
+            AI::Imitator(ai) => ai.choose_move(board),
+            AI::Minimax(ai) => ai.choose_move(board),
             _ => panic!("Invalid AI."),
         }
     }
@@ -40,13 +65,36 @@ impl AI {
     /// Chooses a move for an Ultimate tic-tac-toe board.
     pub fn choose_move_utt(&mut self, board: &BigBoard) -> Move {
         match self {
-            AI::Weak(_) => random_move(board),
+            AI::Weak(_) => RandomAI.choose_move(board),
             AI::Medium(ai) => ai.choose_move(board),
             AI::StrongUTT(ai) => ai.choose_move(board),
             _ => panic!("Invalid AI."),
         }
     }
 
+    /// Returns the display name of the engine behind this AI.
+    pub fn name(&self) -> Cow<'static, str> {
+        match self {
+            AI::Weak(_) => Engine::<SmallBoard>::name(&RandomAI),
+            AI::Medium(ai) => Engine::<SmallBoard>::name(ai),
+            AI::StrongTTT(ai) => ai.name(),
+            AI::StrongUTT(ai) => ai.name(),
+            AI::Imitator(ai) => ai.name(),
+            AI::Minimax(ai) => Engine::<SmallBoard>::name(ai),
+        }
+    }
+
+    /// Returns the estimated chance that this AI wins, as of its last move.
+    ///
+    /// Only engines that evaluate positions provide an estimate.
+    pub fn evaluation(&self) -> Option<f32> {
+        match self {
+            AI::StrongTTT(ai) => ai.last_evaluation(),
+            AI::StrongUTT(ai) => ai.last_evaluation(),
+            _ => None,
+        }
+    }
+
     /// Returns the mark controlled by this AI.
     pub fn get_mark(&self) -> Mark {
         match self {
@@ -54,15 +102,26 @@ impl AI {
             AI::Medium(ai) => ai.ai_mark,
             AI::StrongTTT(ai) => ai.ai_mark,
             AI::StrongUTT(ai) => ai.ai_mark,
+            AI::Imitator(ai) => ai.ai_mark,
+            AI::Minimax(ai) => ai.ai_mark,
         }
     }
 
-    /// Switches the starting mark for stateful AI implementations.
-    pub fn switch_starting_mark(&mut self) {
+    /// Makes the AI play `mark` from the start of the next game.
+    pub fn set_mark(&mut self, mark: Mark) {
         match self {
-            AI::StrongTTT(ai) => ai.switch_starting_mark(),
-            AI::StrongUTT(ai) => ai.switch_starting_mark(),
-            _ => {}
+            AI::Weak(ai_mark) => *ai_mark = mark,
+            AI::Medium(ai) => ai.set_mark(mark),
+            AI::StrongTTT(ai) => {
+                ai.ai_mark = mark;
+                ai.reset();
+            }
+            AI::StrongUTT(ai) => {
+                ai.ai_mark = mark;
+                ai.reset();
+            }
+            AI::Imitator(ai) => ai.ai_mark = mark,
+            AI::Minimax(ai) => ai.ai_mark = mark,
         }
     }
 
@@ -77,7 +136,7 @@ impl AI {
 }
 
 /// A legal move in either supported game variant.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Move {
     /// A classic move containing its row and column.
     Base(usize, usize),
@@ -112,7 +171,9 @@ impl Move {
 }
 
 /// Common board operations required by AI implementations.
-pub trait Game {
+///
+/// Boards are [`Send`] so searches can run on other threads.
+pub trait Game: Send {
     /// Applies a move using the supplied mark.
     fn play(&mut self, mv: &Move, ai_mark: Mark);
     /// Returns every legal move in the current state.
@@ -121,4 +182,229 @@ pub trait Game {
     fn score(&self, mark: Mark) -> i8;
     /// Returns the current game state.
     fn get_state(&self) -> GameState;
+    /// Infers whose turn it is from the number of marks on the board.
+    ///
+    /// # Returns
+    /// The mark to move, or None if it cannot be inferred. With equally many
+    /// marks of each kind the answer depends on who moved first.
+    fn current_player(&self) -> Option<Mark> {
+        None
+    }
+    /// Returns a known strong reply if the board is an opening position.
+    ///
+    /// Boards without an opening book keep the default, which returns None.
+    fn book_move(&self) -> Option<Move> {
+        None
+    }
+}
+
+/// A move-selection strategy that can play on boards of type `T`.
+pub trait Engine<T: Game> {
+    /// Chooses a legal move for the current board.
+    fn choose_move(&mut self, board: &T) -> Move;
+    /// Returns a short human-readable name used to label the engine in the UI.
+    fn name(&self) -> Cow<'static, str>;
+    /// Discards any state retained between moves, before a new game.
+    ///
+    /// Engines without such state keep the default, which does nothing.
+    fn reset(&mut self) {}
+}
+
+impl Engine<SmallBoard> for AI {
+    fn choose_move(&mut self, board: &SmallBoard) -> Move {
+        self.choose_move_ttt(board)
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        AI::name(self)
+    }
+
+    fn reset(&mut self) {
+        AI::reset(self)
+    }
+}
+
+impl Engine<BigBoard> for AI {
+    fn choose_move(&mut self, board: &BigBoard) -> Move {
+        self.choose_move_utt(board)
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        AI::name(self)
+    }
+
+    fn reset(&mut self) {
+        AI::reset(self)
+    }
+}
+
+/// Plays a full game between two engines, starting with `first_player`.
+///
+/// Illegal moves returned by an engine are not applied, but every request
+/// counts towards `max_moves`, so a misbehaving engine cannot stall the game
+/// forever.
+///
+/// # Arguments
+/// * `board` - The board to play on, updated in place
+/// * `first_player` - The mark that moves first
+/// * `engine_x` - The engine playing `X`
+/// * `engine_o` - The engine playing `O`
+/// * `max_moves` - The maximum number of moves to request
+///
+/// # Returns
+/// The final game state, or a [`MoveCapExceeded`] error if the game is still
+/// in progress after `max_moves` requests.
+pub fn play_ai_game<T: Game>(
+    board: &mut T,
+    first_player: Mark,
+    engine_x: &mut (impl Engine<T> + ?Sized),
+    engine_o: &mut (impl Engine<T> + ?Sized),
+    max_moves: u32,
+) -> Result<GameState, MoveCapExceeded> {
+    let mut active_player = first_player;
+    for _ in 0..max_moves {
+        if board.get_state() != GameState::Playing {
+            break;
+        }
+        let mv = match active_player {
+            Mark::X => engine_x.choose_move(board),
+            Mark::O => engine_o.choose_move(board),
+        };
+        if board.get_possible_moves().contains(&mv) {
+            board.play(&mv, active_player);
+            active_player = active_player.switch();
+        }
+    }
+
+    match board.get_state() {
+        GameState::Playing => Err(MoveCapExceeded { max_moves }),
+        state => Ok(state),
+    }
+}
+
+/// Error returned when an AI-vs-AI game does not finish within its move cap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveCapExceeded {
+    /// The cap that was reached.
+    pub max_moves: u32,
+}
+
+impl fmt::Display for MoveCapExceeded {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "game aborted after {} moves without finishing",
+            self.max_moves
+        )
+    }
+}
+
+impl std::error::Error for MoveCapExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Board;
+
+    /// Engine that always answers with the same move, legal or not.
+    struct StuckEngine(Move);
+
+    impl<T: Game> Engine<T> for StuckEngine {
+        fn choose_move(&mut self, _board: &T) -> Move {
+            self.0
+        }
+
+        fn name(&self) -> Cow<'static, str> {
+            Cow::Borrowed("Stuck")
+        }
+    }
+
+    #[test]
+    fn test_ai_game_aborts_at_move_cap() {
+        let mut board = SmallBoard::new();
+        let mut engine_x = StuckEngine(Move::Base(0, 0));
+        let mut engine_o = StuckEngine(Move::Base(0, 0));
+        let result = play_ai_game(
+            &mut board,
+            Mark::X,
+            &mut engine_x,
+            &mut engine_o,
+            MAX_MOVES_CLASSIC,
+        );
+
+        assert_eq!(
+            result,
+            Err(MoveCapExceeded {
+                max_moves: MAX_MOVES_CLASSIC
+            })
+        );
+        assert_eq!(board.get(0, 0), Some(Mark::X));
+    }
+
+    #[test]
+    fn test_ai_game_finishes_within_cap() {
+        let mut board = BigBoard::new();
+        let result = play_ai_game(
+            &mut board,
+            Mark::X,
+            &mut RandomAI,
+            &mut RandomAI,
+            MAX_MOVES_ULTIMATE,
+        );
+        assert_ne!(result.unwrap(), GameState::Playing);
+    }
+
+    #[test]
+    fn test_boxed_engines_play_full_games() {
+        let mut engines: Vec<Box<dyn Engine<SmallBoard>>> = vec![
+            Box::new(RandomAI),
+            Box::new(SimpleAi::new(Mark::X)),
+            Box::new(minimax::MinimaxAi::new(Mark::X)),
+            Box::new(MCTSAi::with_rounds(SmallBoard::new(), Mark::X, 50)),
+            Box::new(AI::Medium(SimpleAi::new(Mark::X))),
+        ];
+        for engine in &mut engines {
+            for _ in 0..2 {
+                engine.reset();
+                let mut board = SmallBoard::new();
+                let result = play_ai_game(
+                    &mut board,
+                    Mark::X,
+                    engine.as_mut(),
+                    &mut RandomAI,
+                    MAX_MOVES_CLASSIC,
+                );
+                assert_ne!(result.unwrap(), GameState::Playing, "{}", engine.name());
+            }
+        }
+
+        let mut engine: Box<dyn Engine<BigBoard>> = Box::new(AI::StrongUTT(MCTSAi::with_rounds(
+            BigBoard::new(),
+            Mark::O,
+            10,
+        )));
+        let mut board = BigBoard::new();
+        let result = play_ai_game(
+            &mut board,
+            Mark::X,
+            &mut RandomAI,
+            engine.as_mut(),
+            MAX_MOVES_ULTIMATE,
+        );
+        assert_ne!(result.unwrap(), GameState::Playing);
+    }
+
+    #[test]
+    fn test_engine_names() {
+        assert_eq!(AI::Weak(Mark::X).name(), "Random");
+        assert_eq!(AI::Medium(SimpleAi::new(Mark::X)).name(), "Simple");
+        assert_eq!(
+            AI::StrongTTT(MCTSAi::new(SmallBoard::new(), Mark::X)).name(),
+            "MCTS (1000)"
+        );
+        assert_eq!(
+            AI::StrongUTT(MCTSAi::new(BigBoard::new(), Mark::O)).name(),
+            "MCTS (1000)"
+        );
+    }
 }
@@ -0,0 +1,155 @@
+use crate::ai::simple::SimpleAi;
+use crate::ai::{Engine, Game, Move};
+use crate::game::base::SmallBoard;
+use crate::game::{Board, Mark, winning_move};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Classic position seen from the player to move: `Some(true)` marks their own
+/// cells and `Some(false)` their opponent's.
+type Pattern = [Option<bool>; 9];
+
+/// Moves chosen by a player, counted by the position they were played in.
+///
+/// Positions are stored from the mover's point of view, so a preference
+/// learned while playing X also applies when the same shape arises for O.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoveMemory {
+    counts: HashMap<Pattern, HashMap<Move, u32>>,
+}
+
+impl MoveMemory {
+    /// Records that `mark` played `mv` on `board`.
+    pub fn record(&mut self, board: &SmallBoard, mark: Mark, mv: Move) {
+        *self
+            .counts
+            .entry(pattern(board, mark))
+            .or_default()
+            .entry(mv)
+            .or_default() += 1;
+    }
+
+    /// Finds the legal move most often played by the recorded player in this position.
+    ///
+    /// Ties are broken by preferring the earliest cell in row-major order.
+    ///
+    /// # Returns
+    /// The favourite move, or None if the position was never seen.
+    pub fn favourite(&self, board: &SmallBoard, mark: Mark) -> Option<Move> {
+        let moves = self.counts.get(&pattern(board, mark))?;
+        board
+            .get_possible_moves()
+            .into_iter()
+            .filter_map(|mv| moves.get(&mv).map(|count| (mv, *count)))
+            .fold(None, |best: Option<(Move, u32)>, (mv, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((mv, count)),
+            })
+            .map(|(mv, _)| mv)
+    }
+}
+
+/// Builds the pattern of `board` as seen by `mark`.
+fn pattern(board: &SmallBoard, mark: Mark) -> Pattern {
+    std::array::from_fn(|index| {
+        board
+            .get(index / 3, index % 3)
+            .map(|cell_mark| cell_mark == mark)
+    })
+}
+
+/// A classic AI persona that imitates the human it plays against.
+///
+/// Winning and blocking moves are always taken. Otherwise the AI plays the
+/// move the human favoured in the same position, and falls back to
+/// [`SimpleAi`] in positions it has not seen yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImitatorAi {
+    /// Mark controlled by the AI.
+    pub ai_mark: Mark,
+    memory: Rc<RefCell<MoveMemory>>,
+}
+
+impl ImitatorAi {
+    /// Creates an imitator learning from the shared `memory`.
+    ///
+    /// # Arguments
+    /// * `ai_mark` - The mark (X or O) that the AI will play as
+    /// * `memory` - Moves observed so far, kept for the whole session
+    pub fn new(ai_mark: Mark, memory: Rc<RefCell<MoveMemory>>) -> Self {
+        Self { ai_mark, memory }
+    }
+
+    /// Records a move played by the human.
+    pub fn observe(&self, board: &SmallBoard, mark: Mark, mv: Move) {
+        self.memory.borrow_mut().record(board, mark, mv);
+    }
+
+    /// Chooses a move for the AI on the given board.
+    ///
+    /// # Panics
+    /// Panics if there are no available moves on the board.
+    pub fn choose_move(&self, board: &SmallBoard) -> Move {
+        let forced = winning_move(board, self.ai_mark)
+            .or_else(|| winning_move(board, self.ai_mark.switch()));
+        if let Some((row, col)) = forced {
+            return Move::Base(row, col);
+        }
+        self.memory
+            .borrow()
+            .favourite(board, self.ai_mark)
+            .unwrap_or_else(|| SimpleAi::new(self.ai_mark).choose_move(board))
+    }
+}
+
+impl Engine<SmallBoard> for ImitatorAi {
+    fn choose_move(&mut self, board: &SmallBoard) -> Move {
+        ImitatorAi::choose_move(self, board)
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Imitator")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imitator_reproduces_consistent_preference() {
+        let memory = Rc::new(RefCell::new(MoveMemory::default()));
+        let ai = ImitatorAi::new(Mark::O, memory.clone());
+
+        let mut board = SmallBoard::new();
+        board.set(1, 1, Some(Mark::X));
+        for _ in 0..3 {
+            ai.observe(&board, Mark::O, Move::Base(2, 1));
+        }
+        ai.observe(&board, Mark::O, Move::Base(0, 0));
+
+        assert_eq!(ai.choose_move(&board), Move::Base(2, 1));
+
+        // The human played O there, so the pattern also matches X facing an O center.
+        let mut mirrored = SmallBoard::new();
+        mirrored.set(1, 1, Some(Mark::O));
+        let ai_x = ImitatorAi::new(Mark::X, memory);
+        assert_eq!(ai_x.choose_move(&mirrored), Move::Base(2, 1));
+    }
+
+    #[test]
+    fn test_imitator_still_blocks_a_line() {
+        let memory = Rc::new(RefCell::new(MoveMemory::default()));
+        let ai = ImitatorAi::new(Mark::O, memory);
+
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+        ai.observe(&board, Mark::O, Move::Base(2, 2));
+
+        assert_eq!(ai.choose_move(&board), Move::Base(0, 2));
+    }
+}
@@ -1,11 +1,27 @@
-use crate::ai::{Game, Move};
+use crate::ai::rng::with_rng;
+use crate::ai::{Engine, Game, Move};
 use crate::game::{GameState, Mark};
 use rand::prelude::*;
 use rand::seq::SliceRandom;
+use std::borrow::Cow;
 use std::f32::consts::SQRT_2;
+use std::time::{Duration, Instant};
 
-/// Number of MCTS simulation rounds per move decision.
-const N_ROUNDS: i16 = 1000;
+/// Default number of MCTS simulation rounds per move decision.
+pub const DEFAULT_ROUNDS: u32 = 1000;
+/// Number of rounds run between two clock checks of a timed search.
+const ROUNDS_PER_CLOCK_CHECK: u32 = 16;
+/// Default largest number of nodes in the tree of an AI.
+///
+/// Every node holds a copy of its board and its legal moves, so a full tree
+/// of Ultimate boards takes a few tens of megabytes.
+pub const DEFAULT_MAX_NODES: usize = 200_000;
+/// Largest tree kept by [`MCTSAi::reset`] when the tree is reused across games.
+///
+/// Every node holds a copy of its board and its legal moves, so this many
+/// nodes take a few megabytes for classic boards and a few tens of megabytes
+/// for Ultimate ones. A larger tree is discarded instead.
+pub const MAX_REUSED_NODES: usize = 100_000;
 
 /// An AI opponent that uses Monte Carlo Tree Search (MCTS) to select moves.
 ///
@@ -23,6 +39,25 @@ where
     root_id: usize,
     /// The mark (`X` or `O`) this AI plays as.
     pub ai_mark: Mark,
+    /// Estimated winning chance of the most recently chosen move.
+    last_evaluation: Option<f32>,
+    /// Number of simulation rounds per move decision.
+    rounds: u32,
+    /// Generator driving move shuffling and playouts, boxed to keep the AI small.
+    rng: Box<StdRng>,
+    /// Seed given to [`MCTSAi::with_seed`], reused when the tree is reset.
+    seed: Option<u64>,
+    /// Whether [`MCTSAi::reset`] keeps the tree for the next game.
+    reuse_tree: bool,
+    /// Number of nodes past which the search stops expanding the tree.
+    max_nodes: usize,
+    /// Largest gap in winning chance to the best move for a move to be picked.
+    variety_margin: f32,
+    /// Whether known opening positions are answered from the board's book.
+    opening_book: bool,
+    /// Whether the rounds of a move decision run on every core.
+    #[cfg(feature = "parallel")]
+    parallel: bool,
 }
 
 impl<T> MCTSAi<T>
@@ -31,33 +66,373 @@ where
 {
     /// Creates a new `MCTSAi` with the given starting board and mark.
     ///
-    /// The tree is initialized with a single root node for `board`.
-    /// The first active player is always assumed to be `Mark::X`.
+    /// The tree is initialized with a single root node for `board`, whose
+    /// active player is inferred from the board and defaults to `Mark::X`.
+    /// It is corrected by the first call to [`MCTSAi::choose_move`].
     pub fn new(board: T, ai_mark: Mark) -> Self {
+        Self::with_rounds(board, ai_mark, DEFAULT_ROUNDS)
+    }
+
+    /// Creates a new `MCTSAi` running `rounds` simulations per move.
+    ///
+    /// Fewer rounds make the AI faster but weaker.
+    ///
+    /// # Arguments
+    /// * `board` - The starting board
+    /// * `ai_mark` - The mark (X or O) that the AI will play as
+    /// * `rounds` - Number of simulation rounds per move decision
+    pub fn with_rounds(board: T, ai_mark: Mark, rounds: u32) -> Self {
+        Self::build(board, ai_mark, rounds, None)
+    }
+
+    /// Creates a new `MCTSAi` whose search is driven by a generator seeded with `seed`.
+    ///
+    /// Two AIs created with the same seed choose the same moves when given
+    /// the same boards, independently of any other engine.
+    ///
+    /// # Arguments
+    /// * `board` - The starting board
+    /// * `ai_mark` - The mark (X or O) that the AI will play as
+    /// * `seed` - The seed of the AI's generator
+    pub fn with_seed(board: T, ai_mark: Mark, seed: u64) -> Self {
+        Self::build(board, ai_mark, DEFAULT_ROUNDS, Some(seed))
+    }
+
+    fn build(board: T, ai_mark: Mark, rounds: u32, seed: Option<u64>) -> Self {
+        let mut rng = new_rng(seed);
+        let active_player = board.current_player().unwrap_or(Mark::X);
         Self {
-            nodes: vec![Node::new(board, Mark::X, None)],
+            nodes: vec![Node::new(board, active_player, None, &mut rng)],
             root_id: 0,
             ai_mark,
+            last_evaluation: None,
+            rounds,
+            rng: Box::new(rng),
+            seed,
+            reuse_tree: false,
+            max_nodes: DEFAULT_MAX_NODES,
+            variety_margin: 0.0,
+            opening_book: true,
+            #[cfg(feature = "parallel")]
+            parallel: false,
         }
     }
 
+    /// Returns the number of simulation rounds per move decision.
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// Sets the number of simulation rounds used by the next move decisions.
+    pub fn set_rounds(&mut self, rounds: u32) {
+        self.rounds = rounds;
+    }
+
+    /// Returns the number of nodes past which the tree stops growing.
+    pub fn max_nodes(&self) -> usize {
+        self.max_nodes
+    }
+
+    /// Sets the number of nodes past which the tree stops growing.
+    ///
+    /// Once the cap is reached, rounds keep running playouts from the leaves
+    /// they select without expanding them. Only the expansion of a new root
+    /// can go past the cap, by at most one node per legal move.
+    pub fn set_max_nodes(&mut self, max_nodes: usize) {
+        self.max_nodes = max_nodes;
+    }
+
+    /// Returns how far below the best estimate a move can be and still be picked.
+    pub fn variety_margin(&self) -> f32 {
+        self.variety_margin
+    }
+
+    /// Sets how far below the best estimated winning chance a move can be
+    /// and still be picked, to vary the openings.
+    ///
+    /// Among the moves within `margin` of the best one, the move is drawn at
+    /// random, weighted by its estimated winning chance. The default of zero
+    /// always plays the best move.
+    pub fn set_variety_margin(&mut self, margin: f32) {
+        self.variety_margin = margin.max(0.0);
+    }
+
+    /// Returns whether the tree is kept across games.
+    pub fn reuses_tree(&self) -> bool {
+        self.reuse_tree
+    }
+
+    /// Sets whether [`MCTSAi::reset`] keeps the tree for the next game.
+    ///
+    /// A kept tree carries the statistics of earlier games, so the openings
+    /// start from the simulations already run for them. The tree is still
+    /// discarded once it holds more than [`MAX_REUSED_NODES`] nodes.
+    pub fn set_reuse_tree(&mut self, reuse: bool) {
+        self.reuse_tree = reuse;
+    }
+
+    /// Returns whether opening positions are answered from the opening book.
+    pub fn uses_opening_book(&self) -> bool {
+        self.opening_book
+    }
+
+    /// Sets whether the move decisions consult the opening book of the board.
+    ///
+    /// On by default. A position in the book is answered with its book move
+    /// without any search, and [`MCTSAi::last_evaluation`] is then None.
+    /// Boards without a book, like classic ones, always run the search.
+    pub fn set_opening_book(&mut self, enabled: bool) {
+        self.opening_book = enabled;
+    }
+
+    /// Returns whether move decisions run their rounds on every core.
+    #[cfg(feature = "parallel")]
+    pub fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    /// Sets whether [`MCTSAi::choose_move`] runs its rounds on every core.
+    ///
+    /// The parallel search grows one tree per thread of the rayon pool from
+    /// the current board, then adds their statistics for each move to the
+    /// root of this tree. A seeded AI only repeats its moves on machines
+    /// with the same number of threads.
+    #[cfg(feature = "parallel")]
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
     /// Chooses the best move for the current `board` state.
     ///
-    /// Runs `N_ROUNDS` of selection → simulation → back-propagation, then
+    /// Runs the configured number of rounds of selection → simulation → back-propagation, then
     /// returns the child move with the highest estimated winning chance.
     /// The tree root is advanced to the chosen child for future reuse.
+    /// Opening positions in the book are answered without searching.
     ///
     /// # Panics
     /// Panics if there are no legal moves available.
     pub fn choose_move(&mut self, board: &T) -> Move {
+        self.enter_board(board);
+        if let Some(book_move) = self.play_book_move() {
+            return book_move;
+        }
+        self.search();
+        self.best_move()
+    }
+
+    /// Chooses the best move for the current `board` state using a node budget.
+    ///
+    /// Instead of a fixed number of rounds, the search runs until roughly
+    /// `max_new_nodes` nodes have been added to the tree during this turn.
+    /// The budget can be exceeded by at most one expansion, and the search
+    /// stops early if the reachable tree is exhausted.
+    ///
+    /// # Panics
+    /// Panics if there are no legal moves available.
+    pub fn choose_move_node_limited(&mut self, board: &T, max_new_nodes: usize) -> Move {
+        self.enter_board(board);
+
+        // Explore and expand tree until the node budget is spent
+        let starting_nodes = self.nodes.len();
+        let mut idle_rounds = 0;
+        while self.nodes.len() - starting_nodes < max_new_nodes && idle_rounds < self.rounds {
+            let nodes_before = self.nodes.len();
+            self.run_round();
+            if self.nodes.len() == nodes_before {
+                idle_rounds += 1;
+            } else {
+                idle_rounds = 0;
+            }
+        }
+
+        self.best_move()
+    }
+
+    /// Chooses the best move for the current `board` state within a time budget.
+    ///
+    /// Rounds run in small batches until `budget` has elapsed, so the search
+    /// overshoots it by at most one batch. At least one batch always runs.
+    ///
+    /// # Panics
+    /// Panics if there are no legal moves available.
+    pub fn choose_move_timed(&mut self, board: &T, budget: Duration) -> Move {
+        let started = Instant::now();
+        self.enter_board(board);
+
+        // Explore and expand tree until the budget is spent
+        loop {
+            for _ in 0..ROUNDS_PER_CLOCK_CHECK {
+                self.run_round();
+            }
+            if started.elapsed() >= budget {
+                break;
+            }
+        }
+
+        self.best_move()
+    }
+
+    /// Runs the normal search on `board` and ranks every legal move by its
+    /// estimated winning chance for the AI.
+    ///
+    /// Unlike [`Self::choose_move`], the root stays on `board`, so the tree
+    /// can keep growing if the position is analyzed again.
+    ///
+    /// # Returns
+    /// Each legal move with its winning chance, best first. Moves the search
+    /// never visited have a chance of 0.0.
+    pub fn evaluate_moves(&mut self, board: &T) -> Vec<(Move, f32)> {
+        self.enter_board(board);
+        self.search();
+        if self.nodes[self.root_id].children.is_none() {
+            self.make_children(self.root_id);
+        }
+
+        let root = &self.nodes[self.root_id];
+        let mut ranking: Vec<(Move, f32)> = root
+            .possible_moves
+            .iter()
+            .zip(root.children.as_ref().unwrap())
+            .map(|(mv, child_id)| (*mv, self.nodes[*child_id].winning_chance(self.ai_mark)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranking
+    }
+
+    /// Moves the root to the node of `board` and frees the nodes that can no
+    /// longer be reached from it.
+    fn enter_board(&mut self, board: &T) {
+        self.derive_starting_mark(board);
         let new_root_id = self.find_state(board);
         self.reroot(new_root_id);
+        self.prune();
+    }
 
-        // Explore and expand tree
-        for _ in 0..N_ROUNDS {
-            let selected_id = self.selection();
-            let result = self.simulate(selected_id);
-            self.back_propagate(selected_id, result);
+    /// Advances the root to the book move of the root board, if the book is
+    /// used and has one.
+    fn play_book_move(&mut self) -> Option<Move> {
+        if !self.opening_book {
+            return None;
+        }
+        let book_move = self.nodes[self.root_id].board.book_move()?;
+        let index = self.nodes[self.root_id]
+            .possible_moves
+            .iter()
+            .position(|mv| *mv == book_move)?;
+        if self.nodes[self.root_id].children.is_none() {
+            self.make_children(self.root_id);
+        }
+        self.last_evaluation = None;
+        self.reroot(self.nodes[self.root_id].children.as_ref().unwrap()[index]);
+        Some(book_move)
+    }
+
+    /// Explores and expands the tree for the configured number of rounds,
+    /// on the thread pool when the search is parallel.
+    fn search(&mut self) {
+        #[cfg(feature = "parallel")]
+        if self.parallel {
+            self.run_parallel_rounds();
+            return;
+        }
+
+        for _ in 0..self.rounds {
+            self.run_round();
+        }
+    }
+
+    /// Runs a single round of selection → simulation → back-propagation.
+    fn run_round(&mut self) {
+        let selected_id = self.selection();
+        let result = self.simulate(selected_id);
+        self.back_propagate(selected_id, result);
+    }
+
+    /// Splits the rounds between independent trees grown on the rayon thread
+    /// pool and adds the statistics of their root moves to the root children.
+    ///
+    /// Each worker owns its tree and generator, so the threads share nothing
+    /// and the arena is only modified once they have all finished.
+    #[cfg(feature = "parallel")]
+    fn run_parallel_rounds(&mut self) {
+        use rayon::prelude::*;
+
+        if self.nodes[self.root_id].children.is_none() {
+            self.make_children(self.root_id);
+        }
+        let workers = rayon::current_num_threads().max(1);
+        let rounds = self.rounds.div_ceil(workers as u32);
+        let max_nodes = self.max_nodes / workers;
+        let root = &self.nodes[self.root_id];
+        let (active_player, ai_mark) = (root.active_player, self.ai_mark);
+        let jobs: Vec<(T, u64)> = (0..workers)
+            .map(|_| (root.board.clone(), self.rng.random()))
+            .collect();
+
+        let statistics: Vec<Vec<(Move, f32, f32)>> = jobs
+            .into_par_iter()
+            .map(|(board, seed)| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut worker = Self {
+                    nodes: vec![Node::new(board, active_player, None, &mut rng)],
+                    root_id: 0,
+                    ai_mark,
+                    last_evaluation: None,
+                    rounds,
+                    rng: Box::new(rng),
+                    seed: Some(seed),
+                    reuse_tree: false,
+                    max_nodes,
+                    variety_margin: 0.0,
+                    opening_book: false,
+                    parallel: false,
+                };
+                for _ in 0..rounds {
+                    worker.run_round();
+                }
+                worker.root_statistics()
+            })
+            .collect();
+
+        for (mv, plays, wins) in statistics.into_iter().flatten() {
+            let root = &self.nodes[self.root_id];
+            let Some(index) = root
+                .possible_moves
+                .iter()
+                .position(|root_move| *root_move == mv)
+            else {
+                continue;
+            };
+            let child_id = root.children.as_ref().unwrap()[index];
+            self.nodes[child_id].plays += plays;
+            self.nodes[child_id].wins += wins;
+            // A playout won by the child's player is lost by the root's one.
+            self.nodes[self.root_id].plays += plays;
+            self.nodes[self.root_id].wins += plays - wins;
+        }
+    }
+
+    /// Returns each root move with the plays and wins of its child.
+    #[cfg(feature = "parallel")]
+    fn root_statistics(&self) -> Vec<(Move, f32, f32)> {
+        let root = &self.nodes[self.root_id];
+        let Some(children) = &root.children else {
+            return Vec::new();
+        };
+        root.possible_moves
+            .iter()
+            .zip(children)
+            .map(|(mv, child_id)| (*mv, self.nodes[*child_id].plays, self.nodes[*child_id].wins))
+            .collect()
+    }
+
+    /// Returns the root move with the best probability of winning and advances the root to it.
+    ///
+    /// # Panics
+    /// Panics if there are no legal moves available.
+    fn best_move(&mut self) -> Move {
+        if self.nodes[self.root_id].children.is_none() {
+            self.make_children(self.root_id);
         }
 
         // Find move with the best probability of winning
@@ -76,11 +451,36 @@ where
             panic!("No available moves found by MCTSAi");
         }
 
+        if self.variety_margin > 0.0 {
+            (best_move_id, best_chance) = self.pick_near_best(best_move_id, best_chance);
+        }
+
         let best_move = self.nodes[self.root_id].possible_moves[best_move_id];
+        self.last_evaluation = Some(best_chance);
         self.reroot(self.nodes[self.root_id].children.as_ref().unwrap()[best_move_id]);
         best_move
     }
 
+    /// Draws a root move within the variety margin of `best_chance`, weighted
+    /// by its winning chance.
+    ///
+    /// # Returns
+    /// The index of the move among the root moves and its winning chance, or
+    /// the best move if no move has been visited.
+    fn pick_near_best(&mut self, best_move_id: usize, best_chance: f32) -> (usize, f32) {
+        let children_ids = self.nodes[self.root_id].children.as_ref().unwrap();
+        let candidates: Vec<(usize, f32)> = children_ids
+            .iter()
+            .map(|child_id| self.nodes[*child_id].winning_chance(self.ai_mark))
+            .enumerate()
+            .filter(|(_, chance)| *chance > 0.0 && *chance >= best_chance - self.variety_margin)
+            .collect();
+        match candidates.choose_weighted(&mut self.rng, |(_, chance)| *chance) {
+            Ok(candidate) => *candidate,
+            Err(_) => (best_move_id, best_chance),
+        }
+    }
+
     /// Selects a node to simulate using the UCB1 policy.
     ///
     /// Traverses from the root, preferring unexplored nodes first, then
@@ -95,12 +495,17 @@ where
             }
 
             if self.nodes[starting_node].children.is_none() {
+                // Select as a leaf once the tree is full
+                let new_nodes = self.nodes[starting_node].possible_moves.len();
+                if self.nodes.len() + new_nodes > self.max_nodes {
+                    return starting_node;
+                }
                 self.make_children(starting_node);
             }
 
             let children = self.nodes[starting_node].children.as_ref().unwrap();
             // Select if it has no children (terminal node)
-            if children.len() == 0 {
+            if children.is_empty() {
                 return starting_node;
             }
 
@@ -133,9 +538,8 @@ where
         let mut board = self.nodes[node_id].board.clone();
         while board.get_state() == GameState::Playing {
             let possible_moves = board.get_possible_moves();
-            let mut rng = rand::rng();
-            let mv = possible_moves.choose(&mut rng).unwrap();
-            board.play(mv, active_player);
+            let mv = *possible_moves.choose(&mut self.rng).unwrap();
+            board.play(&mv, active_player);
             active_player = active_player.switch();
         }
         board.get_state()
@@ -176,6 +580,7 @@ where
                 new_board,
                 self.nodes[parent_id].active_player.switch(),
                 Some(parent_id),
+                &mut self.rng,
             ));
         }
 
@@ -190,10 +595,55 @@ where
         self.root_id = new_root_id;
     }
 
+    /// Removes the nodes unreachable from the root and compacts the arena.
+    ///
+    /// The first node keeps the starting board for `reset`, so it is always
+    /// kept, without its children unless the tree is reused across games.
+    /// Kept nodes stay in the same order, with their indices remapped.
+    fn prune(&mut self) {
+        let mut kept = vec![false; self.nodes.len()];
+        let mut stack = vec![self.root_id];
+        if self.reuse_tree {
+            stack.push(0);
+        }
+        while let Some(node_id) = stack.pop() {
+            if !kept[node_id] {
+                kept[node_id] = true;
+                stack.extend(self.nodes[node_id].children.iter().flatten());
+            }
+        }
+        kept[0] = true;
+        if kept.iter().all(|&is_kept| is_kept) {
+            return;
+        }
+
+        let mut new_ids = vec![None; kept.len()];
+        let mut next_id = 0;
+        for (new_id, &is_kept) in new_ids.iter_mut().zip(&kept) {
+            if is_kept {
+                *new_id = Some(next_id);
+                next_id += 1;
+            }
+        }
+        let nodes = std::mem::take(&mut self.nodes);
+        self.nodes = nodes
+            .into_iter()
+            .zip(kept)
+            .filter_map(|(node, is_kept)| is_kept.then_some(node))
+            .map(|mut node| {
+                node.parent = node.parent.and_then(|parent_id| new_ids[parent_id]);
+                node.children = node.children.and_then(|children| {
+                    children.iter().map(|child_id| new_ids[*child_id]).collect()
+                });
+                node
+            })
+            .collect();
+        self.root_id = new_ids[self.root_id].unwrap();
+    }
+
     /// Locates the node matching `board` among the root or its direct children.
     ///
-    /// # Panics
-    /// Panics if the board state is not found (indicates an illegal board transition).
+    /// A board found elsewhere gets a new detached node, with the AI to move.
     fn find_state(&mut self, board: &T) -> usize {
         if self.nodes[self.root_id].board == *board {
             return self.root_id;
@@ -207,13 +657,43 @@ where
                 return *child_id;
             }
         }
-        panic!("Board state following move was not found in MCTS.");
+        // The board is not reachable from the root, e.g. because moves were
+        // taken back, so a new tree is grown from it. The first node keeps the
+        // starting board for `reset`.
+        self.nodes
+            .push(Node::new(board.clone(), self.ai_mark, None, &mut self.rng));
+        self.nodes.len() - 1
+    }
+
+    /// Sets the root node's active player before the tree is first expanded.
+    ///
+    /// The AI is only asked to move on its own turn, so the root belongs to
+    /// `ai_mark` if `board` is the root board, or to the opponent if the
+    /// opponent has already moved from it.
+    ///
+    /// A tree kept by [`MCTSAi::reset`] is discarded if the other player
+    /// starts this game, since its statistics belong to the other order.
+    fn derive_starting_mark(&mut self, board: &T) {
+        let starting_mark = if self.nodes[0].board == *board {
+            self.ai_mark
+        } else {
+            self.ai_mark.switch()
+        };
+        if self.nodes.len() != 1 {
+            let kept_tree = self.root_id == 0;
+            if !kept_tree || self.nodes[0].active_player == starting_mark {
+                return;
+            }
+            self.clear_tree();
+        }
+        self.nodes[0].active_player = starting_mark;
     }
 
     /// Swaps the root node's active player.
     ///
     /// Must be called before any tree exploration (i.e. when the tree has
-    /// exactly one node). Use this when the AI plays as `O` and moves second.
+    /// exactly one node). Choosing a move derives the starting mark on its
+    /// own, so this is only needed to explore the tree manually.
     ///
     /// # Panics
     /// Panics if the tree has already been expanded.
@@ -224,12 +704,79 @@ where
         self.nodes[0].active_player = self.nodes[0].active_player.switch();
     }
 
+    /// Returns the estimated winning chance of the most recently chosen move.
+    ///
+    /// The chance is given from the perspective of `ai_mark`, or `None` if no
+    /// move has been chosen since the last reset.
+    pub fn last_evaluation(&self) -> Option<f32> {
+        self.last_evaluation
+    }
+
     /// Resets the tree to its initial single-node state, discarding all exploration.
+    ///
+    /// With [`MCTSAi::set_reuse_tree`] enabled, the tree is kept instead and
+    /// its root moved back to the starting board, unless it holds more than
+    /// [`MAX_REUSED_NODES`] nodes.
+    ///
+    /// The generator restarts from the seed given to [`MCTSAi::with_seed`],
+    /// or else is drawn again from the shared AI generator.
     pub fn reset(&mut self) {
+        *self.rng = new_rng(self.seed);
+        self.last_evaluation = None;
+        if self.reuse_tree && self.nodes.len() <= MAX_REUSED_NODES {
+            self.restore_root();
+        } else {
+            self.clear_tree();
+        }
+    }
+
+    /// Replaces the tree with a single node for the starting board.
+    fn clear_tree(&mut self) {
         let clean_board = self.nodes[0].board.clone();
-        self.nodes = vec![Node::new(clean_board, Mark::X, None)];
+        let active_player = clean_board.current_player().unwrap_or(Mark::X);
+        self.nodes = vec![Node::new(clean_board, active_player, None, &mut self.rng)];
         self.root_id = 0;
     }
+
+    /// Makes the starting board the root again and reattaches the roots
+    /// detached during the game, so playouts update the whole path again.
+    fn restore_root(&mut self) {
+        for parent_id in 0..self.nodes.len() {
+            for child_id in self.nodes[parent_id].children.clone().unwrap_or_default() {
+                self.nodes[child_id].parent = Some(parent_id);
+            }
+        }
+        self.root_id = 0;
+    }
+}
+
+impl<T> Engine<T> for MCTSAi<T>
+where
+    T: Game + Clone + PartialEq,
+{
+    fn choose_move(&mut self, board: &T) -> Move {
+        MCTSAi::choose_move(self, board)
+    }
+
+    fn reset(&mut self) {
+        MCTSAi::reset(self)
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Owned(format!("MCTS ({})", self.rounds))
+    }
+}
+
+/// Creates the generator of an AI, seeded with `seed` if given.
+///
+/// Without a seed the generator is drawn from the shared AI generator, which
+/// starts from entropy but follows [`seed_rng`](crate::ai::rng::seed_rng) so a
+/// game can still be restarted with the same moves.
+fn new_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => with_rng(StdRng::from_rng),
+    }
 }
 
 /// A single node in the MCTS tree.
@@ -254,10 +801,11 @@ where
     T: Game + Clone,
 {
     /// Creates a new leaf node for `board` with `active_player` to move.
-    pub fn new(board: T, active_player: Mark, parent: Option<usize>) -> Self {
+    ///
+    /// The legal moves are shuffled with `rng`.
+    pub fn new(board: T, active_player: Mark, parent: Option<usize>, rng: &mut StdRng) -> Self {
         let mut possible_moves = board.get_possible_moves();
-        let mut rng = rand::rng();
-        possible_moves.shuffle(&mut rng);
+        possible_moves.shuffle(rng);
         Node {
             parent,
             children: None,
@@ -292,7 +840,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ai::minimax::move_value;
+    use crate::game::Board;
     use crate::game::base::SmallBoard;
+    use crate::game::ultimate::BigBoard;
 
     fn make_ai(mark: Mark) -> MCTSAi<SmallBoard> {
         MCTSAi::new(SmallBoard::new(), mark)
@@ -327,6 +878,28 @@ mod tests {
         ai.switch_starting_mark();
     }
 
+    #[test]
+    fn test_same_seed_chooses_same_moves() {
+        let mut first = MCTSAi::with_seed(SmallBoard::new(), Mark::X, 7);
+        let mut second = MCTSAi::with_seed(SmallBoard::new(), Mark::X, 7);
+        let mut board = SmallBoard::new();
+        while board.state == GameState::Playing {
+            let mv = first.choose_move(&board);
+            // Draws from the shared generator must not affect seeded AIs.
+            with_rng(|rng| rng.random::<u64>());
+            assert_eq!(second.choose_move(&board), mv);
+            board.play(&mv, Mark::X);
+            if let Some(reply) = board.get_possible_moves().first() {
+                board.play(reply, Mark::O);
+            }
+        }
+
+        first.reset();
+        second.reset();
+        let board = SmallBoard::new();
+        assert_eq!(first.choose_move(&board), second.choose_move(&board));
+    }
+
     #[test]
     fn test_reset_collapses_tree_to_single_node() {
         let mut ai = make_ai(Mark::X);
@@ -343,6 +916,153 @@ mod tests {
         assert_eq!(ai.nodes[0].plays, 0.0);
     }
 
+    /// Plays a classic game where the AI is X and O always takes the first free cell.
+    fn play_game(ai: &mut MCTSAi<SmallBoard>) {
+        let mut board = SmallBoard::new();
+        while board.state == GameState::Playing {
+            board.play(&ai.choose_move(&board), Mark::X);
+            if let Some(reply) = board.get_possible_moves().first() {
+                board.play(reply, Mark::O);
+            }
+        }
+    }
+
+    /// Returns the best winning chance among the root's children, as known before searching.
+    fn opening_estimate(ai: &MCTSAi<SmallBoard>) -> f32 {
+        ai.nodes[ai.root_id]
+            .children
+            .iter()
+            .flatten()
+            .map(|child_id| ai.nodes[*child_id].winning_chance(ai.ai_mark))
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_reused_tree_keeps_opening_statistics() {
+        let mut ai = MCTSAi::with_seed(SmallBoard::new(), Mark::X, 3);
+        ai.set_rounds(200);
+        ai.set_reuse_tree(true);
+        for _ in 0..3 {
+            play_game(&mut ai);
+            ai.reset();
+        }
+        assert_eq!(ai.root_id, 0);
+        assert!(opening_estimate(&ai) > 0.0);
+        let plays = ai.nodes[0].plays;
+        ai.choose_move(&SmallBoard::new());
+        assert!(ai.nodes[0].plays > plays, "playouts reach the kept root");
+
+        ai.set_reuse_tree(false);
+        ai.reset();
+        assert_eq!(ai.nodes.len(), 1);
+        assert_eq!(opening_estimate(&ai), 0.0);
+    }
+
+    #[test]
+    fn test_reused_tree_is_discarded_when_the_other_player_starts() {
+        let mut ai = MCTSAi::with_seed(SmallBoard::new(), Mark::O, 3);
+        ai.set_rounds(50);
+        ai.set_reuse_tree(true);
+        ai.choose_move(&SmallBoard::new());
+        ai.reset();
+        assert!(ai.nodes.len() > 1);
+
+        let mut board = SmallBoard::new();
+        board.play(&Move::Base(1, 1), Mark::X);
+        ai.choose_move(&board);
+        assert_eq!(ai.nodes[0].active_player, Mark::X);
+    }
+
+    /// Returns the plays and wins of the root's subtree, parents before children.
+    fn subtree_statistics(ai: &MCTSAi<SmallBoard>) -> Vec<(f32, f32)> {
+        let mut statistics = Vec::new();
+        let mut stack = vec![ai.root_id];
+        while let Some(node_id) = stack.pop() {
+            let node = &ai.nodes[node_id];
+            statistics.push((node.plays, node.wins));
+            stack.extend(node.children.iter().flatten());
+        }
+        statistics
+    }
+
+    #[test]
+    fn test_prune_keeps_reachable_subtree_statistics() {
+        let mut ai = MCTSAi::with_seed(SmallBoard::new(), Mark::X, 5);
+        ai.set_rounds(300);
+        let mv = ai.choose_move(&SmallBoard::new());
+        let statistics = subtree_statistics(&ai);
+        let nodes_before = ai.nodes.len();
+
+        ai.prune();
+        assert!(ai.nodes.len() < nodes_before);
+        assert_eq!(ai.nodes.len(), statistics.len() + 1);
+        assert_eq!(subtree_statistics(&ai), statistics);
+        assert_eq!(ai.nodes[0].board, SmallBoard::new());
+
+        let mut board = SmallBoard::new();
+        board.play(&mv, Mark::X);
+        assert_eq!(ai.nodes[ai.root_id].board, board);
+        let root_id = ai.root_id;
+        assert!(
+            ai.nodes[root_id]
+                .children
+                .iter()
+                .flatten()
+                .all(|child_id| ai.nodes[*child_id].parent == Some(root_id))
+        );
+    }
+
+    #[test]
+    fn test_tree_stays_under_node_cap() {
+        let mut ai = MCTSAi::with_seed(BigBoard::new(), Mark::X, 9);
+        ai.set_rounds(300);
+        ai.set_max_nodes(1000);
+        let mut board = BigBoard::new();
+        while board.state == GameState::Playing {
+            board.play(&ai.choose_move(&board), Mark::X);
+            assert!(ai.nodes.len() <= 1000, "{} nodes", ai.nodes.len());
+            if let Some(reply) = board.get_possible_moves().first() {
+                board.play(reply, Mark::O);
+            }
+        }
+    }
+
+    #[test]
+    fn test_opening_book_answers_without_searching() {
+        // So many rounds would take far too long if they actually ran.
+        let mut ai = MCTSAi::with_rounds(BigBoard::new(), Mark::X, u32::MAX);
+        let started = Instant::now();
+        assert_eq!(ai.choose_move(&BigBoard::new()), Move::Ultimate(1, 1, 1, 1));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(ai.last_evaluation(), None);
+
+        // The tree follows the book move, so the next position is found in it.
+        let mut board = BigBoard::new();
+        board.make_move(1, 1, 1, 1, Mark::X);
+        board.make_move(1, 1, 0, 0, Mark::O);
+        assert_eq!(ai.choose_move(&board), Move::Ultimate(0, 0, 1, 1));
+        assert_eq!(
+            ai.nodes[ai.root_id].board.get_board(0, 0).get(1, 1),
+            Some(Mark::X)
+        );
+    }
+
+    #[test]
+    fn test_out_of_book_positions_are_searched() {
+        let mut board = BigBoard::new();
+        board.make_move(2, 2, 0, 1, Mark::X);
+        let mut ai = MCTSAi::with_rounds(board, Mark::O, 200);
+        let mv = ai.choose_move(&board);
+        assert!(board.get_possible_moves().contains(&mv));
+        assert!(ai.last_evaluation().is_some());
+
+        let mut ai = MCTSAi::with_rounds(BigBoard::new(), Mark::X, 200);
+        ai.set_opening_book(false);
+        assert!(!ai.uses_opening_book());
+        ai.choose_move(&BigBoard::new());
+        assert!(ai.last_evaluation().is_some());
+    }
+
     #[test]
     fn test_choose_move_returns_valid_move_on_empty_board() {
         let board = SmallBoard::new();
@@ -352,15 +1072,232 @@ mod tests {
         assert!(row < 3 && col < 3);
     }
 
+    #[test]
+    fn test_o_moving_first_searches_for_o_without_manual_switch() {
+        let mut board = SmallBoard::new();
+        board.make_move(1, 0, Mark::O);
+        board.make_move(0, 0, Mark::X);
+        board.make_move(1, 1, Mark::O);
+        board.make_move(0, 1, Mark::X);
+
+        let mut ai = MCTSAi::new(board, Mark::O);
+        assert_eq!(ai.choose_move(&board), Move::Base(1, 2));
+
+        let mut ai = make_ai(Mark::O);
+        ai.choose_move(&SmallBoard::new());
+        assert_eq!(ai.nodes[0].active_player, Mark::O);
+    }
+
+    #[test]
+    fn test_moving_second_infers_opponent_started() {
+        let mut board = SmallBoard::new();
+        board.make_move(1, 1, Mark::X);
+
+        let mut ai = make_ai(Mark::O);
+        ai.choose_move(&board);
+        assert_eq!(ai.nodes[0].active_player, Mark::X);
+    }
+
+    #[test]
+    fn test_choose_move_timed_respects_budget() {
+        let board = BigBoard::new();
+        let mut ai = MCTSAi::new(board, Mark::X);
+        let budget = Duration::from_millis(50);
+
+        let started = Instant::now();
+        let mv = ai.choose_move_timed(&board, budget);
+        let elapsed = started.elapsed();
+
+        assert!(elapsed >= budget);
+        assert!(elapsed < budget * 5, "search took {elapsed:?}");
+        assert!(board.get_possible_moves().contains(&mv));
+    }
+
+    #[test]
+    fn test_choose_move_timed_with_tiny_budget_returns_legal_move() {
+        let mut board = SmallBoard::new();
+        board.make_move(0, 0, Mark::X);
+        let mut ai = make_ai(Mark::O);
+        let mv = ai.choose_move_timed(&board, Duration::from_millis(1));
+        assert!(board.get_possible_moves().contains(&mv));
+    }
+
+    #[test]
+    fn test_unreachable_board_starts_new_subtree() {
+        let mut ai = make_ai(Mark::O);
+        let mut board = SmallBoard::new();
+        board.make_move(1, 1, Mark::X);
+        let reply = ai.choose_move(&board);
+        board.play(&reply, Mark::O);
+
+        // Take both moves back and play a different opening.
+        let mut board = SmallBoard::new();
+        board.make_move(0, 0, Mark::X);
+        let mv = ai.choose_move(&board);
+        assert!(board.get_possible_moves().contains(&mv));
+
+        ai.reset();
+        assert_eq!(ai.nodes[0].board, SmallBoard::new());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_and_serial_search_agree_on_fixed_position() {
+        // X can win the top row or must otherwise block O on the middle one.
+        let mut board = SmallBoard::new();
+        for (row, col, mark) in [
+            (0, 0, Mark::X),
+            (1, 0, Mark::O),
+            (0, 1, Mark::X),
+            (1, 1, Mark::O),
+        ] {
+            board.make_move(row, col, mark);
+        }
+        let mut agreements = 0;
+        for seed in 0..8 {
+            let mut serial = MCTSAi::with_seed(SmallBoard::new(), Mark::X, seed);
+            let mut parallel = MCTSAi::with_seed(SmallBoard::new(), Mark::X, seed);
+            parallel.set_parallel(true);
+            assert!(parallel.is_parallel());
+
+            let serial_move = serial.choose_move(&board);
+            let parallel_move = parallel.choose_move(&board);
+            assert_eq!(parallel_move, Move::Base(0, 2));
+            if serial_move == parallel_move {
+                agreements += 1;
+            }
+            let difference =
+                serial.last_evaluation().unwrap() - parallel.last_evaluation().unwrap();
+            assert!(difference.abs() < 0.1, "evaluations differ by {difference}");
+        }
+        assert!(agreements >= 7, "only {agreements} of 8 searches agreed");
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_rounds_merge_every_playout_into_the_root() {
+        let mut ai = MCTSAi::with_rounds(SmallBoard::new(), Mark::X, 200);
+        ai.set_parallel(true);
+        ai.enter_board(&SmallBoard::new());
+        ai.run_parallel_rounds();
+
+        let root = &ai.nodes[ai.root_id];
+        let children = root.children.as_ref().unwrap();
+        let child_plays: f32 = children.iter().map(|id| ai.nodes[*id].plays).sum();
+        let workers = rayon::current_num_threads() as u32;
+        let rounds = 200_u32.div_ceil(workers) * workers;
+        // Each worker spends its first round on its own root.
+        assert_eq!(child_plays, (rounds - workers) as f32);
+        assert_eq!(root.plays, child_plays);
+    }
+
+    #[test]
+    fn test_variety_margin_varies_openings_without_losing_moves() {
+        let mut first_moves = Vec::new();
+        for seed in 0..20 {
+            let mut ai = MCTSAi::with_seed(SmallBoard::new(), Mark::X, seed);
+            ai.set_variety_margin(0.1);
+            let mv = ai.choose_move(&SmallBoard::new());
+            if !first_moves.contains(&mv) {
+                first_moves.push(mv);
+            }
+        }
+        assert!(first_moves.len() > 1, "always opened with {first_moves:?}");
+
+        // O loses with any move that does not block the top row.
+        let mut board = SmallBoard::new();
+        for (row, col, mark) in [(0, 0, Mark::X), (1, 1, Mark::O), (0, 1, Mark::X)] {
+            board.make_move(row, col, mark);
+        }
+        for seed in 0..20 {
+            let mut ai = MCTSAi::with_seed(SmallBoard::new(), Mark::O, seed);
+            ai.set_variety_margin(0.1);
+            let mv = ai.choose_move(&board);
+            assert!(
+                move_value(&board, Mark::O, &mv) >= 0,
+                "seed {seed} played {mv:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_moves_ranks_the_winning_move_first() {
+        // X to move wins only at the top right, completing the top row.
+        let mut board = SmallBoard::new();
+        for (row, col, mark) in [
+            (0, 0, Mark::X),
+            (1, 0, Mark::O),
+            (0, 1, Mark::X),
+            (2, 2, Mark::O),
+        ] {
+            board.make_move(row, col, mark);
+        }
+        let mut ai = MCTSAi::with_seed(board, Mark::X, 7);
+
+        let ranking = ai.evaluate_moves(&board);
+
+        assert_eq!(ranking.len(), 5);
+        assert_eq!(ranking[0].0, Move::Base(0, 2));
+        assert!(ranking.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        // The root stays on the analyzed position.
+        assert_eq!(ai.nodes[ai.root_id].board, board);
+    }
+
+    #[test]
+    fn test_name_includes_round_count() {
+        let ai = make_ai(Mark::X);
+        assert_eq!(ai.name(), format!("MCTS ({DEFAULT_ROUNDS})"));
+
+        let ai = MCTSAi::with_rounds(SmallBoard::new(), Mark::X, 50);
+        assert_eq!(ai.rounds(), 50);
+        assert_eq!(ai.name(), "MCTS (50)");
+    }
+
+    #[test]
+    fn test_single_round_returns_legal_move() {
+        let mut board = SmallBoard::new();
+        board.make_move(1, 1, Mark::X);
+        let mut ai = MCTSAi::with_rounds(SmallBoard::new(), Mark::O, 1);
+        let mv = ai.choose_move(&board);
+        assert!(board.get_possible_moves().contains(&mv));
+    }
+
+    #[test]
+    fn test_more_rounds_raise_best_child_estimate() {
+        let mut board = SmallBoard::new();
+        board.make_move(0, 0, Mark::X);
+        board.make_move(1, 0, Mark::O);
+        board.make_move(0, 1, Mark::X);
+        board.make_move(1, 1, Mark::O);
+
+        let mut few = MCTSAi::with_rounds(board, Mark::X, 1);
+        few.choose_move(&board);
+        let mut many = MCTSAi::with_rounds(board, Mark::X, 1);
+        many.set_rounds(500);
+        assert_eq!(many.choose_move(&board), Move::Base(0, 2));
+
+        assert!(many.last_evaluation().unwrap() > few.last_evaluation().unwrap());
+    }
+
     #[test]
     fn test_winning_chance_unvisited_node_is_zero() {
-        let node = Node::new(SmallBoard::new(), Mark::X, None);
+        let node = Node::new(
+            SmallBoard::new(),
+            Mark::X,
+            None,
+            &mut StdRng::seed_from_u64(0),
+        );
         assert_eq!(node.winning_chance(Mark::X), 0.0);
     }
 
     #[test]
     fn test_winning_chance_active_player_perspective() {
-        let mut node = Node::new(SmallBoard::new(), Mark::X, None);
+        let mut node = Node::new(
+            SmallBoard::new(),
+            Mark::X,
+            None,
+            &mut StdRng::seed_from_u64(0),
+        );
         node.plays = 4.0;
         node.wins = 3.0;
         // Active player is X, asking for X → wins/plays = 0.75
@@ -369,6 +1306,51 @@ mod tests {
         assert!((node.winning_chance(Mark::O) - 0.25).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_last_evaluation_is_recorded_and_reset() {
+        let mut ai = make_ai(Mark::X);
+        assert_eq!(ai.last_evaluation(), None);
+
+        ai.choose_move(&SmallBoard::new());
+        let evaluation = ai.last_evaluation().unwrap();
+        assert!((0.0..=1.0).contains(&evaluation));
+
+        ai.reset();
+        assert_eq!(ai.last_evaluation(), None);
+    }
+
+    #[test]
+    fn test_choose_move_node_limited_respects_budget() {
+        let board = SmallBoard::new();
+        let mut ai = make_ai(Mark::X);
+        let max_new_nodes = 200;
+
+        let nodes_before = ai.nodes.len();
+        let mv = ai.choose_move_node_limited(&board, max_new_nodes);
+        let new_nodes = ai.nodes.len() - nodes_before;
+
+        // A single expansion adds at most 9 children on a classic board
+        assert!(new_nodes >= max_new_nodes);
+        assert!(new_nodes < max_new_nodes + 9);
+        assert!(board.get_possible_moves().contains(&mv));
+    }
+
+    #[test]
+    fn test_choose_move_node_limited_stops_on_exhausted_tree() {
+        let mut board = SmallBoard::new();
+        board.make_move(0, 0, Mark::X);
+        board.make_move(0, 1, Mark::O);
+        board.make_move(0, 2, Mark::X);
+        board.make_move(1, 1, Mark::O);
+        board.make_move(1, 0, Mark::X);
+        board.make_move(1, 2, Mark::O);
+        board.make_move(2, 1, Mark::X);
+
+        let mut ai = MCTSAi::new(board, Mark::O);
+        let mv = ai.choose_move_node_limited(&board, 10_000);
+        assert!(board.get_possible_moves().contains(&mv));
+    }
+
     // --- back_propagate ---
 
     #[test]
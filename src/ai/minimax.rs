@@ -0,0 +1,213 @@
+use crate::ai::{Engine, Game, Move};
+use crate::game::{GameState, Mark};
+use std::borrow::Cow;
+
+/// Bound of the search window, beyond any reachable score.
+const SCORE_BOUND: i8 = 100;
+
+/// A perfect AI that searches the whole game tree with negamax and alpha-beta pruning.
+///
+/// Searching to the end is instant on a classic board, but far too slow
+/// for an Ultimate one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinimaxAi {
+    /// Mark controlled by the AI.
+    pub ai_mark: Mark,
+}
+
+impl MinimaxAi {
+    /// Creates a new MinimaxAi with the given mark.
+    ///
+    /// # Arguments
+    /// * `ai_mark` - The mark (X or O) that the AI will play as
+    pub fn new(ai_mark: Mark) -> Self {
+        Self { ai_mark }
+    }
+
+    /// Chooses the move with the best guaranteed outcome on the given board.
+    ///
+    /// The move never loses a position that can be held. Among equally good
+    /// moves, faster wins and slower losses are preferred, then the first
+    /// move in the board's move order.
+    ///
+    /// # Panics
+    /// Panics if there are no available moves on the board.
+    pub fn choose_move<T>(&self, board: &T) -> Move
+    where
+        T: Game + Clone,
+    {
+        *self
+            .best_moves(board)
+            .first()
+            .expect("No available moves found by MinimaxAi")
+    }
+
+    /// Returns every move with the best guaranteed outcome on the given board.
+    ///
+    /// # Returns
+    /// The moves sharing the best [`move_value`], in the board's move order,
+    /// or no moves if the game is over.
+    pub fn best_moves<T>(&self, board: &T) -> Vec<Move>
+    where
+        T: Game + Clone,
+    {
+        let moves = board.get_possible_moves();
+        let values: Vec<i8> = moves
+            .iter()
+            .map(|mv| move_value(board, self.ai_mark, mv))
+            .collect();
+        let Some(&best_value) = values.iter().max() else {
+            return Vec::new();
+        };
+        moves
+            .into_iter()
+            .zip(values)
+            .filter(|(_, value)| *value == best_value)
+            .map(|(mv, _)| mv)
+            .collect()
+    }
+}
+
+impl<T> Engine<T> for MinimaxAi
+where
+    T: Game + Clone,
+{
+    fn choose_move(&mut self, board: &T) -> Move {
+        MinimaxAi::choose_move(self, board)
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Minimax")
+    }
+}
+
+/// Scores a move for `mark` assuming perfect play afterwards.
+///
+/// # Returns
+/// A positive value if the move wins, zero if it draws and a negative value
+/// if it loses. Faster wins and slower losses are further from zero.
+pub fn move_value<T>(board: &T, mark: Mark, mv: &Move) -> i8
+where
+    T: Game + Clone,
+{
+    let mut next = board.clone();
+    next.play(mv, mark);
+    -negamax(&next, mark.switch(), 1, -SCORE_BOUND, SCORE_BOUND)
+}
+
+/// Scores `board` for `mark`, who is about to move, assuming perfect play.
+///
+/// # Returns
+/// A positive value if `mark` wins, zero for a draw and a negative value if
+/// `mark` loses. A finished board is scored by its own result.
+pub(crate) fn position_value<T>(board: &T, mark: Mark) -> i8
+where
+    T: Game + Clone,
+{
+    negamax(board, mark, 0, -SCORE_BOUND, SCORE_BOUND)
+}
+
+/// Scores `board` from the perspective of `mark`, who is about to move.
+///
+/// Only scores inside the `alpha`..`beta` window are exact; outside it the
+/// result is a bound, which is enough to know the move will not be chosen.
+fn negamax<T>(board: &T, mark: Mark, depth: i8, mut alpha: i8, beta: i8) -> i8
+where
+    T: Game + Clone,
+{
+    match board.get_state() {
+        GameState::Won(winner) if winner == mark => return 10 - depth,
+        GameState::Won(_) => return depth - 10,
+        GameState::Draw => return 0,
+        GameState::Playing => {}
+    }
+    let mut best = -SCORE_BOUND;
+    for mv in board.get_possible_moves() {
+        let mut next = board.clone();
+        next.play(&mv, mark);
+        let value = -negamax(&next, mark.switch(), depth + 1, -beta, -alpha);
+        best = best.max(value);
+        alpha = alpha.max(value);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{MAX_MOVES_CLASSIC, play_ai_game};
+    use crate::game::RuleSet;
+    use crate::game::base::SmallBoard;
+
+    #[test]
+    fn test_takes_immediate_win() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::O));
+        board.set(1, 1, Some(Mark::O));
+        board.set(0, 1, Some(Mark::X));
+        board.set(0, 2, Some(Mark::X));
+        board.set(1, 0, Some(Mark::X));
+
+        let ai = MinimaxAi::new(Mark::O);
+        assert_eq!(ai.choose_move(&board), Move::Base(2, 2));
+    }
+
+    #[test]
+    fn test_blocks_forced_loss() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+
+        let ai = MinimaxAi::new(Mark::O);
+        assert_eq!(ai.choose_move(&board), Move::Base(0, 2));
+    }
+
+    #[test]
+    fn test_avoids_completing_a_line_under_misere_rules() {
+        let mut board = SmallBoard::with_rules(RuleSet::Misere);
+        board.set(0, 0, Some(Mark::O));
+        board.set(0, 1, Some(Mark::O));
+        board.set(1, 1, Some(Mark::X));
+        board.set(1, 2, Some(Mark::X));
+        board.set(2, 0, Some(Mark::X));
+
+        let ai = MinimaxAi::new(Mark::O);
+        assert!(move_value(&board, Mark::O, &Move::Base(0, 2)) < 0);
+        assert_ne!(ai.choose_move(&board), Move::Base(0, 2));
+    }
+
+    #[test]
+    fn test_only_the_center_holds_a_corner_opening() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        let ai = MinimaxAi::new(Mark::O);
+        assert_eq!(ai.best_moves(&board), vec![Move::Base(1, 1)]);
+        assert_eq!(position_value(&board, Mark::O), 0);
+        assert!(move_value(&board, Mark::O, &Move::Base(0, 1)) < 0);
+    }
+
+    #[test]
+    fn test_self_play_always_draws() {
+        for first_player in [Mark::X, Mark::O] {
+            for opening in SmallBoard::new().get_possible_moves() {
+                let mut board = SmallBoard::new();
+                board.play(&opening, first_player);
+                let mut ai_x = MinimaxAi::new(Mark::X);
+                let mut ai_o = MinimaxAi::new(Mark::O);
+                let state = play_ai_game(
+                    &mut board,
+                    first_player.switch(),
+                    &mut ai_x,
+                    &mut ai_o,
+                    MAX_MOVES_CLASSIC,
+                )
+                .unwrap();
+                assert_eq!(state, GameState::Draw, "opening {opening:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::ai::Game;
+use crate::ai::Move::{self, Ultimate};
+use crate::game::ultimate::BigBoard;
+use crate::game::{Board, GameState};
+
+/// Strong replies to the first few moves of an Ultimate game.
+///
+/// Each key holds the 81 cells of a board as written by [`book_key`]: the
+/// nine small boards in row-major order, separated by `/`, each one a row-major
+/// run of nine cells with `.` for empty cells.
+const BOOK: [(&str, Move); 5] = [
+    // The center of the center board sends the opponent back to it.
+    (
+        "........./........./........./........./........./........./........./........./.........",
+        Ultimate(1, 1, 1, 1),
+    ),
+    // A corner reply keeps the center board open for O.
+    (
+        "........./........./........./........./....X..../........./........./........./.........",
+        Ultimate(1, 1, 0, 0),
+    ),
+    // The center of the corner board sends O back to the center board.
+    (
+        "........./........./........./........./O...X..../........./........./........./.........",
+        Ultimate(0, 0, 1, 1),
+    ),
+    // The opposite corner blocks the center diagonal.
+    (
+        "....X..../........./........./........./O...X..../........./........./........./.........",
+        Ultimate(1, 1, 2, 2),
+    ),
+    // After a corner opening, the center of the corner board sends X back.
+    (
+        "........./........./........./........./X......../........./........./........./.........",
+        Ultimate(0, 0, 1, 1),
+    ),
+];
+
+/// Looks up the reply the book gives for `board`.
+///
+/// # Returns
+/// The book move, or None if the position is not in the book or the book
+/// move is not legal on `board`, e.g. because the active board differs.
+pub fn book_move(board: &BigBoard) -> Option<Move> {
+    if board.state != GameState::Playing {
+        return None;
+    }
+    let key = book_key(board);
+    let (_, book_move) = BOOK.iter().find(|(position, _)| *position == key)?;
+    board
+        .get_possible_moves()
+        .contains(book_move)
+        .then_some(*book_move)
+}
+
+/// Writes the cells of `board` in the format of the book keys.
+fn book_key(board: &BigBoard) -> String {
+    let mut key = String::with_capacity(89);
+    for index in 0..9 {
+        if index > 0 {
+            key.push('/');
+        }
+        let small_board = board.get_board(index / 3, index % 3);
+        for cell in 0..9 {
+            match small_board.get(cell / 3, cell % 3) {
+                Some(mark) => key.push_str(&mark.to_string()),
+                None => key.push('.'),
+            }
+        }
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Mark;
+
+    #[test]
+    fn test_main_line_is_in_the_book() {
+        let mut board = BigBoard::new();
+        let mut mark = Mark::X;
+        for _ in 0..4 {
+            let mv = book_move(&board).expect("the main line stays in the book");
+            board.play(&mv, mark);
+            mark = mark.switch();
+        }
+        assert_eq!(book_move(&board), None);
+    }
+
+    #[test]
+    fn test_every_book_move_is_legal_in_its_position() {
+        for (key, mv) in BOOK {
+            assert_eq!(key.len(), 89, "{key}");
+            // Every key is reached by the moves of the book or a single
+            // opening move, so replaying those finds each position.
+            let found = reachable_positions()
+                .into_iter()
+                .any(|board| book_key(&board) == key && book_move(&board) == Some(mv));
+            assert!(found, "{key} is not reached by a book line");
+        }
+    }
+
+    #[test]
+    fn test_unknown_positions_are_out_of_book() {
+        let mut board = BigBoard::new();
+        board.make_move(2, 2, 0, 1, Mark::X);
+        assert_eq!(book_move(&board), None);
+    }
+
+    /// Returns the positions after every opening move and every book line.
+    fn reachable_positions() -> Vec<BigBoard> {
+        let mut positions = vec![BigBoard::new()];
+        for mv in BigBoard::new().get_possible_moves() {
+            let mut board = BigBoard::new();
+            board.play(&mv, Mark::X);
+            positions.push(board);
+        }
+        let mut board = BigBoard::new();
+        let mut mark = Mark::X;
+        while let Some(mv) = book_move(&board) {
+            board.play(&mv, mark);
+            mark = mark.switch();
+            positions.push(board);
+        }
+        positions
+    }
+}
@@ -1,9 +1,29 @@
-/// Marker type for the random move strategy.
-pub struct RandomAI {}
-use crate::ai::{Game, Move};
+use crate::ai::rng::with_rng;
+use crate::ai::{Engine, Game, Move};
 use rand::prelude::*;
+use std::borrow::Cow;
 
-/// Chooses a random available for the AI on the given board.
+/// An AI opponent that plays any legal move, chosen uniformly at random.
+///
+/// Moves are drawn from the generator shared by the AI engines, so they
+/// are reproduced by [`seed_rng`](crate::ai::rng::seed_rng).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RandomAI;
+
+impl RandomAI {
+    /// Chooses a random available move on the given board.
+    ///
+    /// # Panics
+    /// Panics if there are no available moves on the board
+    pub fn choose_move<T>(&self, board: &T) -> Move
+    where
+        T: Game,
+    {
+        random_move(board)
+    }
+}
+
+/// Chooses a random available move for the AI on the given board.
 ///
 /// # Arguments
 /// * `board` - The current game board state
@@ -23,6 +43,57 @@ where
         panic!("No available moves found by RandomAi");
     }
 
-    let mut rng = rand::rng();
-    *ai_moves.choose(&mut rng).unwrap()
+    with_rng(|rng| *ai_moves.choose(rng).unwrap())
+}
+
+impl<T: Game> Engine<T> for RandomAI {
+    fn choose_move(&mut self, board: &T) -> Move {
+        RandomAI::choose_move(self, board)
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Random")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::rng::seed_rng;
+    use crate::game::Mark;
+    use crate::game::base::SmallBoard;
+    use crate::game::ultimate::BigBoard;
+
+    #[test]
+    fn test_always_chooses_a_legal_move() {
+        let mut board = SmallBoard::new();
+        board.make_move(1, 1, Mark::X);
+        let mut big_board = BigBoard::new();
+        big_board.make_move(0, 0, 1, 1, Mark::X);
+
+        for seed in 0..20 {
+            seed_rng(seed);
+            let mv = RandomAI.choose_move(&board);
+            assert!(board.get_possible_moves().contains(&mv));
+            let mv = RandomAI.choose_move(&big_board);
+            assert!(big_board.get_possible_moves().contains(&mv));
+        }
+    }
+
+    #[test]
+    fn test_same_seed_repeats_moves() {
+        let board = SmallBoard::new();
+        seed_rng(5);
+        let first: Vec<Move> = (0..5).map(|_| RandomAI.choose_move(&board)).collect();
+        seed_rng(5);
+        let second: Vec<Move> = (0..5).map(|_| RandomAI.choose_move(&board)).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    #[should_panic(expected = "No available moves found by RandomAi")]
+    fn test_panics_on_full_board() {
+        let board = SmallBoard::from_str_grid("XOX|XOO|OXX").unwrap();
+        RandomAI.choose_move(&board);
+    }
 }
@@ -1,18 +1,45 @@
-use crate::ai::{Game, Move};
+use crate::ai::minimax::MinimaxAi;
+use crate::ai::rng::with_rng;
+use crate::ai::{Engine, Game, Move};
 use crate::game::Mark;
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How carefully a [`SimpleAi`] chooses its moves.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum Difficulty {
+    /// Takes immediate wins but otherwise moves at random, without blocking.
+    Easy,
+    /// Takes immediate wins and blocks the opponent's.
+    #[default]
+    Medium,
+    /// Plays like [`Difficulty::Medium`], then sets up and defuses forks, and
+    /// otherwise takes the center, a corner or an edge, in that order.
+    Tactical,
+    /// Searches the full game tree, which is only practical on a classic board.
+    Hard,
+}
 
 /// A simple AI opponent for Tic-Tac-Toe that uses basic strategy.
 ///
-/// The AI prioritizes moves in the following order:
+/// At [`Difficulty::Medium`] the AI prioritizes moves in the following order:
 /// 1. Win if possible
-/// 2. Block opponent's winning move
-/// 3. Choose randomly from available positions
+/// 2. Avoid moves that lose right away or let the opponent win
+/// 3. Choose randomly from the remaining positions, or by
+///    [preference](SimpleAi::deterministic) when set
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SimpleAi {
     /// Mark controlled by the AI.
     pub ai_mark: Mark,
     enemy_mark: Mark,
+    /// How carefully moves are chosen.
+    pub difficulty: Difficulty,
+    /// Whether ties between equally good moves are broken by a fixed order
+    /// instead of at random: the center, then the corners, then the edges.
+    ///
+    /// Off by default. Ultimate moves are ranked by their cell in the small board.
+    pub deterministic: bool,
 }
 
 impl SimpleAi {
@@ -28,12 +55,29 @@ impl SimpleAi {
     /// assert_eq!(ai.ai_mark, Mark::X);
     /// ```
     pub fn new(ai_mark: Mark) -> SimpleAi {
+        SimpleAi::with_difficulty(ai_mark, Difficulty::Medium)
+    }
+
+    /// Creates a new SimpleAi with the given mark and difficulty.
+    ///
+    /// # Arguments
+    /// * `ai_mark` - The mark (X or O) that the AI will play as
+    /// * `difficulty` - How carefully the AI chooses its moves
+    pub fn with_difficulty(ai_mark: Mark, difficulty: Difficulty) -> SimpleAi {
         SimpleAi {
             ai_mark,
             enemy_mark: ai_mark.switch(),
+            difficulty,
+            deterministic: false,
         }
     }
 
+    /// Makes the AI play `ai_mark`, keeping its difficulty and tie-breaking.
+    pub fn set_mark(&mut self, ai_mark: Mark) {
+        self.ai_mark = ai_mark;
+        self.enemy_mark = ai_mark.switch();
+    }
+
     /// Chooses the best move for the AI on the given board.
     ///
     /// # Arguments
@@ -53,6 +97,11 @@ impl SimpleAi {
         if ai_moves.is_empty() {
             panic!("No available moves found by SimpleAi");
         }
+        match self.difficulty {
+            Difficulty::Easy => return self.choose_move_easy(board, &ai_moves),
+            Difficulty::Medium | Difficulty::Tactical => {}
+            Difficulty::Hard => return self.choose_move_hard(board),
+        }
         let mut non_losing_moves = ai_moves.clone();
 
         // save original board score
@@ -67,6 +116,12 @@ impl SimpleAi {
             if board_i.score(self.ai_mark) > original_ai_score {
                 return *mv;
             }
+            // a move that loses on the spot, such as completing a line under
+            // misère rules, is never safe
+            if board_i.score(self.ai_mark) < original_ai_score {
+                non_losing_moves.remove(i);
+                continue;
+            }
             // else check that the move doesn't let the enemy win
             let enemy_moves = board_i.get_possible_moves();
             for enemy_mv in enemy_moves.iter() {
@@ -80,21 +135,167 @@ impl SimpleAi {
             }
         }
 
-        let mut rng = rand::rng();
+        // if there are non-losing moves return one of them, else any move
+        if non_losing_moves.is_empty() {
+            self.break_tie(&ai_moves)
+        } else if self.difficulty == Difficulty::Tactical {
+            self.choose_move_tactical(board, &non_losing_moves)
+        } else {
+            self.break_tie(&non_losing_moves)
+        }
+    }
 
-        // if there are non-losing moves return one of them
-        if let Some(mv) = non_losing_moves.choose(&mut rng) {
-            return *mv;
+    /// Chooses among the non-losing `moves` by the classic strategy.
+    ///
+    /// A move creating two winning threats at once comes first. Otherwise the
+    /// move must leave the opponent no fork, even after answering a threat it
+    /// creates, and the center, the corners and the edges are preferred in
+    /// that order.
+    fn choose_move_tactical<T>(&self, board: &T, moves: &[Move]) -> Move
+    where
+        T: Game + Clone,
+    {
+        let after = |mv: &Move| {
+            let mut board_i = board.clone();
+            board_i.play(mv, self.ai_mark);
+            board_i
+        };
+        let forks: Vec<Move> = moves
+            .iter()
+            .filter(|mv| winning_moves(&after(mv), self.ai_mark).len() >= 2)
+            .copied()
+            .collect();
+        if !forks.is_empty() {
+            return self.break_tie(&forks);
         }
-        // else move at random
-        *ai_moves.choose(&mut rng).unwrap()
+
+        let safe_moves: Vec<Move> = moves
+            .iter()
+            .filter(|mv| !self.allows_fork(&after(mv)))
+            .copied()
+            .collect();
+        let candidates = if safe_moves.is_empty() {
+            moves
+        } else {
+            &safe_moves
+        };
+        let best_rank = candidates.iter().map(preference_rank).min().unwrap();
+        let preferred: Vec<Move> = candidates
+            .iter()
+            .filter(|mv| preference_rank(mv) == best_rank)
+            .copied()
+            .collect();
+        self.break_tie(&preferred)
+    }
+
+    /// Returns whether the opponent, on turn on `board`, can set up a fork.
+    ///
+    /// If the AI threatens to win, the opponent must block, so only that
+    /// block is considered.
+    fn allows_fork<T>(&self, board: &T) -> bool
+    where
+        T: Game + Clone,
+    {
+        let creates_fork = |mv: &Move| {
+            let mut board_j = board.clone();
+            board_j.play(mv, self.enemy_mark);
+            winning_moves(&board_j, self.enemy_mark).len() >= 2
+        };
+        match winning_moves(board, self.ai_mark).as_slice() {
+            [] => board.get_possible_moves().iter().any(creates_fork),
+            [forced] => creates_fork(forced),
+            // the AI already has a fork of its own
+            _ => false,
+        }
+    }
+
+    /// Picks one of `moves`, which the AI considers equally good.
+    ///
+    /// # Panics
+    /// Panics if `moves` is empty.
+    fn break_tie(&self, moves: &[Move]) -> Move {
+        if self.deterministic {
+            // min_by_key keeps the first of several equally ranked moves
+            return *moves.iter().min_by_key(|mv| preference_rank(mv)).unwrap();
+        }
+        with_rng(|rng| *moves.choose(rng).unwrap())
+    }
+
+    /// Plays an immediate win if there is one, else a random move.
+    fn choose_move_easy<T>(&self, board: &T, ai_moves: &[Move]) -> Move
+    where
+        T: Game + Clone,
+    {
+        let original_ai_score = board.score(self.ai_mark);
+        let winning_move = ai_moves.iter().find(|mv| {
+            let mut board_i = board.clone();
+            board_i.play(mv, self.ai_mark);
+            board_i.score(self.ai_mark) > original_ai_score
+        });
+        match winning_move {
+            Some(mv) => *mv,
+            None => self.break_tie(ai_moves),
+        }
+    }
+
+    /// Plays a random move among those with the best minimax value.
+    fn choose_move_hard<T>(&self, board: &T) -> Move
+    where
+        T: Game + Clone,
+    {
+        self.break_tie(&MinimaxAi::new(self.ai_mark).best_moves(board))
+    }
+}
+
+/// Returns the moves that would win right away for `mark`, as if it were on turn.
+fn winning_moves<T>(board: &T, mark: Mark) -> Vec<Move>
+where
+    T: Game + Clone,
+{
+    let original_score = board.score(mark);
+    board
+        .get_possible_moves()
+        .into_iter()
+        .filter(|mv| {
+            let mut board_i = board.clone();
+            board_i.play(mv, mark);
+            board_i.score(mark) > original_score
+        })
+        .collect()
+}
+
+/// Ranks a move for deterministic tie-breaking: 0 for the center cell, 1 for
+/// a corner and 2 for an edge.
+fn preference_rank(mv: &Move) -> u8 {
+    let (row, col) = match *mv {
+        Move::Base(row, col) | Move::Ultimate(_, _, row, col) => (row, col),
+    };
+    match (row, col) {
+        (1, 1) => 0,
+        _ if row != 1 && col != 1 => 1,
+        _ => 2,
+    }
+}
+
+impl<T> Engine<T> for SimpleAi
+where
+    T: Game + Clone,
+{
+    fn choose_move(&mut self, board: &T) -> Move {
+        SimpleAi::choose_move(self, board)
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Simple")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ai::rng::seed_rng;
     use crate::game::base::SmallBoard;
+    use crate::game::{GameState, RuleSet};
 
     #[test]
     fn test_ai_takes_winning_move() {
@@ -124,6 +325,23 @@ mod tests {
         assert_eq!((row, col), (0, 2));
     }
 
+    #[test]
+    fn test_ai_avoids_completing_a_line_under_misere_rules() {
+        let mut board = SmallBoard::with_rules(RuleSet::Misere);
+        board.set(0, 0, Some(Mark::O));
+        board.set(0, 1, Some(Mark::O));
+        board.set(1, 1, Some(Mark::X));
+        board.set(1, 2, Some(Mark::X));
+        board.set(2, 0, Some(Mark::X));
+        // Position (0, 2) would complete O's line and lose
+
+        let ai = SimpleAi::new(Mark::O);
+        for seed in 0..20 {
+            seed_rng(seed);
+            assert_ne!(ai.choose_move(&board), Move::Base(0, 2));
+        }
+    }
+
     #[test]
     #[should_panic(expected = "No available moves found by SimpleAi")]
     fn test_ai_panics_on_full_board() {
@@ -138,4 +356,172 @@ mod tests {
         let ai = SimpleAi::new(Mark::O);
         ai.choose_move(&board);
     }
+
+    #[test]
+    fn test_new_defaults_to_medium() {
+        assert_eq!(SimpleAi::new(Mark::O).difficulty, Difficulty::Medium);
+    }
+
+    /// Plays every possible reply of X against the AI, returning whether X ever wins.
+    fn x_can_beat(ai: &SimpleAi, board: &SmallBoard) -> bool {
+        match board.state {
+            GameState::Won(winner) => return winner == Mark::X,
+            GameState::Draw => return false,
+            GameState::Playing => {}
+        }
+        board.get_possible_moves().iter().any(|mv| {
+            let mut next = *board;
+            next.play(mv, Mark::X);
+            if next.state != GameState::Playing {
+                return next.state == GameState::Won(Mark::X);
+            }
+            next.play(&ai.choose_move(&next), Mark::O);
+            x_can_beat(ai, &next)
+        })
+    }
+
+    #[test]
+    fn test_hard_never_loses_from_any_opening() {
+        seed_rng(3);
+        let ai = SimpleAi::with_difficulty(Mark::O, Difficulty::Hard);
+        // Corner, edge and center openings cover the board up to symmetry.
+        for (row, col) in [(0, 0), (0, 1), (1, 1)] {
+            let mut board = SmallBoard::new();
+            board.make_move(row, col, Mark::X);
+            board.play(&ai.choose_move(&board), Mark::O);
+            assert!(
+                !x_can_beat(&ai, &board),
+                "lost after opening ({row}, {col})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_deterministic_ai_opens_in_the_center() {
+        let mut ai = SimpleAi::new(Mark::X);
+        ai.deterministic = true;
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            ai.difficulty = difficulty;
+            for seed in 0..10 {
+                seed_rng(seed);
+                assert_eq!(ai.choose_move(&SmallBoard::new()), Move::Base(1, 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deterministic_ai_prefers_corners_over_edges() {
+        let mut board = SmallBoard::new();
+        board.set(1, 1, Some(Mark::X));
+        let mut ai = SimpleAi::new(Mark::O);
+        ai.deterministic = true;
+        for seed in 0..10 {
+            seed_rng(seed);
+            assert_eq!(ai.choose_move(&board), Move::Base(0, 0));
+        }
+
+        // The edge (0, 1) comes first in reading order, but a corner is still preferred.
+        board.set(0, 0, Some(Mark::O));
+        board.set(2, 2, Some(Mark::X));
+        assert_eq!(ai.choose_move(&board), Move::Base(0, 2));
+    }
+
+    #[test]
+    fn test_set_mark_keeps_deterministic_tie_breaking() {
+        let mut simple = SimpleAi::new(Mark::X);
+        simple.deterministic = true;
+        let mut ai = crate::ai::AI::Medium(simple);
+        ai.set_mark(Mark::O);
+
+        let crate::ai::AI::Medium(simple) = &ai else {
+            panic!("set_mark changed the kind of AI");
+        };
+        assert!(simple.deterministic);
+        assert_eq!(simple.ai_mark, Mark::O);
+        assert_eq!(simple.enemy_mark, Mark::X);
+    }
+
+    #[test]
+    fn test_tactical_ai_creates_a_fork() {
+        // X O .
+        // . O .
+        // . X .
+        let board = SmallBoard::from_str_grid("XO.|.O.|.X.").unwrap();
+        let ai = SimpleAi::with_difficulty(Mark::X, Difficulty::Tactical);
+        for seed in 0..20 {
+            seed_rng(seed);
+            // (2, 0) threatens both the left column and the bottom row.
+            assert_eq!(ai.choose_move(&board), Move::Base(2, 0));
+        }
+    }
+
+    #[test]
+    fn test_tactical_ai_blocks_a_fork_with_an_edge() {
+        // X . .
+        // . O .
+        // . . X
+        let board = SmallBoard::from_str_grid("X..|.O.|..X").unwrap();
+        let ai = SimpleAi::with_difficulty(Mark::O, Difficulty::Tactical);
+        for seed in 0..20 {
+            seed_rng(seed);
+            // A corner would force X to block in the other corner, which
+            // forks; an edge forces a harmless block instead.
+            let (row, col) = ai.choose_move(&board).unwrap_base();
+            assert!(row == 1 || col == 1, "played ({row}, {col})");
+        }
+    }
+
+    #[test]
+    fn test_tactical_never_loses_from_any_opening() {
+        let mut ai = SimpleAi::with_difficulty(Mark::O, Difficulty::Tactical);
+        ai.deterministic = true;
+        for (row, col) in [(0, 0), (0, 1), (1, 1)] {
+            let mut board = SmallBoard::new();
+            board.make_move(row, col, Mark::X);
+            board.play(&ai.choose_move(&board), Mark::O);
+            assert!(
+                !x_can_beat(&ai, &board),
+                "lost after opening ({row}, {col})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tactical_ai_takes_the_center_then_a_corner() {
+        let ai = SimpleAi::with_difficulty(Mark::X, Difficulty::Tactical);
+        assert_eq!(ai.choose_move(&SmallBoard::new()), Move::Base(1, 1));
+
+        let mut board = SmallBoard::new();
+        board.set(1, 1, Some(Mark::X));
+        let ai = SimpleAi::with_difficulty(Mark::O, Difficulty::Tactical);
+        for seed in 0..20 {
+            seed_rng(seed);
+            let (row, col) = ai.choose_move(&board).unwrap_base();
+            assert!(row != 1 && col != 1, "played ({row}, {col})");
+        }
+    }
+
+    #[test]
+    fn test_easy_sometimes_declines_a_block() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+
+        let ai = SimpleAi::with_difficulty(Mark::O, Difficulty::Easy);
+        let mut blocked = 0;
+        for seed in 0..50 {
+            seed_rng(seed);
+            if ai.choose_move(&board) == Move::Base(0, 2) {
+                blocked += 1;
+            }
+        }
+        assert!(blocked > 0);
+        assert!(blocked < 50);
+
+        // A win is still taken, even when it also blocks.
+        board.set(2, 0, Some(Mark::O));
+        board.set(1, 0, Some(Mark::X));
+        assert_eq!(ai.choose_move(&board), Move::Base(0, 2));
+    }
 }
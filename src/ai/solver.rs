@@ -0,0 +1,113 @@
+use crate::ai::Game;
+use crate::ai::Move;
+use crate::ai::minimax::position_value;
+use crate::game::base::SmallBoard;
+use crate::game::{GameState, Mark};
+
+pub use crate::ai::minimax::move_value;
+
+/// Returns a move with the best guaranteed outcome for `mark`.
+///
+/// The full game tree is searched, so the move never loses a position that
+/// can be held. Among equally good moves, faster wins and slower losses are
+/// preferred.
+///
+/// # Panics
+/// Panics if there are no legal moves on the board.
+pub fn perfect_move(board: &SmallBoard, mark: Mark) -> Move {
+    board
+        .get_possible_moves()
+        .into_iter()
+        .max_by_key(|mv| move_value(board, mark, mv))
+        .expect("No available moves found by the solver")
+}
+
+/// Returns the outcome of `board` under perfect play from both players.
+///
+/// The full game tree is searched, so the result is exact. A finished board
+/// keeps its own result.
+///
+/// # Arguments
+/// * `board` - The board to solve
+/// * `mark` - The mark about to move
+pub fn forced_result(board: &SmallBoard, mark: Mark) -> GameState {
+    match position_value(board, mark) {
+        value if value > 0 => GameState::Won(mark),
+        value if value < 0 => GameState::Won(mark.switch()),
+        _ => GameState::Draw,
+    }
+}
+
+/// Checks whether a game in progress is bound to end in a draw.
+///
+/// Unlike a draw under perfect play, a dead draw cannot be won by either
+/// player whatever moves are made, so playing it out changes nothing.
+///
+/// # Arguments
+/// * `board` - The board to check
+/// * `mark` - The mark about to move
+///
+/// # Returns
+/// True if the game is still in progress and no sequence of moves wins it.
+pub fn is_dead_draw(board: &SmallBoard, mark: Mark) -> bool {
+    board.state == GameState::Playing && !can_be_won(board, mark)
+}
+
+/// Checks whether any sequence of moves starting with `mark` ends in a win.
+fn can_be_won(board: &SmallBoard, mark: Mark) -> bool {
+    match board.state {
+        GameState::Won(_) => true,
+        GameState::Draw => false,
+        GameState::Playing => board.get_possible_moves().iter().any(|mv| {
+            let mut next = *board;
+            next.play(mv, mark);
+            can_be_won(&next, mark.switch())
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_takes_immediate_win() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 0, Some(Mark::O));
+        board.set(1, 1, Some(Mark::O));
+        assert_eq!(perfect_move(&board, Mark::X), Move::Base(0, 2));
+    }
+
+    #[test]
+    fn test_move_value_signs() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+        assert!(move_value(&board, Mark::O, &Move::Base(0, 2)) == 0);
+        assert!(move_value(&board, Mark::O, &Move::Base(2, 2)) < 0);
+        assert!(move_value(&board, Mark::X, &Move::Base(0, 2)) > 0);
+    }
+
+    #[test]
+    fn test_answers_corner_opening_with_center() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        assert_eq!(perfect_move(&board, Mark::O), Move::Base(1, 1));
+    }
+
+    #[test]
+    fn test_dead_draw_detection() {
+        let mut board = SmallBoard::new();
+        assert!(!is_dead_draw(&board, Mark::X));
+
+        board.set_row(0, [Some(Mark::X), Some(Mark::O), Some(Mark::X)]);
+        board.set_row(2, [Some(Mark::O), Some(Mark::X), Some(Mark::O)]);
+        assert!(is_dead_draw(&board, Mark::X));
+
+        board.set(2, 2, None);
+        assert!(!is_dead_draw(&board, Mark::O));
+    }
+}
@@ -1,17 +1,264 @@
 use crate::ai::AI;
 use crate::ai::AI::{Medium, StrongTTT, StrongUTT, Weak};
+use crate::ai::imitator::{ImitatorAi, MoveMemory};
 use crate::ai::mcts::MCTSAi;
-use crate::ai::simple::SimpleAi;
+use crate::ai::simple::{Difficulty, SimpleAi};
+use crate::cli::parse_cell;
 use crate::game::Mark::{O, X};
 use crate::game::base::SmallBoard;
 use crate::game::ultimate::BigBoard;
-use crate::game::{GameVariant, Mark};
+use crate::game::{Board, GameState, GameVariant, Mark, RuleSet};
+use crate::keymap::{KeyBindings, resolve_action};
+use crate::net::{NetError, RemoteLink, RemoteMove};
 use crate::network::protocol::{MoveMessage, UltimateMoveMessage};
 use crate::network::{NetworkClient, NetworkCommand, NetworkEvent, NetworkStatus};
+use crate::save::{SaveError, SavedGame, append_game_log, read_save, write_save};
 use crate::scenes::{
-    AI_MENU_OPTIONS, AIMenuStatus, GameMode, GamePlayTTT, GamePlayUTT, MAIN_MENU_OPTIONS, Menu,
-    ONLINE_MENU_OPTIONS, Scene, TTT_MENU_OPTIONS, TicketInput, UTT_MENU_OPTIONS,
+    AI_MENU_OPTIONS, AIMenuStatus, CLASSIC_PVE_AI_MENU_OPTIONS, DIFFICULTY_MENU_OPTIONS,
+    FIRST_PLAYER_MENU_OPTIONS, GameMode, GamePlayGomoku, GamePlayTTT, GamePlayUTT, LargeBoard,
+    MAIN_MENU_OPTIONS, MISERE_RULES_OPTION, Menu, ONLINE_MENU_OPTIONS, Opponent, PLAY_VS_AI_OPTION,
+    Replay, SIDE_MENU_OPTIONS, STANDARD_RULES_OPTION, Scene, TTT_MENU_OPTIONS, TicketInput,
+    UTT_MENU_OPTIONS, rules_option,
 };
+use crate::stats::{GameResult, Scoreboard};
+use crate::theme::Theme;
+use crate::utils::Position;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Action taken once the application has received no input for too long.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IdleAction {
+    /// Exit the application.
+    Quit,
+    /// Leave any running game and return to the main menu.
+    MainMenu,
+}
+
+/// Idle timeout used by unattended setups such as kiosks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IdleTimeout {
+    /// Time without input after which the action is taken.
+    pub after: Duration,
+    /// What to do when the timeout expires.
+    pub action: IdleAction,
+}
+
+/// Share of the move-time budget below which the countdown warns the player.
+pub const LOW_TIME_FRACTION: f32 = 0.25;
+
+/// Countdown of the time left for the current move.
+///
+/// The clock is purely informative: running out of time does not forfeit the move.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveClock {
+    /// Time available for each move.
+    pub budget: Duration,
+    turn: u32,
+    started: Instant,
+    elapsed: Duration,
+}
+
+impl MoveClock {
+    /// Starts a countdown for `turn` at `now`.
+    pub fn new(budget: Duration, turn: u32, now: Instant) -> Self {
+        Self {
+            budget,
+            turn,
+            started: now,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Advances the countdown to `now`, restarting it if a new turn began.
+    pub fn tick(&mut self, turn: u32, now: Instant) {
+        if turn != self.turn {
+            self.turn = turn;
+            self.started = now;
+        }
+        self.elapsed = now.saturating_duration_since(self.started);
+    }
+
+    /// Returns the time left for the current move.
+    pub fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.elapsed)
+    }
+
+    /// Returns the share of the budget that is left, between 0 and 1.
+    pub fn fraction_left(&self) -> f32 {
+        if self.budget.is_zero() {
+            return 0.0;
+        }
+        self.remaining().as_secs_f32() / self.budget.as_secs_f32()
+    }
+
+    /// Checks whether the remaining time fell to [`LOW_TIME_FRACTION`] of the budget.
+    pub fn is_low(&self) -> bool {
+        self.fraction_left() <= LOW_TIME_FRACTION
+    }
+}
+
+/// Game input that can be bound to different keys.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    /// Move the selection left.
+    Left,
+    /// Move the selection right.
+    Right,
+    /// Move the selection up.
+    Up,
+    /// Move the selection down.
+    Down,
+    /// Confirm the selection.
+    Confirm,
+    /// Go back, such as from a cell to the choice of Ultimate board.
+    Back,
+}
+
+/// Application command bound to a key outside of text input and hot-seat keys.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    /// Exit the application.
+    Quit,
+    /// Start a new game with the same mode.
+    Reset,
+    /// Return to the main menu.
+    MainMenu,
+    /// Let the other player move first.
+    PlaySecond,
+    /// Concede the current game.
+    Concede,
+    /// Resign the current local game, once confirmed.
+    Resign,
+    /// Toggle beginner tips.
+    Tips,
+    /// Switch between weak and perfect hints.
+    HintStrength,
+    /// Toggle the missed-move feedback.
+    Mistakes,
+    /// Toggle the Ultimate destination overlay.
+    Destinations,
+    /// Toggle drawing won Ultimate boards as one large mark.
+    BigMarks,
+    /// Toggle the evaluation graph.
+    Graph,
+    /// Toggle the analysis bar.
+    Analysis,
+    /// Toggle the coordinate labels around the board.
+    Coordinates,
+    /// Toggle ignoring move input while the game is watched.
+    ReadOnly,
+    /// Replay the game with the same seed.
+    RestartSameSeed,
+    /// Start a Local PvP rematch with the players' marks swapped.
+    SwapSides,
+    /// Offer the opponent a draw in a Local PvP game.
+    OfferDraw,
+    /// Enter or leave analysis mode in a classic game.
+    Explore,
+    /// Take back the last move.
+    Undo,
+    /// Play a taken-back move again.
+    Redo,
+    /// Toggle the suggested-cell overlay.
+    HintCell,
+    /// Save the current game.
+    Save,
+    /// Show the key bindings over the current game.
+    Help,
+    /// Switch to the next color theme.
+    Theme,
+    /// Move the selection left.
+    Left,
+    /// Move the selection right.
+    Right,
+    /// Move the selection up.
+    Up,
+    /// Move the selection down.
+    Down,
+    /// Confirm the selection.
+    Enter,
+    /// Select and play the classic cell at this position.
+    Cell(Position),
+    /// Go back or cancel.
+    Esc,
+    /// Delete the last typed character.
+    Backspace,
+}
+
+/// Maps a key press to the command bound to it by default.
+///
+/// [`resolve_action`] applies the configured key bindings on top of this mapping.
+///
+/// # Returns
+/// The command, or None if the key is not bound.
+pub fn key_command(key: KeyEvent) -> Option<Command> {
+    let command = match key.code {
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => Command::Redo,
+        KeyCode::Char('q' | 'Q') => Command::Quit,
+        KeyCode::Char('r' | 'R') => Command::Reset,
+        KeyCode::Char('m' | 'M') => Command::MainMenu,
+        KeyCode::Char('s' | 'S') => Command::PlaySecond,
+        KeyCode::Char('c' | 'C') => Command::Concede,
+        KeyCode::Char('x' | 'X') => Command::Resign,
+        KeyCode::Char('t' | 'T') => Command::Tips,
+        KeyCode::Char('v' | 'V') => Command::HintStrength,
+        KeyCode::Char('b' | 'B') => Command::Mistakes,
+        KeyCode::Char('n' | 'N') => Command::Destinations,
+        KeyCode::Char('y' | 'Y') => Command::BigMarks,
+        KeyCode::Char('g' | 'G') => Command::Graph,
+        KeyCode::Char('i' | 'I') => Command::Analysis,
+        KeyCode::Char('p' | 'P') => Command::Coordinates,
+        KeyCode::Char('o' | 'O') => Command::ReadOnly,
+        KeyCode::Char('e' | 'E') => Command::RestartSameSeed,
+        KeyCode::Char('f' | 'F') => Command::SwapSides,
+        KeyCode::Char('=') => Command::OfferDraw,
+        KeyCode::Char('u' | 'U') => Command::Undo,
+        KeyCode::Char('?') => Command::HintCell,
+        KeyCode::F(1) => Command::Help,
+        KeyCode::F(2) => Command::Save,
+        KeyCode::F(3) => Command::Theme,
+        KeyCode::F(4) => Command::Explore,
+        KeyCode::Left | KeyCode::Char('h') => Command::Left,
+        KeyCode::Right | KeyCode::Char('l') => Command::Right,
+        KeyCode::Up | KeyCode::Char('k') => Command::Up,
+        KeyCode::Down | KeyCode::Char('j') => Command::Down,
+        KeyCode::Enter | KeyCode::Char(' ') => Command::Enter,
+        KeyCode::Char(digit @ '0'..='8') => {
+            let (row, col) = parse_cell(&digit.to_string()).ok()?;
+            Command::Cell(Position { row, col })
+        }
+        KeyCode::Esc => Command::Esc,
+        KeyCode::Backspace => Command::Backspace,
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Maps a key to its owner and action in the hot-seat mode.
+///
+/// X plays with WASD and Space, while O plays with the arrow keys and Enter.
+///
+/// # Returns
+/// The mark owning the key and its action, or None if no player owns it.
+pub fn split_keyset_action(code: KeyCode) -> Option<(Mark, Action)> {
+    match code {
+        KeyCode::Char('a' | 'A') => Some((Mark::X, Action::Left)),
+        KeyCode::Char('d' | 'D') => Some((Mark::X, Action::Right)),
+        KeyCode::Char('w' | 'W') => Some((Mark::X, Action::Up)),
+        KeyCode::Char('s' | 'S') => Some((Mark::X, Action::Down)),
+        KeyCode::Char(' ') => Some((Mark::X, Action::Confirm)),
+        KeyCode::Left => Some((Mark::O, Action::Left)),
+        KeyCode::Right => Some((Mark::O, Action::Right)),
+        KeyCode::Up => Some((Mark::O, Action::Up)),
+        KeyCode::Down => Some((Mark::O, Action::Down)),
+        KeyCode::Enter => Some((Mark::O, Action::Confirm)),
+        _ => None,
+    }
+}
 
 /// Main application state manager.
 ///
@@ -24,21 +271,70 @@ pub struct App {
     pub network_status: NetworkStatus,
     /// Whether the application event loop should terminate.
     pub should_quit: bool,
+    /// Results of the games finished during this session.
+    pub results: Vec<GameResult>,
+    /// Tally of the games finished since the main menu was last shown.
+    pub scoreboard: Scoreboard,
+    /// Optional timeout applied when no input is received, disabled by default.
+    pub idle_timeout: Option<IdleTimeout>,
+    /// Whether new classic games end as a draw once neither player can win.
+    pub auto_draw: bool,
+    /// Rules new classic games are played with, switched from the classic menu.
+    pub rules: RuleSet,
+    /// Optional time budget per move shown as a countdown, disabled by default.
+    pub move_time: Option<Duration>,
+    /// Optional time each player has for a whole classic game, disabled by default.
+    pub game_time: Option<Duration>,
+    /// Moves the player made against imitator AIs during this session.
+    pub imitator_memory: Rc<RefCell<MoveMemory>>,
+    /// File games are saved to with F2, disabled by default.
+    pub save_path: Option<PathBuf>,
+    /// File finished games are appended to and replayed from, disabled by default.
+    pub game_log_path: Option<PathBuf>,
+    /// Message shown until the next key press, such as the result of saving.
+    pub notice: Option<String>,
+    /// Whether the key bindings are drawn over the current game.
+    pub show_help: bool,
+    /// Whether quitting a game in progress waits for a `y` or `n` answer.
+    pub confirm_quit: bool,
+    /// Colors of the interface, cycled with F3 from the menus.
+    pub theme: Theme,
+    /// Keys bound to the remappable commands.
+    pub key_bindings: KeyBindings,
+    move_clock: Option<MoveClock>,
+    resign_pending: bool,
+    last_input: Instant,
     network_client: Option<NetworkClient>,
+    remote_link: Option<RemoteLink>,
 }
 
 impl App {
     /// Creates a new App starting at the main menu.
     pub fn new() -> Self {
         Self {
-            current_scene: Scene::MainMenu(Menu::new(vec![
-                "Ultimate Tic Tac Toe",
-                "Tic Tac Toe",
-                "Quit",
-            ])),
+            current_scene: Scene::MainMenu(Menu::new(MAIN_MENU_OPTIONS.to_vec())),
             network_status: NetworkStatus::Idle,
             should_quit: false,
+            results: Vec::new(),
+            scoreboard: Scoreboard::default(),
+            idle_timeout: None,
+            auto_draw: false,
+            rules: RuleSet::Standard,
+            move_time: None,
+            game_time: None,
+            imitator_memory: Rc::default(),
+            save_path: None,
+            game_log_path: None,
+            notice: None,
+            key_bindings: KeyBindings::default(),
+            show_help: false,
+            confirm_quit: false,
+            theme: Theme::default(),
+            move_clock: None,
+            resign_pending: false,
+            last_input: Instant::now(),
             network_client: None,
+            remote_link: None,
         }
     }
 
@@ -53,12 +349,25 @@ impl App {
     /// Stops the network worker and resets its visible status.
     pub fn stop_network(&mut self) {
         self.network_client = None;
+        self.remote_link = None;
         self.network_status = NetworkStatus::Idle;
     }
 
-    /// Returns whether the network worker is currently running.
+    /// Returns whether the network worker or a TCP game is currently running.
     pub fn network_is_active(&self) -> bool {
-        self.network_client.is_some()
+        self.network_client.is_some() || self.remote_link.is_some()
+    }
+
+    /// Starts a classic game against the remote player of a TCP `link`.
+    ///
+    /// The game runs in [`GameMode::OnlinePvP`] with the local player on
+    /// `local_mark`. The TCP protocol only carries moves, so rematches,
+    /// concessions and first-move yields are unavailable.
+    pub fn start_remote_game(&mut self, link: RemoteLink, local_mark: Mark) {
+        self.stop_network();
+        self.remote_link = Some(link);
+        self.network_status = NetworkStatus::Connected { mark: local_mark };
+        self.start_ttt_game(GameMode::OnlinePvP(local_mark));
     }
 
     /// Starts hosting an online match.
@@ -81,6 +390,9 @@ impl App {
 
     /// Applies all network events currently waiting for the synchronous app loop.
     pub fn poll_network_events(&mut self) {
+        while let Some(received) = self.remote_link.as_ref().and_then(RemoteLink::try_receive) {
+            self.handle_remote_move(received);
+        }
         loop {
             let Some(client) = &self.network_client else {
                 return;
@@ -92,6 +404,26 @@ impl App {
         }
     }
 
+    /// Plays a move received over a TCP link, or closes the link on an
+    /// error or an illegal move.
+    fn handle_remote_move(&mut self, received: Result<RemoteMove, NetError>) {
+        let status = match received {
+            Ok((mark, row, col)) => {
+                if let Scene::PlayingTTT(game) = &mut self.current_scene
+                    && game.active_player == mark
+                    && game.play_remote_move(row, col)
+                {
+                    return;
+                }
+                NetworkStatus::Failed(format!("illegal move from the opponent at ({row}, {col})"))
+            }
+            Err(NetError::Disconnected) => NetworkStatus::OpponentDisconnected,
+            Err(error) => NetworkStatus::Failed(error.to_string()),
+        };
+        self.remote_link = None;
+        self.network_status = status;
+    }
+
     fn handle_network_event(&mut self, event: NetworkEvent) {
         match event {
             NetworkEvent::Connected { mark, game } => {
@@ -168,6 +500,12 @@ impl App {
         }
     }
 
+    /// Returns whether rematches, concessions and first-move yields can be
+    /// sent, which needs a connected iroh session rather than a TCP link.
+    fn online_actions_available(&self) -> bool {
+        matches!(self.network_status, NetworkStatus::Connected { .. }) && self.remote_link.is_none()
+    }
+
     fn send_online_action(&mut self, command: NetworkCommand) {
         if let Err(error) = self.send_active_network_command(command) {
             self.network_status = NetworkStatus::Failed(error.to_string());
@@ -201,7 +539,10 @@ impl App {
 
     /// Starts a new tic-tac-toe game with the specified mode.
     pub fn start_ttt_game(&mut self, mode: GameMode) {
-        self.current_scene = Scene::PlayingTTT(GamePlayTTT::new(mode));
+        let mut game = GamePlayTTT::with_rules(mode, self.rules);
+        game.auto_draw = self.auto_draw;
+        game.set_time_budget(self.game_time);
+        self.current_scene = Scene::PlayingTTT(game);
     }
 
     /// Starts a new ultimate tic-tac-toe game with the specified mode.
@@ -209,14 +550,239 @@ impl App {
         self.current_scene = Scene::PlayingUTT(GamePlayUTT::new(mode));
     }
 
-    /// Goes to the main menu, discarding any active game.
+    /// Starts a local game of the given variant with the chosen mark moving first.
+    pub fn start_local_game(&mut self, game: GameVariant, first_player: Mark) {
+        self.current_scene = match game {
+            GameVariant::Classic => {
+                let mut game = GamePlayTTT::with_rules(GameMode::LocalPvP, self.rules);
+                if first_player == O {
+                    game.play_second();
+                }
+                game.auto_draw = self.auto_draw;
+                game.set_time_budget(self.game_time);
+                Scene::PlayingTTT(game)
+            }
+            GameVariant::Ultimate => Scene::PlayingUTT(GamePlayUTT::with_first_player(
+                GameMode::LocalPvP,
+                first_player,
+            )),
+        };
+    }
+
+    /// Starts a local game in which each player has their own keys.
+    pub fn start_hot_seat_game(&mut self, game: GameVariant) {
+        self.current_scene = match game {
+            GameVariant::Classic => {
+                let mut game = GamePlayTTT::with_rules(GameMode::LocalPvP, self.rules);
+                game.split_keys = true;
+                game.auto_draw = self.auto_draw;
+                game.set_time_budget(self.game_time);
+                Scene::PlayingTTT(game)
+            }
+            GameVariant::Ultimate => {
+                let mut game = GamePlayUTT::new(GameMode::LocalPvP);
+                game.split_keys = true;
+                Scene::PlayingUTT(game)
+            }
+        };
+    }
+
+    /// Starts a local game of Gomoku-style n-in-a-row.
+    pub fn start_gomoku_game(&mut self) {
+        self.current_scene = Scene::PlayingGomoku(GamePlayGomoku::new());
+    }
+
+    /// Starts a local game of tic-tac-toe on a 4x4 board, won with four in a row.
+    pub fn start_four_by_four_game(&mut self) {
+        self.current_scene =
+            Scene::PlayingGomoku(GamePlayGomoku::with_kind(LargeBoard::FourByFour));
+    }
+
+    /// Goes to the main menu, discarding any active game and clearing the
+    /// scoreboard.
+    ///
+    /// The menu offers to continue the saved game when one exists.
     pub fn go_to_main_menu(&mut self) {
-        self.current_scene = Scene::MainMenu(Menu::new(MAIN_MENU_OPTIONS.to_vec()));
+        self.scoreboard = Scoreboard::default();
+        let mut options = MAIN_MENU_OPTIONS.to_vec();
+        if self.save_path.as_ref().is_some_and(|path| path.exists()) {
+            options.insert(0, "Continue");
+        }
+        self.current_scene = Scene::MainMenu(Menu::new(options));
+    }
+
+    /// Resumes the game stored at [`App::save_path`].
+    ///
+    /// If the save cannot be loaded, stays on the current scene and shows
+    /// the reason as a notice.
+    pub fn continue_saved_game(&mut self) {
+        let Some(path) = self.save_path.clone() else {
+            return;
+        };
+        match self.load_saved_game(&path) {
+            Ok(scene) => self.current_scene = scene,
+            Err(error) => self.notice = Some(format!("Unable to continue: {error}")),
+        }
+    }
+
+    fn load_saved_game(&self, path: &Path) -> Result<Scene, SaveError> {
+        Ok(match read_save(path)? {
+            SavedGame::Classic(saved) => {
+                let mode = saved
+                    .mode
+                    .to_mode(GameVariant::Classic, &self.imitator_memory)?;
+                let mut game = GamePlayTTT::resume(saved, mode);
+                game.auto_draw = self.auto_draw;
+                game.set_time_budget(self.game_time);
+                Scene::PlayingTTT(game)
+            }
+            SavedGame::Ultimate(saved) => {
+                let mode = saved
+                    .mode
+                    .to_mode(GameVariant::Ultimate, &self.imitator_memory)?;
+                Scene::PlayingUTT(GamePlayUTT::resume(saved, mode))
+            }
+        })
+    }
+
+    /// Starts a fresh session without restarting the application.
+    ///
+    /// Clears the results of finished games and the moves learned by imitator
+    /// AIs, and discards any active game, returning to the main menu. Settings
+    /// such as the idle timeout are kept.
+    pub fn reset_session(&mut self) {
+        self.results.clear();
+        *self.imitator_memory.borrow_mut() = MoveMemory::default();
+        self.go_to_main_menu();
+    }
+
+    /// Opens the replay scene with the games stored in [`App::game_log_path`].
+    pub fn start_replay(&mut self) {
+        let replay = match &self.game_log_path {
+            Some(path) => match std::fs::read_to_string(path) {
+                Ok(contents) => Replay::from_log(&contents),
+                Err(error) => Replay::failed(format!("Unable to read {}: {error}", path.display())),
+            },
+            None => Replay::failed("No game log is kept on this platform"),
+        };
+        self.current_scene = Scene::Replay(replay);
+    }
+
+    /// Advances time-driven scenes, such as automatic replays and game
+    /// clocks, and applies the idle timeout.
+    pub fn handle_tick(&mut self) {
+        let now = Instant::now();
+        if let Scene::Replay(replay) = &mut self.current_scene {
+            replay.tick(now);
+        }
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => {
+                game.tick_clock(now);
+                game.update_hint();
+                game.update_ranking();
+                game.tick_animation();
+            }
+            Scene::PlayingUTT(game) => game.update_hint(),
+            _ => {}
+        }
+        self.record_result();
+        self.update_move_clock(now);
+        self.check_idle(now);
+    }
+
+    /// Runs the move countdown while a local player is choosing a move.
+    pub fn update_move_clock(&mut self, now: Instant) {
+        let turn = match &self.current_scene {
+            Scene::PlayingTTT(game)
+                if game.board.state == GameState::Playing && game.is_local_turn() =>
+            {
+                Some(game.turn)
+            }
+            Scene::PlayingUTT(game)
+                if game.big_board.state == GameState::Playing && game.is_local_turn() =>
+            {
+                Some(game.turn)
+            }
+            _ => None,
+        };
+        self.move_clock = match (self.move_time, turn, self.move_clock) {
+            (Some(_), Some(turn), Some(mut clock)) => {
+                clock.tick(turn, now);
+                Some(clock)
+            }
+            (Some(budget), Some(turn), None) => Some(MoveClock::new(budget, turn, now)),
+            _ => None,
+        };
+    }
+
+    /// Returns the countdown of the current move, if one is running.
+    pub fn move_clock(&self) -> Option<&MoveClock> {
+        self.move_clock.as_ref()
+    }
+
+    /// Stores the result of the current game once it has finished, and
+    /// appends its moves to [`App::game_log_path`].
+    pub fn record_result(&mut self) {
+        let finished = match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game
+                .take_result()
+                .map(|result| (result, game.game_record())),
+            Scene::PlayingUTT(game) => game
+                .take_result()
+                .map(|result| (result, game.game_record())),
+            _ => None,
+        };
+        let Some((result, record)) = finished else {
+            return;
+        };
+        self.scoreboard.record(result.outcome);
+        self.results.push(result);
+        if let (Some(path), Some(record)) = (&self.game_log_path, record)
+            && let Err(error) = append_game_log(path, &record)
+        {
+            self.notice = Some(format!("Unable to log the game: {error}"));
+        }
+    }
+
+    /// Records that the user pressed a key, restarting the idle timer.
+    pub fn record_input(&mut self, now: Instant) {
+        self.last_input = now;
+        self.notice = None;
+    }
+
+    /// Applies the idle timeout if no input was recorded for long enough.
+    ///
+    /// Returning to the main menu also restarts the idle timer, so the action
+    /// is taken once per idle period.
+    pub fn check_idle(&mut self, now: Instant) {
+        let Some(timeout) = self.idle_timeout else {
+            return;
+        };
+        if now.saturating_duration_since(self.last_input) < timeout.after {
+            return;
+        }
+
+        match timeout.action {
+            IdleAction::Quit => self.quit(),
+            IdleAction::MainMenu => {
+                if self.network_is_active() {
+                    self.stop_network();
+                }
+                self.go_to_main_menu();
+            }
+        }
+        self.last_input = now;
     }
 
     /// Goes to the tic-tac-toe menu.
     pub fn go_to_ttt_menu(&mut self) {
-        self.current_scene = Scene::TTTMenu(Menu::new(TTT_MENU_OPTIONS.to_vec()));
+        let opponent = Opponent::default();
+        let options = TTT_MENU_OPTIONS.map(|option| match option {
+            STANDARD_RULES_OPTION => rules_option(self.rules),
+            PLAY_VS_AI_OPTION => opponent.menu_option(),
+            option => option,
+        });
+        self.current_scene = Scene::TTTMenu(Menu::new(options.to_vec()), opponent);
     }
 
     /// Goes to the online match setup menu for a game variant.
@@ -262,12 +828,11 @@ impl App {
         if matches!(
             &self.network_status,
             NetworkStatus::Idle | NetworkStatus::Failed(_)
-        ) {
-            if let Scene::JoiningOnline(input, _) = &mut self.current_scene {
-                input.backspace();
-                if matches!(&self.network_status, NetworkStatus::Failed(_)) {
-                    self.network_status = NetworkStatus::Idle;
-                }
+        ) && let Scene::JoiningOnline(input, _) = &mut self.current_scene
+        {
+            input.backspace();
+            if matches!(&self.network_status, NetworkStatus::Failed(_)) {
+                self.network_status = NetworkStatus::Idle;
             }
         }
     }
@@ -296,12 +861,21 @@ impl App {
 
     /// Goes to the ultimate tic-tac-toe menu.
     pub fn go_to_utt_menu(&mut self) {
-        self.current_scene = Scene::UTTMenu(Menu::new(UTT_MENU_OPTIONS.to_vec()));
+        let opponent = Opponent::default();
+        let options = UTT_MENU_OPTIONS.map(|option| match option {
+            PLAY_VS_AI_OPTION => opponent.menu_option(),
+            option => option,
+        });
+        self.current_scene = Scene::UTTMenu(Menu::new(options.to_vec()), opponent);
     }
 
     /// Goes to the AI menu.
     pub fn go_to_ai_menu(&mut self, status: AIMenuStatus) {
-        self.current_scene = Scene::AIMenu(Menu::new(AI_MENU_OPTIONS.to_vec()), status);
+        let options = match status {
+            AIMenuStatus::TTTpve => CLASSIC_PVE_AI_MENU_OPTIONS.to_vec(),
+            _ => AI_MENU_OPTIONS.to_vec(),
+        };
+        self.current_scene = Scene::AIMenu(Menu::new(options), status);
     }
 
     /// Handles left arrow or 'h' key input.
@@ -319,6 +893,8 @@ impl App {
                 game.input_left()
             }
             Scene::PlayingUTT(_) => {}
+            Scene::PlayingGomoku(game) => game.input_left(),
+            Scene::TTTMenu(..) | Scene::UTTMenu(..) => self.cycle_opponent(false),
             _ => {}
         }
     }
@@ -338,10 +914,55 @@ impl App {
                 game.input_right()
             }
             Scene::PlayingUTT(_) => {}
+            Scene::PlayingGomoku(game) => game.input_right(),
+            Scene::TTTMenu(..) | Scene::UTTMenu(..) => self.cycle_opponent(true),
             _ => {}
         }
     }
 
+    /// Switches the opponent of "Play vs AI" to the next or the previous one
+    /// while that option is selected in the classic or Ultimate menu.
+    fn cycle_opponent(&mut self, forward: bool) {
+        let (menu, opponent, game) = match &mut self.current_scene {
+            Scene::TTTMenu(menu, opponent) => (menu, opponent, GameVariant::Classic),
+            Scene::UTTMenu(menu, opponent) => (menu, opponent, GameVariant::Ultimate),
+            _ => return,
+        };
+        if menu.get_selected() != opponent.menu_option() {
+            return;
+        }
+        *opponent = if forward {
+            opponent.next(game)
+        } else {
+            opponent.previous(game)
+        };
+        menu.options[menu.selected_option] = opponent.menu_option();
+    }
+
+    /// Goes to the menu choosing the first player of a local game.
+    pub fn go_to_first_player_menu(&mut self, game: GameVariant) {
+        self.current_scene =
+            Scene::FirstPlayerMenu(Menu::new(FIRST_PLAYER_MENU_OPTIONS.to_vec()), game);
+    }
+
+    /// Goes to the menu choosing the AI a human plays a `game` against.
+    fn go_to_pve_ai_menu(&mut self, game: GameVariant) {
+        self.go_to_ai_menu(match game {
+            GameVariant::Classic => AIMenuStatus::TTTpve,
+            GameVariant::Ultimate => AIMenuStatus::UTTpve,
+        });
+    }
+
+    /// Goes to the menu choosing the difficulty of the Medium AI in classic games.
+    pub fn go_to_difficulty_menu(&mut self) {
+        self.current_scene = Scene::DifficultyMenu(Menu::new(DIFFICULTY_MENU_OPTIONS.to_vec()));
+    }
+
+    /// Goes to the menu choosing the mark of the human in a `game` against `ai`.
+    pub fn go_to_side_menu(&mut self, ai: AI, game: GameVariant) {
+        self.current_scene = Scene::SideMenu(Menu::new(SIDE_MENU_OPTIONS.to_vec()), ai, game);
+    }
+
     /// Handles up arrow or 'k' key input.
     ///
     /// Moves menu selection up in main menu, or board selection up in game.
@@ -349,11 +970,15 @@ impl App {
         let online_frozen = matches!(self.network_status, NetworkStatus::OpponentDisconnected);
         match &mut self.current_scene {
             Scene::MainMenu(menu)
-            | Scene::TTTMenu(menu)
+            | Scene::TTTMenu(menu, _)
             | Scene::OnlineMenu(menu, _)
-            | Scene::UTTMenu(menu)
+            | Scene::UTTMenu(menu, _)
+            | Scene::FirstPlayerMenu(menu, _)
+            | Scene::DifficultyMenu(menu)
+            | Scene::SideMenu(menu, _, _)
             | Scene::AIMenu(menu, _) => menu.move_up(),
-            Scene::HostingOnline(_) | Scene::JoiningOnline(_, _) => {}
+            Scene::HostingOnline(_) | Scene::JoiningOnline(_, _) | Scene::Statistics => {}
+            Scene::Replay(replay) => replay.faster(),
             Scene::PlayingTTT(game)
                 if !online_frozen || !matches!(game.mode, GameMode::OnlinePvP(_)) =>
             {
@@ -366,6 +991,7 @@ impl App {
                 game.input_up()
             }
             Scene::PlayingUTT(_) => {}
+            Scene::PlayingGomoku(game) => game.input_up(),
         }
     }
 
@@ -376,11 +1002,15 @@ impl App {
         let online_frozen = matches!(self.network_status, NetworkStatus::OpponentDisconnected);
         match &mut self.current_scene {
             Scene::MainMenu(menu)
-            | Scene::TTTMenu(menu)
+            | Scene::TTTMenu(menu, _)
             | Scene::OnlineMenu(menu, _)
-            | Scene::UTTMenu(menu)
+            | Scene::UTTMenu(menu, _)
+            | Scene::FirstPlayerMenu(menu, _)
+            | Scene::DifficultyMenu(menu)
+            | Scene::SideMenu(menu, _, _)
             | Scene::AIMenu(menu, _) => menu.move_down(),
-            Scene::HostingOnline(_) | Scene::JoiningOnline(_, _) => {}
+            Scene::HostingOnline(_) | Scene::JoiningOnline(_, _) | Scene::Statistics => {}
+            Scene::Replay(replay) => replay.slower(),
             Scene::PlayingTTT(game)
                 if !online_frozen || !matches!(game.mode, GameMode::OnlinePvP(_)) =>
             {
@@ -393,7 +1023,22 @@ impl App {
                 game.input_down()
             }
             Scene::PlayingUTT(_) => {}
+            Scene::PlayingGomoku(game) => game.input_down(),
+        }
+    }
+
+    /// Handles a digit key by playing the classic cell it names.
+    ///
+    /// Occupied cells, and scenes other than a classic game, ignore the key.
+    pub fn handle_cell(&mut self, cell: Position) {
+        let Scene::PlayingTTT(game) = &mut self.current_scene else {
+            return;
+        };
+        if !game.board.is_playable(cell.row, cell.col) {
+            return;
         }
+        game.selected = cell;
+        self.play_ttt_move();
     }
 
     fn play_ttt_move(&mut self) {
@@ -408,19 +1053,33 @@ impl App {
                 return;
             }
 
+            let mark = game.active_player;
             if game.play_move() && is_online {
                 let row = u8::try_from(selected.row).expect("board row fits in u8");
                 let col = u8::try_from(selected.col).expect("board column fits in u8");
-                Some(MoveMessage::new(row, col).expect("selected board position is valid"))
+                Some((
+                    mark,
+                    MoveMessage::new(row, col).expect("selected board position is valid"),
+                ))
             } else {
                 None
             }
         };
 
-        if let Some(message) = message
-            && let Err(error) = self.send_active_network_command(NetworkCommand::SendMove(message))
-        {
-            self.network_status = NetworkStatus::Failed(error.to_string());
+        let Some((mark, message)) = message else {
+            return;
+        };
+        let sent = match &mut self.remote_link {
+            Some(link) => link
+                .send_move(mark, message.row(), message.col())
+                .map_err(|error| error.to_string()),
+            None => self
+                .send_active_network_command(NetworkCommand::SendMove(message))
+                .map_err(|error| error.to_string()),
+        };
+        if let Err(error) = sent {
+            self.remote_link = None;
+            self.network_status = NetworkStatus::Failed(error);
         }
     }
 
@@ -461,29 +1120,169 @@ impl App {
         }
     }
 
+    /// Handles a terminal event received at `now`.
+    ///
+    /// While the help overlay is shown, any key press only dismisses it, and
+    /// while the quit confirmation is shown only `y`, `n` and Esc are handled.
+    /// Otherwise key presses go to text input first, then to the hot-seat
+    /// keys, and finally to [`resolve_action`] with the configured key bindings. Key releases, resizes and mouse events are
+    /// ignored: the event loop redraws after every event, so a resize takes
+    /// effect immediately.
+    pub fn handle_event(&mut self, event: Event, now: Instant) {
+        let Event::Key(key) = event else {
+            return;
+        };
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        self.record_input(now);
+        let command = resolve_action(key, &self.key_bindings);
+        // Any other key cancels a pending resignation.
+        if command != Some(Command::Resign) {
+            self.resign_pending = false;
+        }
+        if self.show_help {
+            self.show_help = false;
+            return;
+        }
+        if self.confirm_quit {
+            match key.code {
+                KeyCode::Char('y' | 'Y') => self.quit(),
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => self.confirm_quit = false,
+                _ => {}
+            }
+            return;
+        }
+        if self.answer_draw_offer(key.code) {
+            return;
+        }
+        if let KeyCode::Char(character) = key.code
+            && self.handle_text_input(&character.to_string())
+        {
+            return;
+        }
+        if self.handle_split_key(key.code) {
+            return;
+        }
+        if let Some(command) = command {
+            self.run_command(command);
+        }
+    }
+
+    /// Runs the handler of `command`.
+    pub fn run_command(&mut self, command: Command) {
+        match command {
+            Command::Quit => self.handle_quit(),
+            Command::Reset => self.handle_reset(),
+            Command::MainMenu => self.handle_main_menu(),
+            Command::PlaySecond => self.handle_second(),
+            Command::Concede => self.handle_concede(),
+            Command::Resign => self.handle_resign(),
+            Command::Tips => self.handle_tips(),
+            Command::HintStrength => self.handle_hint_strength(),
+            Command::Mistakes => self.handle_mistakes(),
+            Command::Destinations => self.handle_destinations(),
+            Command::BigMarks => self.handle_big_marks(),
+            Command::Graph => self.handle_graph(),
+            Command::Analysis => self.handle_analysis(),
+            Command::Coordinates => self.handle_coordinates(),
+            Command::ReadOnly => self.handle_read_only(),
+            Command::RestartSameSeed => self.handle_restart_same_seed(),
+            Command::SwapSides => self.handle_swap_sides(),
+            Command::OfferDraw => self.handle_offer_draw(),
+            Command::Explore => self.handle_explore(),
+            Command::Undo => self.handle_undo(),
+            Command::Redo => self.handle_redo(),
+            Command::HintCell => self.handle_hint_cell(),
+            Command::Save => self.handle_save(),
+            Command::Help => self.handle_help(),
+            Command::Theme => self.handle_theme(),
+            Command::Left => self.handle_left(),
+            Command::Right => self.handle_right(),
+            Command::Up => self.handle_up(),
+            Command::Down => self.handle_down(),
+            Command::Enter => self.handle_enter(),
+            Command::Cell(cell) => self.handle_cell(cell),
+            Command::Esc => self.handle_esc(),
+            Command::Backspace => self.handle_backspace(),
+        }
+    }
+
+    /// Handles a key press in a hot-seat game, where each player has their own keys.
+    ///
+    /// Keys of the player who is not on turn are ignored, as are the shared
+    /// vim-style movement keys. After a classic rematch with sides swapped,
+    /// the WASD keys play O and the arrow keys play X.
+    ///
+    /// # Returns
+    /// True if the key was consumed, false if it should be handled normally.
+    pub fn handle_split_key(&mut self, code: KeyCode) -> bool {
+        let (active_player, swapped) = match &self.current_scene {
+            Scene::PlayingTTT(game) if game.split_keys => {
+                (game.active_player, game.players_swapped)
+            }
+            Scene::PlayingUTT(game) if game.split_keys => (game.active_player, false),
+            _ => return false,
+        };
+        let Some((mark, action)) = split_keyset_action(code) else {
+            return matches!(code, KeyCode::Char('h' | 'j' | 'k' | 'l'));
+        };
+        let mark = if swapped { mark.switch() } else { mark };
+        if mark == active_player {
+            match action {
+                Action::Left => self.handle_left(),
+                Action::Right => self.handle_right(),
+                Action::Up => self.handle_up(),
+                Action::Down => self.handle_down(),
+                Action::Confirm => self.handle_enter(),
+                Action::Back => self.handle_esc(),
+            }
+        }
+        true
+    }
+
     /// Handles Enter or Space key input.
     ///
     /// Confirms menu selection or places a mark on the board.
     pub fn handle_enter(&mut self) {
         match &mut self.current_scene {
             Scene::MainMenu(menu) => match menu.get_selected() {
+                "Continue" => self.continue_saved_game(),
                 "Ultimate Tic Tac Toe" => self.go_to_utt_menu(),
                 "Tic Tac Toe" => self.go_to_ttt_menu(),
+                "Gomoku 5x5" => self.start_gomoku_game(),
+                "Tic Tac Toe 4x4" => self.start_four_by_four_game(),
+                "Watch Replay" => self.start_replay(),
+                "Statistics" => self.current_scene = Scene::Statistics,
+                "Reset Session" => self.reset_session(),
                 "Quit" => self.should_quit = true,
                 _ => panic!("Option selected in Tic Tac Toe Menu does not exist."),
             },
-            Scene::TTTMenu(menu) => match menu.get_selected() {
-                "Local PvP" => self.start_ttt_game(GameMode::LocalPvP),
-                "Play vs AI" => self.go_to_ai_menu(AIMenuStatus::TTTpve),
+            Scene::TTTMenu(menu, opponent) => match menu.get_selected() {
+                "Local PvP" => self.go_to_first_player_menu(GameVariant::Classic),
+                "Hot Seat" => self.start_hot_seat_game(GameVariant::Classic),
+                option if option == opponent.menu_option() => {
+                    let ai = opponent.ai(GameVariant::Classic, O);
+                    self.go_to_side_menu(ai, GameVariant::Classic);
+                }
                 "AI vs AI" => self.go_to_ai_menu(AIMenuStatus::TTTeve(None)),
+                "Solitaire" => self.start_ttt_game(GameMode::Solitaire),
                 "Online PvP" => self.go_to_online_menu(GameVariant::Classic),
+                STANDARD_RULES_OPTION | MISERE_RULES_OPTION => {
+                    self.rules = self.rules.switch();
+                    menu.options[menu.selected_option] = rules_option(self.rules);
+                }
                 "Back" => self.go_to_main_menu(),
                 _ => panic!("Option selected in Tic Tac Toe Menu does not exist."),
             },
-            Scene::UTTMenu(menu) => match menu.get_selected() {
+            Scene::UTTMenu(menu, opponent) => match menu.get_selected() {
                 "Online PvP" => self.go_to_online_menu(GameVariant::Ultimate),
-                "Local PvP" => self.start_utt_game(GameMode::LocalPvP),
-                "Play vs AI" => self.go_to_ai_menu(AIMenuStatus::UTTpve),
+                "Local PvP" => self.go_to_first_player_menu(GameVariant::Ultimate),
+                "Hot Seat" => self.start_hot_seat_game(GameVariant::Ultimate),
+                option if option == opponent.menu_option() => {
+                    let ai = opponent.ai(GameVariant::Ultimate, O);
+                    self.go_to_side_menu(ai, GameVariant::Ultimate);
+                }
                 "AI vs AI" => self.go_to_ai_menu(AIMenuStatus::UTTeve(None)),
                 "Back" => self.go_to_main_menu(),
                 _ => panic!("Option selected in Ultimate Tic Tac Toe Menu does not exist."),
@@ -495,6 +1294,48 @@ impl App {
                 ("Back", GameVariant::Ultimate) => self.go_to_utt_menu(),
                 _ => panic!("Option selected in Online Tic Tac Toe Menu does not exist."),
             },
+            Scene::FirstPlayerMenu(menu, game) => match (menu.get_selected(), *game) {
+                ("X Moves First", game) => self.start_local_game(game, X),
+                ("O Moves First", game) => self.start_local_game(game, O),
+                ("Back", GameVariant::Classic) => self.go_to_ttt_menu(),
+                ("Back", GameVariant::Ultimate) => self.go_to_utt_menu(),
+                _ => panic!("Option selected in First Player Menu does not exist."),
+            },
+            Scene::DifficultyMenu(menu) => {
+                let difficulty = match menu.get_selected() {
+                    "Easy" => Difficulty::Easy,
+                    "Medium" => Difficulty::Medium,
+                    "Tactical" => Difficulty::Tactical,
+                    "Hard" => Difficulty::Hard,
+                    "Back" => {
+                        self.go_to_ai_menu(AIMenuStatus::TTTpve);
+                        return;
+                    }
+                    _ => panic!("Option selected in Difficulty Menu does not exist."),
+                };
+                let ai = SimpleAi::with_difficulty(O, difficulty);
+                self.go_to_side_menu(Medium(ai), GameVariant::Classic);
+            }
+            Scene::SideMenu(menu, ai, game) => {
+                let human_mark = match menu.get_selected() {
+                    "Play as X" => X,
+                    "Play as O" => O,
+                    "Random" if rand::random_bool(0.5) => X,
+                    "Random" => O,
+                    "Back" => {
+                        let game = *game;
+                        self.go_to_pve_ai_menu(game);
+                        return;
+                    }
+                    _ => panic!("Option selected in Side Menu does not exist."),
+                };
+                let mut ai = ai.clone();
+                ai.set_mark(human_mark.switch());
+                match game {
+                    GameVariant::Classic => self.start_ttt_game(GameMode::PvE(ai)),
+                    GameVariant::Ultimate => self.start_utt_game(GameMode::PvE(ai)),
+                }
+            }
             Scene::AIMenu(menu, status) => {
                 let selected_option = menu.get_selected();
                 if selected_option == "Back" {
@@ -512,6 +1353,15 @@ impl App {
                     }
                     return;
                 }
+                if selected_option == "Medium" && matches!(status, AIMenuStatus::TTTpve) {
+                    self.go_to_difficulty_menu();
+                    return;
+                }
+                if selected_option == "Imitator" {
+                    let ai = ImitatorAi::new(O, self.imitator_memory.clone());
+                    self.go_to_side_menu(AI::Imitator(ai), GameVariant::Classic);
+                    return;
+                }
                 let new_ai = match selected_option {
                     "Weak" => |mark: Mark| -> AI { Weak(mark) },
                     "Medium" => |mark: Mark| -> AI { Medium(SimpleAi::new(mark)) },
@@ -526,8 +1376,8 @@ impl App {
                     _ => panic!("Option selected in AI Menu does not exist."),
                 };
                 match status {
-                    AIMenuStatus::TTTpve => self.start_ttt_game(GameMode::PvE(new_ai(O))),
-                    AIMenuStatus::UTTpve => self.start_utt_game(GameMode::PvE(new_ai(O))),
+                    AIMenuStatus::TTTpve => self.go_to_side_menu(new_ai(O), GameVariant::Classic),
+                    AIMenuStatus::UTTpve => self.go_to_side_menu(new_ai(O), GameVariant::Ultimate),
                     AIMenuStatus::TTTeve(None) => {
                         self.go_to_ai_menu(AIMenuStatus::TTTeve(Some(new_ai(X))))
                     }
@@ -544,10 +1394,14 @@ impl App {
                     }
                 }
             }
-            Scene::HostingOnline(_) => {}
+            Scene::HostingOnline(_) | Scene::Statistics => {}
             Scene::JoiningOnline(_, _) => self.submit_joining_online(),
+            Scene::Replay(replay) => replay.next_game(),
             Scene::PlayingTTT(_) => self.play_ttt_move(),
             Scene::PlayingUTT(_) => self.play_utt_move(),
+            Scene::PlayingGomoku(game) => {
+                game.play_move();
+            }
         }
     }
 
@@ -558,15 +1412,23 @@ impl App {
         let online_frozen = matches!(self.network_status, NetworkStatus::OpponentDisconnected);
         match &mut self.current_scene {
             Scene::MainMenu(_) => self.quit(),
-            Scene::TTTMenu(_) => self.go_to_main_menu(),
+            Scene::TTTMenu(..) => self.go_to_main_menu(),
             Scene::OnlineMenu(_, GameVariant::Classic) => self.go_to_ttt_menu(),
             Scene::OnlineMenu(_, GameVariant::Ultimate) => self.go_to_utt_menu(),
+            Scene::FirstPlayerMenu(_, GameVariant::Classic) => self.go_to_ttt_menu(),
+            Scene::FirstPlayerMenu(_, GameVariant::Ultimate) => self.go_to_utt_menu(),
+            Scene::DifficultyMenu(_) => self.go_to_ai_menu(AIMenuStatus::TTTpve),
+            Scene::SideMenu(_, _, game) => {
+                let game = *game;
+                self.go_to_pve_ai_menu(game);
+            }
             Scene::HostingOnline(game) | Scene::JoiningOnline(_, game) => {
                 let game = *game;
                 self.stop_network();
                 self.go_to_online_menu(game);
             }
-            Scene::UTTMenu(_) => self.go_to_main_menu(),
+            Scene::UTTMenu(..) => self.go_to_main_menu(),
+            Scene::Replay(_) | Scene::Statistics => self.go_to_main_menu(),
             Scene::AIMenu(_, status) => match status {
                 AIMenuStatus::TTTpve => self.go_to_ttt_menu(),
                 AIMenuStatus::TTTeve(None) => self.go_to_ttt_menu(),
@@ -580,14 +1442,14 @@ impl App {
             {
                 game.input_esc()
             }
-            Scene::PlayingUTT(_) => {}
+            Scene::PlayingUTT(_) | Scene::PlayingGomoku(_) => {}
             Scene::PlayingTTT(_) => {}
         }
     }
 
-    /// Handles 's' key input to allow AI to play first in PvE mode.
+    /// Handles 's' key input to let the opponent move first.
     pub fn handle_second(&mut self) {
-        let online_connected = matches!(self.network_status, NetworkStatus::Connected { .. });
+        let online_connected = self.online_actions_available();
         let yielded = match &mut self.current_scene {
             Scene::PlayingTTT(game)
                 if matches!(game.mode, GameMode::OnlinePvP(_)) && online_connected =>
@@ -618,7 +1480,7 @@ impl App {
 
     /// Handles 'c' key input to concede an active online game.
     pub fn handle_concede(&mut self) {
-        let online_connected = matches!(self.network_status, NetworkStatus::Connected { .. });
+        let online_connected = self.online_actions_available();
         let conceded = match &mut self.current_scene {
             Scene::PlayingTTT(game)
                 if matches!(game.mode, GameMode::OnlinePvP(_)) && online_connected =>
@@ -637,51 +1499,293 @@ impl App {
         }
     }
 
-    /// Handles 'r' key input to reset the current game.
-    pub fn handle_reset(&mut self) {
-        let online_connected = matches!(self.network_status, NetworkStatus::Connected { .. });
-        let requested = match &mut self.current_scene {
-            Scene::PlayingTTT(game)
-                if matches!(game.mode, GameMode::OnlinePvP(_)) && online_connected =>
-            {
-                game.request_online_rematch()
-            }
-            Scene::PlayingTTT(game) if matches!(game.mode, GameMode::OnlinePvP(_)) => false,
+    /// Handles 'x' key input to resign a local game.
+    ///
+    /// The first press asks for confirmation, and pressing the key again
+    /// right away resigns for the player on turn.
+    pub fn handle_resign(&mut self) {
+        let can_resign = match &self.current_scene {
             Scene::PlayingTTT(game) => {
-                game.reset_game();
-                false
-            }
-            Scene::PlayingUTT(game)
-                if matches!(game.mode, GameMode::OnlinePvP(_)) && online_connected =>
-            {
-                game.request_online_rematch()
+                game.board.state == GameState::Playing
+                    && matches!(game.mode, GameMode::LocalPvP | GameMode::PvE(_))
             }
-            Scene::PlayingUTT(game) if matches!(game.mode, GameMode::OnlinePvP(_)) => false,
             Scene::PlayingUTT(game) => {
-                game.reset_game();
-                false
+                game.big_board.state == GameState::Playing
+                    && matches!(game.mode, GameMode::LocalPvP | GameMode::PvE(_))
             }
             _ => false,
         };
-        if requested {
-            self.send_online_action(NetworkCommand::SendRematchReady);
+        if !can_resign {
+            return;
+        }
+        if !self.resign_pending {
+            self.resign_pending = true;
+            self.notice = Some("Press X again to resign".to_string());
+            return;
         }
+        self.resign_pending = false;
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.resign(),
+            Scene::PlayingUTT(game) => game.resign(),
+            _ => false,
+        };
     }
 
-    /// Handles 'm' key input to return to main menu from game.
-    pub fn handle_main_menu(&mut self) {
-        let is_online = matches!(
-            &self.current_scene,
-            Scene::PlayingTTT(game) if matches!(game.mode, GameMode::OnlinePvP(_))
-        ) || matches!(
-            &self.current_scene,
-            Scene::PlayingUTT(game) if matches!(game.mode, GameMode::OnlinePvP(_))
-        );
-        let is_game = matches!(
-            self.current_scene,
-            Scene::PlayingTTT(_) | Scene::PlayingUTT(_)
-        );
-
+    /// Handles '=' key input to offer the opponent a draw in a Local PvP game.
+    pub fn handle_offer_draw(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.offer_draw(),
+            Scene::PlayingUTT(game) => game.offer_draw(),
+            _ => false,
+        };
+    }
+
+    /// Answers a pending draw offer: 'y' accepts it and 'n' declines it.
+    ///
+    /// Other keys leave the offer standing until the next move.
+    ///
+    /// # Returns
+    /// True if the key answered an offer.
+    fn answer_draw_offer(&mut self, code: KeyCode) -> bool {
+        let accept = match code {
+            KeyCode::Char('y' | 'Y') => true,
+            KeyCode::Char('n' | 'N') => false,
+            _ => return false,
+        };
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) if accept => game.accept_draw(),
+            Scene::PlayingTTT(game) => game.decline_draw(),
+            Scene::PlayingUTT(game) if accept => game.accept_draw(),
+            Scene::PlayingUTT(game) => game.decline_draw(),
+            _ => false,
+        }
+    }
+
+    /// Handles 't' key input to toggle coaching tips in a game.
+    pub fn handle_tips(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.toggle_tips(),
+            Scene::PlayingUTT(game) => game.toggle_tips(),
+            _ => {}
+        }
+    }
+
+    /// Handles F2 key input to save the current game to [`App::save_path`].
+    ///
+    /// Online and Solitaire games cannot be saved.
+    pub fn handle_save(&mut self) {
+        let Some(path) = &self.save_path else {
+            return;
+        };
+        let saved = match &self.current_scene {
+            Scene::PlayingTTT(game) => game.snapshot(),
+            Scene::PlayingUTT(game) => game.snapshot(),
+            _ => return,
+        };
+        let notice = match saved.map(|saved| write_save(path, &saved)) {
+            Some(Ok(())) => "Game saved".to_string(),
+            Some(Err(error)) => format!("Unable to save: {error}"),
+            None => "This game cannot be saved".to_string(),
+        };
+        self.notice = Some(notice);
+    }
+
+    /// Handles F1 key input to show the key bindings over the current game.
+    pub fn handle_help(&mut self) {
+        if matches!(
+            self.current_scene,
+            Scene::PlayingTTT(_) | Scene::PlayingUTT(_)
+        ) {
+            self.show_help = true;
+        }
+    }
+
+    /// Handles F3 key input to switch to the next color theme from a menu.
+    pub fn handle_theme(&mut self) {
+        if matches!(
+            self.current_scene,
+            Scene::MainMenu(_)
+                | Scene::TTTMenu(..)
+                | Scene::UTTMenu(..)
+                | Scene::OnlineMenu(..)
+                | Scene::FirstPlayerMenu(..)
+                | Scene::DifficultyMenu(_)
+                | Scene::SideMenu(..)
+                | Scene::AIMenu(..)
+        ) {
+            self.theme = self.theme.next();
+            self.notice = Some(format!("Theme: {}", self.theme.name));
+        }
+    }
+
+    /// Handles '?' key input to highlight the suggested cell of a classic game.
+    pub fn handle_hint_cell(&mut self) {
+        if let Scene::PlayingTTT(game) = &mut self.current_scene {
+            game.toggle_hint_cell();
+        }
+    }
+
+    /// Handles 'v' key input to switch between weak and perfect hints.
+    pub fn handle_hint_strength(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.toggle_hint_strength(),
+            Scene::PlayingUTT(game) => game.toggle_hint_strength(),
+            _ => {}
+        }
+    }
+
+    /// Handles 'b' key input to toggle showing the optimal move after a mistake.
+    pub fn handle_mistakes(&mut self) {
+        if let Scene::PlayingTTT(game) = &mut self.current_scene {
+            game.toggle_mistakes();
+        }
+    }
+
+    /// Handles 'n' key input to toggle coloring cells by where they send the opponent.
+    pub fn handle_destinations(&mut self) {
+        if let Scene::PlayingUTT(game) = &mut self.current_scene {
+            game.toggle_destinations();
+        }
+    }
+
+    /// Handles 'y' key input to toggle drawing won Ultimate boards as one large mark.
+    pub fn handle_big_marks(&mut self) {
+        if let Scene::PlayingUTT(game) = &mut self.current_scene {
+            game.toggle_big_marks();
+        }
+    }
+
+    /// Handles 'g' key input to toggle the evaluation graph.
+    pub fn handle_graph(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.toggle_graph(),
+            Scene::PlayingUTT(game) => game.toggle_graph(),
+            _ => {}
+        }
+    }
+
+    /// Handles 'p' key input to toggle the coordinate labels around the board.
+    pub fn handle_coordinates(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.toggle_coordinates(),
+            Scene::PlayingUTT(game) => game.toggle_coordinates(),
+            _ => {}
+        }
+    }
+
+    /// Handles 'o' key input to toggle watching the game without playing moves.
+    pub fn handle_read_only(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.toggle_read_only(),
+            Scene::PlayingUTT(game) => game.toggle_read_only(),
+            _ => {}
+        }
+    }
+
+    /// Handles 'i' key input to toggle the analysis bar.
+    pub fn handle_analysis(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.toggle_analysis(),
+            Scene::PlayingUTT(game) => game.toggle_analysis(),
+            _ => {}
+        }
+    }
+
+    /// Handles F4 input to enter or leave analysis mode in a classic game.
+    ///
+    /// Leaving restores the position the analysis started from.
+    pub fn handle_explore(&mut self) {
+        if let Scene::PlayingTTT(game) = &mut self.current_scene
+            && !game.exit_analysis()
+        {
+            game.enter_analysis();
+        }
+    }
+
+    /// Handles 'u' key input to take back the last move of a classic game.
+    pub fn handle_undo(&mut self) {
+        if let Scene::PlayingTTT(game) = &mut self.current_scene {
+            game.undo();
+        }
+    }
+
+    /// Handles Ctrl+R input to replay a move taken back in a classic game.
+    pub fn handle_redo(&mut self) {
+        if let Scene::PlayingTTT(game) = &mut self.current_scene {
+            game.redo();
+        }
+    }
+
+    /// Handles 'r' key input to reset the current game.
+    pub fn handle_reset(&mut self) {
+        let online_connected = self.online_actions_available();
+        let requested = match &mut self.current_scene {
+            Scene::PlayingTTT(game)
+                if matches!(game.mode, GameMode::OnlinePvP(_)) && online_connected =>
+            {
+                game.request_online_rematch()
+            }
+            Scene::PlayingTTT(game) if matches!(game.mode, GameMode::OnlinePvP(_)) => false,
+            Scene::PlayingTTT(game) => {
+                game.reset_game();
+                false
+            }
+            Scene::PlayingUTT(game)
+                if matches!(game.mode, GameMode::OnlinePvP(_)) && online_connected =>
+            {
+                game.request_online_rematch()
+            }
+            Scene::PlayingUTT(game) if matches!(game.mode, GameMode::OnlinePvP(_)) => false,
+            Scene::PlayingUTT(game) => {
+                game.reset_game();
+                false
+            }
+            Scene::PlayingGomoku(game) => {
+                game.reset_game();
+                false
+            }
+            _ => false,
+        };
+        if requested {
+            self.send_online_action(NetworkCommand::SendRematchReady);
+        }
+    }
+
+    /// Handles 'f' key input to start a classic Local PvP rematch with the
+    /// players' marks swapped.
+    pub fn handle_swap_sides(&mut self) {
+        if let Scene::PlayingTTT(game) = &mut self.current_scene {
+            game.rematch_swap_sides();
+        }
+    }
+
+    /// Handles 'e' key input to restart the current game with the same seed.
+    pub fn handle_restart_same_seed(&mut self) {
+        match &mut self.current_scene {
+            Scene::PlayingTTT(game) => game.restart_same_seed(),
+            Scene::PlayingUTT(game) => game.restart_same_seed(),
+            _ => {}
+        }
+    }
+
+    /// Handles 'm' key input to return to main menu from game.
+    pub fn handle_main_menu(&mut self) {
+        let is_online = matches!(
+            &self.current_scene,
+            Scene::PlayingTTT(game) if matches!(game.mode, GameMode::OnlinePvP(_))
+        ) || matches!(
+            &self.current_scene,
+            Scene::PlayingUTT(game) if matches!(game.mode, GameMode::OnlinePvP(_))
+        );
+        let is_game = matches!(
+            self.current_scene,
+            Scene::PlayingTTT(_)
+                | Scene::PlayingUTT(_)
+                | Scene::PlayingGomoku(_)
+                | Scene::Replay(_)
+                | Scene::Statistics
+        );
+
         if is_online {
             self.stop_network();
         }
@@ -690,16 +1794,44 @@ impl App {
         }
     }
 
+    /// Handles 'q' key input to exit the application.
+    ///
+    /// Quitting while a game is still being played asks for confirmation
+    /// first, so a stray key press does not lose the game.
+    pub fn handle_quit(&mut self) {
+        let in_progress = match &self.current_scene {
+            Scene::PlayingTTT(game) => game.board.state == GameState::Playing,
+            Scene::PlayingUTT(game) => game.big_board.state == GameState::Playing,
+            Scene::PlayingGomoku(game) => game.board.state == GameState::Playing,
+            _ => false,
+        };
+        if in_progress {
+            self.confirm_quit = true;
+        } else {
+            self.quit();
+        }
+    }
+
     /// Sets the quit flag to exit the application.
     pub fn quit(&mut self) {
         self.should_quit = true;
     }
 }
 
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game::{Board, GameState, Mark::X};
+    use crate::ai::Move;
+    use crate::game::Mark::X;
+    use crate::net::{RemotePeer, TcpPeer};
+    use std::io::BufReader;
+    use std::net::{TcpListener, TcpStream};
 
     #[test]
     fn test_app_new_starts_at_menu() {
@@ -710,6 +1842,181 @@ mod tests {
         assert!(!app.network_is_active());
     }
 
+    #[test]
+    fn test_hot_seat_ignores_inactive_player_keys() {
+        let mut app = App::new();
+        app.start_hot_seat_game(GameVariant::Classic);
+
+        assert!(app.handle_split_key(KeyCode::Right));
+        assert!(app.handle_split_key(KeyCode::Enter));
+        assert!(app.handle_split_key(KeyCode::Char('l')));
+        assert!(!app.handle_split_key(KeyCode::Char('r')));
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.turn, 0);
+        assert_eq!((game.selected.row, game.selected.col), (0, 0));
+
+        assert!(app.handle_split_key(KeyCode::Char('d')));
+        assert!(app.handle_split_key(KeyCode::Char(' ')));
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.get(0, 1), Some(X));
+        assert_eq!(game.active_player, Mark::O);
+
+        assert!(app.handle_split_key(KeyCode::Char(' ')));
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.turn, 1);
+    }
+
+    #[test]
+    fn test_split_keys_only_apply_to_hot_seat_games() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        assert!(!app.handle_split_key(KeyCode::Char('d')));
+    }
+
+    #[test]
+    fn test_finished_games_are_recorded_once() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        app.handle_second();
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            let Scene::PlayingTTT(game) = &mut app.current_scene else {
+                panic!("expected classic game");
+            };
+            game.selected = Position { row, col };
+            app.handle_enter();
+            app.record_result();
+        }
+        app.record_result();
+
+        assert_eq!(
+            app.results,
+            vec![GameResult {
+                variant: GameVariant::Classic,
+                first_player: Mark::O,
+                outcome: GameState::Won(Mark::O),
+            }]
+        );
+
+        app.handle_reset();
+        app.record_result();
+        assert_eq!(app.results.len(), 1);
+    }
+
+    #[test]
+    fn test_reset_session_clears_results_and_keeps_settings() {
+        let mut app = App::new();
+        app.idle_timeout = Some(IdleTimeout {
+            after: Duration::from_secs(60),
+            action: IdleAction::Quit,
+        });
+        app.results.push(GameResult {
+            variant: GameVariant::Ultimate,
+            first_player: Mark::X,
+            outcome: GameState::Draw,
+        });
+
+        let Scene::MainMenu(menu) = &mut app.current_scene else {
+            panic!("expected main menu");
+        };
+        menu.selected_option = 6;
+        assert_eq!(menu.get_selected(), "Reset Session");
+        app.handle_enter();
+
+        assert!(app.results.is_empty());
+        assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+        assert_eq!(
+            app.idle_timeout.map(|timeout| timeout.after),
+            Some(Duration::from_secs(60))
+        );
+
+        app.start_ttt_game(GameMode::LocalPvP);
+        app.reset_session();
+        assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+    }
+
+    #[test]
+    fn test_move_clock_low_time_threshold() {
+        let start = Instant::now();
+        let mut clock = MoveClock::new(Duration::from_secs(20), 0, start);
+        assert!(!clock.is_low());
+
+        clock.tick(0, start + Duration::from_secs(14));
+        assert_eq!(clock.remaining(), Duration::from_secs(6));
+        assert!(!clock.is_low());
+
+        clock.tick(0, start + Duration::from_secs(15));
+        assert!(clock.is_low());
+
+        clock.tick(0, start + Duration::from_secs(30));
+        assert_eq!(clock.remaining(), Duration::ZERO);
+        assert!(clock.is_low());
+
+        clock.tick(1, start + Duration::from_secs(31));
+        assert_eq!(clock.remaining(), Duration::from_secs(20));
+        assert!(!clock.is_low());
+    }
+
+    #[test]
+    fn test_move_clock_runs_only_on_local_turns() {
+        let mut app = App::new();
+        let now = Instant::now();
+        app.start_ttt_game(GameMode::LocalPvP);
+        app.update_move_clock(now);
+        assert!(app.move_clock().is_none());
+
+        app.move_time = Some(Duration::from_secs(10));
+        app.update_move_clock(now);
+        assert!(app.move_clock().is_some());
+
+        app.go_to_main_menu();
+        app.update_move_clock(now);
+        assert!(app.move_clock().is_none());
+    }
+
+    #[test]
+    fn test_idle_timeout_is_disabled_by_default() {
+        let mut app = App::new();
+        app.check_idle(Instant::now() + Duration::from_secs(24 * 60 * 60));
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn test_idle_timeout_bookkeeping() {
+        let mut app = App::new();
+        let start = Instant::now();
+        app.idle_timeout = Some(IdleTimeout {
+            after: Duration::from_secs(60),
+            action: IdleAction::MainMenu,
+        });
+        app.record_input(start);
+        app.start_ttt_game(GameMode::LocalPvP);
+
+        app.check_idle(start + Duration::from_secs(59));
+        assert!(matches!(app.current_scene, Scene::PlayingTTT(_)));
+
+        app.record_input(start + Duration::from_secs(30));
+        app.check_idle(start + Duration::from_secs(80));
+        assert!(matches!(app.current_scene, Scene::PlayingTTT(_)));
+
+        app.check_idle(start + Duration::from_secs(90));
+        assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+
+        app.idle_timeout = Some(IdleTimeout {
+            after: Duration::from_secs(60),
+            action: IdleAction::Quit,
+        });
+        app.check_idle(start + Duration::from_secs(120));
+        assert!(!app.should_quit);
+        app.check_idle(start + Duration::from_secs(150));
+        assert!(app.should_quit);
+    }
+
     #[test]
     fn test_network_starts_and_stops_lazily() {
         let mut app = App::new();
@@ -966,7 +2273,7 @@ mod tests {
         assert!(!app.network_is_active());
 
         app.handle_esc();
-        assert!(matches!(app.current_scene, Scene::TTTMenu(_)));
+        assert!(matches!(app.current_scene, Scene::TTTMenu(..)));
     }
 
     #[test]
@@ -983,7 +2290,7 @@ mod tests {
         assert!(!app.network_is_active());
 
         app.handle_esc();
-        assert!(matches!(app.current_scene, Scene::UTTMenu(_)));
+        assert!(matches!(app.current_scene, Scene::UTTMenu(..)));
     }
 
     #[test]
@@ -1008,15 +2315,91 @@ mod tests {
         assert_eq!(app.network_status, NetworkStatus::Idle);
     }
 
+    /// Returns a TCP link over the loopback interface and the peer at its other end.
+    fn tcp_link() -> (RemoteLink, TcpPeer) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let guest = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (host, _) = listener.accept().unwrap();
+        let end = |stream: TcpStream| {
+            RemotePeer::new(BufReader::new(stream.try_clone().unwrap()), stream)
+        };
+        (RemoteLink::start(end(host)), end(guest))
+    }
+
+    /// Polls the network until `done` holds for the app.
+    fn poll_until(app: &mut App, done: impl Fn(&App) -> bool) {
+        for _ in 0..500 {
+            app.poll_network_events();
+            if done(app) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        panic!("the remote event never arrived");
+    }
+
+    fn remote_cell(app: &App, row: usize, col: usize) -> Option<Mark> {
+        match &app.current_scene {
+            Scene::PlayingTTT(game) => game.board.get(row, col),
+            _ => None,
+        }
+    }
+
     #[test]
-    fn test_join_ticket_input_and_cancellation() {
+    fn test_tcp_game_exchanges_moves_and_reports_disconnects() {
+        let (link, mut remote) = tcp_link();
         let mut app = App::new();
-        app.go_to_online_menu(GameVariant::Classic);
-        app.handle_down();
+        app.start_remote_game(link, X);
+        assert!(app.network_is_active());
+
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.selected = Position { row: 1, col: 1 };
         app.handle_enter();
+        assert_eq!(remote.receive_move(), Ok((X, 1, 1)));
 
-        assert!(matches!(
-            app.current_scene,
+        remote.send_move(O, 0, 0).unwrap();
+        poll_until(&mut app, |app| remote_cell(app, 0, 0) == Some(O));
+
+        // Concessions are not part of the TCP protocol.
+        app.handle_concede();
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.state, GameState::Playing);
+
+        drop(remote);
+        poll_until(&mut app, |app| {
+            app.network_status == NetworkStatus::OpponentDisconnected
+        });
+        assert!(!app.network_is_active());
+    }
+
+    #[test]
+    fn test_illegal_tcp_move_closes_the_link() {
+        let (link, mut remote) = tcp_link();
+        let mut app = App::new();
+        app.start_remote_game(link, X);
+
+        // O moves although X, the local player, is on turn.
+        remote.send_move(O, 0, 0).unwrap();
+        poll_until(&mut app, |app| {
+            matches!(app.network_status, NetworkStatus::Failed(_))
+        });
+        assert_eq!(remote_cell(&app, 0, 0), None);
+        assert!(!app.network_is_active());
+    }
+
+    #[test]
+    fn test_join_ticket_input_and_cancellation() {
+        let mut app = App::new();
+        app.go_to_online_menu(GameVariant::Classic);
+        app.handle_down();
+        app.handle_enter();
+
+        assert!(matches!(
+            app.current_scene,
             Scene::JoiningOnline(_, GameVariant::Classic)
         ));
         assert!(!app.network_is_active());
@@ -1065,6 +2448,117 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_misere_rules_apply_to_new_classic_games() {
+        let mut app = App::new();
+        app.go_to_ttt_menu();
+        let Scene::TTTMenu(menu, _) = &mut app.current_scene else {
+            panic!("Expected Tic Tac Toe menu");
+        };
+        menu.selected_option = 6;
+        assert_eq!(menu.get_selected(), STANDARD_RULES_OPTION);
+        app.handle_enter();
+
+        assert_eq!(app.rules, RuleSet::Misere);
+        let Scene::TTTMenu(menu, _) = &app.current_scene else {
+            panic!("Expected Tic Tac Toe menu");
+        };
+        assert_eq!(menu.get_selected(), MISERE_RULES_OPTION);
+
+        app.start_hot_seat_game(GameVariant::Classic);
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("Expected Tic Tac Toe game");
+        };
+        assert_eq!(game.board.rules(), RuleSet::Misere);
+        game.reset_game();
+        assert_eq!(game.board.rules(), RuleSet::Misere);
+
+        // The menu remembers the rules when it is shown again.
+        app.go_to_ttt_menu();
+        let Scene::TTTMenu(menu, _) = &app.current_scene else {
+            panic!("Expected Tic Tac Toe menu");
+        };
+        assert!(menu.options.contains(&MISERE_RULES_OPTION));
+    }
+
+    #[test]
+    fn test_gomoku_from_main_menu_is_won_with_four_in_a_row() {
+        let mut app = App::new();
+        app.handle_down();
+        app.handle_down();
+        app.handle_enter();
+        let Scene::PlayingGomoku(game) = &app.current_scene else {
+            panic!("Expected Gomoku game");
+        };
+        assert_eq!(game.selected, Position { row: 2, col: 2 });
+
+        // X plays down the middle column while O answers in the next one.
+        for _ in 0..2 {
+            app.handle_up();
+        }
+        for _ in 0..3 {
+            app.handle_enter();
+            app.handle_right();
+            app.handle_enter();
+            app.handle_left();
+            app.handle_down();
+        }
+        app.handle_enter();
+
+        let Scene::PlayingGomoku(game) = &app.current_scene else {
+            panic!("Expected Gomoku game");
+        };
+        assert_eq!(game.board.state, GameState::Won(X));
+
+        app.handle_reset();
+        let Scene::PlayingGomoku(game) = &app.current_scene else {
+            panic!("Expected Gomoku game");
+        };
+        assert_eq!(game.board.state, GameState::Playing);
+        assert!(game.board.cells.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_four_by_four_from_main_menu_is_won_with_a_full_line() {
+        let mut app = App::new();
+        for _ in 0..3 {
+            app.handle_down();
+        }
+        app.handle_enter();
+        let Scene::PlayingGomoku(game) = &app.current_scene else {
+            panic!("Expected 4x4 game");
+        };
+        assert_eq!(game.kind, LargeBoard::FourByFour);
+        assert_eq!(game.selected, Position { row: 0, col: 0 });
+
+        // X fills the top row while O fills the second one; the selection
+        // skips to the next free cell after each move.
+        for _ in 0..3 {
+            app.handle_enter();
+            app.handle_down();
+            app.handle_enter();
+        }
+        let Scene::PlayingGomoku(game) = &app.current_scene else {
+            panic!("Expected 4x4 game");
+        };
+        assert_eq!(game.board.state, GameState::Playing);
+        assert_eq!(game.selected, Position { row: 0, col: 3 });
+        app.handle_enter();
+
+        let Scene::PlayingGomoku(game) = &app.current_scene else {
+            panic!("Expected 4x4 game");
+        };
+        assert_eq!(game.board.state, GameState::Won(X));
+
+        app.handle_reset();
+        let Scene::PlayingGomoku(game) = &app.current_scene else {
+            panic!("Expected 4x4 game");
+        };
+        assert_eq!(game.kind, LargeBoard::FourByFour);
+        assert_eq!(game.board.size, 4);
+        assert!(game.board.cells.iter().all(Option::is_none));
+    }
+
     #[test]
     fn test_go_to_main_menu() {
         let mut app = App::new();
@@ -1095,6 +2589,279 @@ mod tests {
         assert!(matches!(app.current_scene, Scene::PlayingTTT(_)));
     }
 
+    #[test]
+    fn test_first_player_menu_starts_local_game() {
+        let mut app = App::new();
+        app.handle_enter();
+        app.handle_down();
+        app.handle_enter();
+        assert!(matches!(
+            app.current_scene,
+            Scene::FirstPlayerMenu(_, GameVariant::Ultimate)
+        ));
+
+        app.handle_down();
+        app.handle_enter();
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected Ultimate game");
+        };
+        assert_eq!(game.active_player, Mark::O);
+
+        app.go_to_first_player_menu(GameVariant::Classic);
+        app.handle_esc();
+        assert!(matches!(app.current_scene, Scene::TTTMenu(..)));
+    }
+
+    #[test]
+    fn test_classic_medium_ai_asks_for_difficulty() {
+        let mut app = App::new();
+        app.go_to_ai_menu(AIMenuStatus::TTTpve);
+        app.handle_down();
+        app.handle_enter();
+        assert!(matches!(app.current_scene, Scene::DifficultyMenu(_)));
+
+        app.handle_esc();
+        assert!(matches!(
+            app.current_scene,
+            Scene::AIMenu(_, AIMenuStatus::TTTpve)
+        ));
+
+        for (downs, difficulty) in [(2, Difficulty::Tactical), (3, Difficulty::Hard)] {
+            app.go_to_difficulty_menu();
+            for _ in 0..downs {
+                app.handle_down();
+            }
+            app.handle_enter();
+            assert!(matches!(
+                app.current_scene,
+                Scene::SideMenu(_, _, GameVariant::Classic)
+            ));
+            app.handle_enter();
+            let Scene::PlayingTTT(game) = &app.current_scene else {
+                panic!("expected classic game");
+            };
+            assert_eq!(
+                game.mode,
+                GameMode::PvE(Medium(SimpleAi::with_difficulty(O, difficulty)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_left_and_right_cycle_the_opponent_of_play_vs_ai() {
+        let mut app = App::new();
+        app.go_to_ttt_menu();
+        // Away from "Play vs AI", the keys leave the opponent alone.
+        app.handle_right();
+        let Scene::TTTMenu(menu, opponent) = &mut app.current_scene else {
+            panic!("expected classic menu");
+        };
+        assert_eq!(*opponent, Opponent::Simple);
+        menu.selected_option = 3;
+
+        for expected in [Opponent::Mcts, Opponent::Minimax, Opponent::Random] {
+            app.handle_right();
+            let Scene::TTTMenu(menu, opponent) = &app.current_scene else {
+                panic!("expected classic menu");
+            };
+            assert_eq!(*opponent, expected);
+            assert_eq!(menu.get_selected(), expected.menu_option());
+        }
+        app.handle_left();
+        app.handle_left();
+        let Scene::TTTMenu(_, opponent) = &app.current_scene else {
+            panic!("expected classic menu");
+        };
+        assert_eq!(*opponent, Opponent::Mcts);
+
+        // Ultimate games have no Minimax opponent, so MCTS wraps to Random.
+        assert_eq!(Opponent::Mcts.next(GameVariant::Ultimate), Opponent::Random);
+        assert_eq!(
+            Opponent::Random.previous(GameVariant::Ultimate),
+            Opponent::Mcts
+        );
+    }
+
+    #[test]
+    fn test_confirming_play_vs_ai_builds_the_chosen_opponent() {
+        let cases = [
+            (GameVariant::Classic, 3, 2, "Minimax"),
+            (GameVariant::Classic, 3, 3, "Random"),
+            (GameVariant::Ultimate, 3, 1, "MCTS"),
+        ];
+        for (game, index, steps, expected) in cases {
+            let mut app = App::new();
+            match game {
+                GameVariant::Classic => app.go_to_ttt_menu(),
+                GameVariant::Ultimate => app.go_to_utt_menu(),
+            }
+            for _ in 0..index {
+                app.handle_down();
+            }
+            for _ in 0..steps {
+                app.handle_right();
+            }
+            app.handle_enter();
+            let Scene::SideMenu(_, ai, side_game) = &app.current_scene else {
+                panic!("expected side menu");
+            };
+            let built = match ai {
+                Weak(_) => "Random",
+                Medium(_) => "Simple",
+                AI::Minimax(_) => "Minimax",
+                StrongTTT(_) if game == GameVariant::Classic => "MCTS",
+                StrongUTT(_) if game == GameVariant::Ultimate => "MCTS",
+                _ => "other",
+            };
+            assert_eq!(built, expected);
+            assert_eq!(*side_game, game);
+            let opponent = ai.clone();
+
+            // The game starts against the chosen AI, playing the other mark.
+            app.handle_enter();
+            let mode = match &app.current_scene {
+                Scene::PlayingTTT(game) => &game.mode,
+                Scene::PlayingUTT(game) => &game.mode,
+                _ => panic!("expected a game"),
+            };
+            let GameMode::PvE(ai) = mode else {
+                panic!("expected a game against the AI");
+            };
+            assert_eq!(ai.name(), opponent.name());
+            assert_eq!(ai.get_mark(), O);
+        }
+    }
+
+    /// Picks the Weak AI, then the side menu option at `index`.
+    fn start_weak_game_as(app: &mut App, index: usize) {
+        app.go_to_ai_menu(AIMenuStatus::TTTpve);
+        app.handle_enter();
+        let Scene::SideMenu(menu, _, _) = &mut app.current_scene else {
+            panic!("expected side menu");
+        };
+        menu.selected_option = index;
+        app.handle_enter();
+    }
+
+    #[test]
+    fn test_ultimate_strong_ai_asks_for_side() {
+        let mut app = App::new();
+        app.go_to_ai_menu(AIMenuStatus::UTTpve);
+        let Scene::AIMenu(menu, _) = &mut app.current_scene else {
+            panic!("expected AI menu");
+        };
+        menu.selected_option = 2;
+        app.handle_enter();
+        let Scene::SideMenu(menu, ai, GameVariant::Ultimate) = &mut app.current_scene else {
+            panic!("expected ultimate side menu");
+        };
+        assert!(matches!(ai, StrongUTT(_)));
+
+        menu.selected_option = 1;
+        app.handle_enter();
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected ultimate game");
+        };
+        let GameMode::PvE(ai) = &game.mode else {
+            panic!("expected game against the AI");
+        };
+        assert_eq!(ai.get_mark(), X);
+        assert_eq!(game.turn, 1);
+        assert_eq!(game.active_player, O);
+
+        app.go_to_side_menu(Weak(O), GameVariant::Ultimate);
+        app.handle_esc();
+        assert!(matches!(
+            app.current_scene,
+            Scene::AIMenu(_, AIMenuStatus::UTTpve)
+        ));
+    }
+
+    #[test]
+    fn test_playing_as_x_moves_first() {
+        let mut app = App::new();
+        start_weak_game_as(&mut app, 0);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.mode, GameMode::PvE(Weak(O)));
+        assert_eq!(game.human_mark(), Some(X));
+        assert_eq!(game.turn, 0);
+        assert_eq!(game.active_player, X);
+    }
+
+    #[test]
+    fn test_playing_as_o_lets_the_ai_open() {
+        let mut app = App::new();
+        start_weak_game_as(&mut app, 1);
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.mode, GameMode::PvE(Weak(X)));
+        assert_eq!(game.human_mark(), Some(O));
+        assert_eq!(game.turn, 1);
+        assert_eq!(game.active_player, O);
+
+        game.reset_game();
+        assert_eq!(game.turn, 1);
+        assert_eq!(game.active_player, O);
+    }
+
+    #[test]
+    fn test_random_side_matches_who_moved_first() {
+        let mut app = App::new();
+        start_weak_game_as(&mut app, 2);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        let human_mark = game.human_mark().unwrap();
+        assert_eq!(game.active_player, human_mark);
+        assert_eq!(game.turn, if human_mark == X { 0 } else { 1 });
+
+        app.go_to_side_menu(Weak(O), GameVariant::Classic);
+        app.handle_esc();
+        assert!(matches!(
+            app.current_scene,
+            Scene::AIMenu(_, AIMenuStatus::TTTpve)
+        ));
+    }
+
+    #[test]
+    fn test_imitator_learns_across_the_session() {
+        let mut app = App::new();
+        app.go_to_ai_menu(AIMenuStatus::TTTpve);
+        let Scene::AIMenu(menu, _) = &mut app.current_scene else {
+            panic!("expected AI menu");
+        };
+        menu.selected_option = 3;
+        assert_eq!(menu.get_selected(), "Imitator");
+        app.handle_enter();
+        app.handle_enter();
+
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert!(matches!(game.mode, GameMode::PvE(AI::Imitator(_))));
+        game.selected = Position { row: 2, col: 0 };
+        app.handle_enter();
+
+        app.go_to_main_menu();
+        assert_eq!(
+            app.imitator_memory
+                .borrow()
+                .favourite(&SmallBoard::new(), X),
+            Some(Move::Base(2, 0))
+        );
+
+        app.reset_session();
+        assert_eq!(
+            app.imitator_memory
+                .borrow()
+                .favourite(&SmallBoard::new(), X),
+            None
+        );
+    }
+
     #[test]
     fn test_handle_reset_resets_game() {
         let mut app = App::new();
@@ -1126,6 +2893,62 @@ mod tests {
         assert!(matches!(app.current_scene, Scene::MainMenu(_)));
     }
 
+    #[test]
+    fn test_m_key_leaves_either_game_for_the_main_menu() {
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+        let mut app = App::new();
+        for (start, key) in [
+            App::start_ttt_game as fn(&mut App, GameMode),
+            App::start_utt_game,
+        ]
+        .into_iter()
+        .zip(['m', 'M'])
+        {
+            start(&mut app, GameMode::LocalPvP);
+            press(&mut app, KeyCode::Enter);
+            press(&mut app, KeyCode::Char(key));
+            assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+            assert!(!app.should_quit);
+            assert!(!app.confirm_quit);
+        }
+
+        // The next game starts from an empty board.
+        app.start_ttt_game(GameMode::LocalPvP);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board, SmallBoard::new());
+        assert_eq!(game.turn, 0);
+    }
+
+    #[test]
+    fn test_f4_explores_a_variation_and_restores_the_game() {
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        press(&mut app, KeyCode::Char('4'));
+        press(&mut app, KeyCode::F(4));
+        for cell in ['0', '3', '1', '5', '2'] {
+            press(&mut app, KeyCode::Char(cell));
+        }
+        app.handle_tick();
+        assert!(app.results.is_empty());
+
+        press(&mut app, KeyCode::F(4));
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert!(!game.is_analyzing());
+        assert_eq!(game.board.to_str_grid(), "...|.X.|...");
+        assert_eq!(game.active_player, Mark::O);
+    }
+
     #[test]
     fn test_main_menu_stops_online_network() {
         let mut app = App::new();
@@ -1162,4 +2985,593 @@ mod tests {
             _ => panic!("Expected MainMenu"),
         }
     }
+
+    #[test]
+    fn test_replay_scene_navigation() {
+        let mut app = App::new();
+        app.current_scene = Scene::Replay(Replay::from_log("classic X 4 0"));
+
+        app.handle_up();
+        let Scene::Replay(replay) = &app.current_scene else {
+            panic!("expected replay scene");
+        };
+        assert!(replay.delay < crate::scenes::DEFAULT_REPLAY_DELAY);
+
+        app.handle_esc();
+        assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+    }
+
+    #[test]
+    fn test_finished_games_are_logged_for_replay() {
+        let path = std::env::temp_dir()
+            .join(format!("tic-tac-foe-log-{}", std::process::id()))
+            .join("games.log");
+        let _ = std::fs::remove_file(&path);
+        let mut app = App::new();
+        app.game_log_path = Some(path.clone());
+
+        app.start_ttt_game(GameMode::LocalPvP);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            let Scene::PlayingTTT(game) = &mut app.current_scene else {
+                panic!("expected classic game");
+            };
+            game.selected = Position { row, col };
+            app.handle_enter();
+        }
+        app.record_result();
+        // A result is only taken once, so the game is logged once.
+        app.record_result();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "classic X 0 3 1 4 2\n"
+        );
+
+        app.start_replay();
+        let Scene::Replay(replay) = &app.current_scene else {
+            panic!("expected replay");
+        };
+        assert_eq!(replay.records.len(), 1);
+        assert_eq!(replay.message, None);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_logged_misere_game_replays_with_the_right_winner() {
+        let path = std::env::temp_dir()
+            .join(format!("tic-tac-foe-misere-log-{}", std::process::id()))
+            .join("games.log");
+        let _ = std::fs::remove_file(&path);
+        let mut app = App::new();
+        app.game_log_path = Some(path.clone());
+        app.rules = RuleSet::Misere;
+
+        app.start_ttt_game(GameMode::LocalPvP);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            let Scene::PlayingTTT(game) = &mut app.current_scene else {
+                panic!("expected classic game");
+            };
+            game.selected = Position { row, col };
+            app.handle_enter();
+        }
+        app.record_result();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "classic misere X 0 3 1 4 2\n"
+        );
+
+        app.start_replay();
+        let Scene::Replay(replay) = &mut app.current_scene else {
+            panic!("expected replay");
+        };
+        while replay.step() {}
+        // X completed the top row, so O won the misère game.
+        assert_eq!(
+            replay.board.map(|board| board.state()),
+            Some(GameState::Won(O))
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_saved_game_can_be_continued_from_main_menu() {
+        let path = std::env::temp_dir()
+            .join(format!("tic-tac-foe-app-{}", std::process::id()))
+            .join("save.json");
+        let mut app = App::new();
+        app.save_path = Some(path.clone());
+        app.go_to_main_menu();
+        let Scene::MainMenu(menu) = &app.current_scene else {
+            panic!("expected main menu");
+        };
+        assert!(!menu.options.contains(&"Continue"));
+
+        app.start_ttt_game(GameMode::PvE(Weak(O)));
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.selected = Position { row: 1, col: 1 };
+        app.handle_enter();
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        let board = game.board;
+        app.handle_save();
+        assert_eq!(app.notice.as_deref(), Some("Game saved"));
+        app.record_input(Instant::now());
+        assert_eq!(app.notice, None);
+
+        app.go_to_main_menu();
+        let Scene::MainMenu(menu) = &app.current_scene else {
+            panic!("expected main menu");
+        };
+        assert_eq!(menu.get_selected(), "Continue");
+        app.handle_enter();
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected resumed classic game");
+        };
+        assert_eq!(game.board, board);
+        assert_eq!(game.turn, 2);
+        assert_eq!(game.active_player, X);
+        assert!(matches!(game.mode, GameMode::PvE(AI::Weak(O))));
+
+        std::fs::write(&path, "not a save").unwrap();
+        app.go_to_main_menu();
+        app.handle_enter();
+        assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+        assert!(app.notice.as_deref().unwrap().contains("corrupt save file"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_key_command_mapping() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(key_command(key(KeyCode::Char('r'))), Some(Command::Reset));
+        assert_eq!(
+            key_command(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)),
+            Some(Command::Redo)
+        );
+        assert_eq!(key_command(key(KeyCode::Char('Q'))), Some(Command::Quit));
+        assert_eq!(key_command(key(KeyCode::F(2))), Some(Command::Save));
+        assert_eq!(key_command(key(KeyCode::F(3))), Some(Command::Theme));
+        assert_eq!(key_command(key(KeyCode::F(4))), Some(Command::Explore));
+        assert_eq!(key_command(key(KeyCode::Char('j'))), Some(Command::Down));
+        assert_eq!(key_command(key(KeyCode::Char(' '))), Some(Command::Enter));
+        assert_eq!(key_command(key(KeyCode::Char('z'))), None);
+        assert_eq!(key_command(key(KeyCode::Tab)), None);
+    }
+
+    #[test]
+    fn test_digits_name_cells_in_reading_order() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let cell = |row, col| Some(Command::Cell(Position { row, col }));
+        assert_eq!(key_command(key(KeyCode::Char('0'))), cell(0, 0));
+        assert_eq!(key_command(key(KeyCode::Char('5'))), cell(1, 2));
+        assert_eq!(key_command(key(KeyCode::Char('8'))), cell(2, 2));
+        assert_eq!(key_command(key(KeyCode::Char('9'))), None);
+    }
+
+    #[test]
+    fn test_digit_plays_the_cell_unless_it_is_occupied() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+
+        press(&mut app, KeyCode::Char('4'));
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.get(1, 1), Some(X));
+        assert_eq!(game.active_player, O);
+
+        let before = game.board;
+        let selected = game.selected;
+        press(&mut app, KeyCode::Char('4'));
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board, before);
+        assert_eq!(game.selected, selected);
+        assert_eq!(game.active_player, O);
+    }
+
+    #[test]
+    fn test_handle_event_ignores_resize_mouse_and_release() {
+        let mut app = App::new();
+        app.notice = Some("Game saved".to_string());
+        let now = Instant::now();
+
+        app.handle_event(Event::Resize(20, 10), now);
+        app.handle_event(
+            Event::Mouse(crossterm::event::MouseEvent {
+                kind: crossterm::event::MouseEventKind::Moved,
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            }),
+            now,
+        );
+        let mut release = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        app.handle_event(Event::Key(release), now);
+        assert!(!app.should_quit);
+        assert!(app.notice.is_some());
+
+        app.handle_event(
+            Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+            now,
+        );
+        let Scene::MainMenu(menu) = &app.current_scene else {
+            panic!("expected main menu");
+        };
+        assert_eq!(menu.selected_option, 1);
+        assert_eq!(app.notice, None);
+
+        app.handle_event(
+            Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)),
+            now,
+        );
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_quitting_a_game_in_progress_needs_confirmation() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.confirm_quit);
+        assert!(!app.should_quit);
+        press(&mut app, KeyCode::Char('n'));
+        assert!(!app.confirm_quit);
+        assert!(!app.should_quit);
+
+        press(&mut app, KeyCode::Char('q'));
+        press(&mut app, KeyCode::Enter);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.get(0, 0), None);
+        assert!(app.confirm_quit);
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_disabled_letter_movement_leaves_arrow_keys() {
+        let mut app = App::new();
+        app.key_bindings.disable_letter_movement();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+        let selected = |app: &App| {
+            let Scene::PlayingTTT(game) = &app.current_scene else {
+                panic!("expected classic game");
+            };
+            game.selected
+        };
+
+        let start = selected(&app);
+        press(&mut app, KeyCode::Char('h'));
+        assert_eq!(selected(&app), start);
+        press(&mut app, KeyCode::Left);
+        assert_ne!(selected(&app), start);
+    }
+
+    #[test]
+    fn test_swapped_rematch_hands_the_hot_seat_keys_to_the_other_mark() {
+        let mut app = App::new();
+        app.start_hot_seat_game(GameVariant::Classic);
+        app.run_command(Command::SwapSides);
+        let now = Instant::now();
+
+        // The arrow keys now belong to X, who moves first.
+        app.handle_event(
+            Event::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE)),
+            now,
+        );
+        app.handle_event(
+            Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            now,
+        );
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert!(game.players_swapped);
+        assert_eq!(game.board.get(0, 0), Some(X));
+        assert_eq!(game.active_player, O);
+    }
+
+    #[test]
+    fn test_read_only_game_ignores_enter() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+
+        press(&mut app, KeyCode::Char('o'));
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Enter);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert!(game.read_only);
+        assert_eq!(game.selected, Position { row: 0, col: 1 });
+        assert_eq!(game.board.state, GameState::Playing);
+        assert_eq!(game.board, SmallBoard::new());
+
+        press(&mut app, KeyCode::Char('o'));
+        press(&mut app, KeyCode::Enter);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.get(0, 1), Some(X));
+    }
+
+    #[test]
+    fn test_quitting_a_finished_game_needs_no_confirmation() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        play_cells(&mut app, &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]);
+        app.run_command(Command::Quit);
+        assert!(!app.confirm_quit);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_theme_is_switched_from_menus_only() {
+        let mut app = App::new();
+        app.handle_event(
+            Event::Key(KeyEvent::new(KeyCode::F(3), KeyModifiers::NONE)),
+            Instant::now(),
+        );
+        assert_eq!(app.theme, Theme::HIGH_CONTRAST);
+        assert_eq!(app.notice.as_deref(), Some("Theme: High Contrast"));
+
+        app.start_ttt_game(GameMode::LocalPvP);
+        app.run_command(Command::Theme);
+        assert_eq!(app.theme, Theme::HIGH_CONTRAST);
+    }
+
+    #[test]
+    fn test_help_overlay_is_dismissed_by_any_key() {
+        let mut app = App::new();
+        app.run_command(Command::Help);
+        assert!(!app.show_help);
+
+        app.start_ttt_game(GameMode::LocalPvP);
+        let now = Instant::now();
+        app.handle_event(
+            Event::Key(KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE)),
+            now,
+        );
+        assert!(app.show_help);
+
+        // The dismissing key is not passed on to the game
+        app.handle_event(
+            Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            now,
+        );
+        assert!(!app.show_help);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.turn, 0);
+        assert_eq!(game.board, SmallBoard::new());
+    }
+
+    /// Plays the given cells in order in the current classic game.
+    fn play_cells(app: &mut App, cells: &[(usize, usize)]) {
+        for &(row, col) in cells {
+            let Scene::PlayingTTT(game) = &mut app.current_scene else {
+                panic!("expected classic game");
+            };
+            game.selected = Position { row, col };
+            app.handle_enter();
+        }
+        app.handle_tick();
+    }
+
+    #[test]
+    fn test_scoreboard_survives_resets_until_main_menu() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let x_wins = [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)];
+        let draw = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 1),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 0),
+            (2, 2),
+        ];
+
+        play_cells(&mut app, &x_wins);
+        app.handle_reset();
+        play_cells(&mut app, &draw);
+        app.handle_tick();
+        app.handle_reset();
+        play_cells(&mut app, &x_wins);
+        app.handle_reset();
+        play_cells(&mut app, &[(1, 1)]);
+        assert_eq!(
+            app.scoreboard,
+            Scoreboard {
+                x_wins: 2,
+                o_wins: 0,
+                draws: 1,
+            }
+        );
+
+        app.handle_main_menu();
+        assert_eq!(app.scoreboard, Scoreboard::default());
+        assert_eq!(app.results.len(), 3);
+    }
+
+    #[test]
+    fn test_resigning_needs_confirmation_and_counts_as_a_loss() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.notice.as_deref(), Some("Press X again to resign"));
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Char('x'));
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.state, GameState::Playing);
+
+        press(&mut app, KeyCode::Char('x'));
+        app.handle_tick();
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.state, GameState::Won(O));
+        assert_eq!(app.scoreboard.o_wins, 1);
+    }
+
+    #[test]
+    fn test_draw_offer_is_answered_with_y_or_n() {
+        let mut app = App::new();
+        app.start_utt_game(GameMode::LocalPvP);
+        let now = Instant::now();
+        let press = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), now)
+        };
+
+        press(&mut app, KeyCode::Char('='));
+        press(&mut app, KeyCode::Char('n'));
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected ultimate game");
+        };
+        assert_eq!(game.draw_offer, None);
+        assert_eq!(game.big_board.state, GameState::Playing);
+        assert!(!game.show_destinations);
+
+        press(&mut app, KeyCode::Char('='));
+        press(&mut app, KeyCode::Char('y'));
+        app.handle_tick();
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected ultimate game");
+        };
+        assert_eq!(game.big_board.state, GameState::Draw);
+        assert!(!game.show_big_marks);
+        assert_eq!(app.scoreboard.draws, 1);
+    }
+
+    #[test]
+    fn test_classic_game_places_marks_and_detects_win() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        play_cells(&mut app, &[(1, 1), (0, 0)]);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.get(1, 1), Some(X));
+        assert_eq!(game.board.get(0, 0), Some(O));
+        assert_eq!(game.board.state, GameState::Playing);
+
+        play_cells(&mut app, &[(0, 2), (0, 1), (2, 0)]);
+        let Scene::PlayingTTT(game) = &app.current_scene else {
+            panic!("expected classic game");
+        };
+        assert_eq!(game.board.state, GameState::Won(X));
+        assert_eq!(
+            game.board.winning_line(),
+            Some([
+                Position { row: 0, col: 2 },
+                Position { row: 1, col: 1 },
+                Position { row: 2, col: 0 },
+            ])
+        );
+        assert_eq!(app.scoreboard.x_wins, 1);
+    }
+
+    #[test]
+    fn test_esc_goes_back_one_scene() {
+        let mut app = App::new();
+        app.go_to_ttt_menu();
+        app.handle_esc();
+        assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+
+        app.go_to_utt_menu();
+        app.handle_esc();
+        assert!(matches!(app.current_scene, Scene::MainMenu(_)));
+
+        app.go_to_difficulty_menu();
+        app.handle_esc();
+        assert!(matches!(
+            app.current_scene,
+            Scene::AIMenu(_, AIMenuStatus::TTTpve)
+        ));
+        app.handle_esc();
+        assert!(matches!(app.current_scene, Scene::TTTMenu(..)));
+
+        app.go_to_ai_menu(AIMenuStatus::UTTeve(Some(Weak(X))));
+        app.handle_esc();
+        assert!(matches!(
+            app.current_scene,
+            Scene::AIMenu(_, AIMenuStatus::UTTeve(None))
+        ));
+        app.handle_esc();
+        assert!(matches!(app.current_scene, Scene::UTTMenu(..)));
+
+        app.go_to_first_player_menu(GameVariant::Ultimate);
+        app.handle_esc();
+        assert!(matches!(app.current_scene, Scene::UTTMenu(..)));
+
+        app.go_to_main_menu();
+        app.handle_esc();
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_esc_deselects_ultimate_board_only_when_free() {
+        let mut app = App::new();
+        app.start_utt_game(GameMode::LocalPvP);
+        app.handle_enter();
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected Ultimate game");
+        };
+        assert!(game.selected_cell.is_some());
+        app.handle_esc();
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected Ultimate game");
+        };
+        assert!(game.selected_cell.is_none());
+
+        // After a move the next board is forced, so Esc keeps the cell selected
+        app.handle_enter();
+        app.handle_enter();
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected Ultimate game");
+        };
+        assert_eq!(game.turn, 1);
+        assert!(game.big_board.active_board.is_some());
+        let selected_cell = game.selected_cell;
+        assert!(selected_cell.is_some());
+        app.handle_esc();
+        let Scene::PlayingUTT(game) = &app.current_scene else {
+            panic!("expected Ultimate game");
+        };
+        assert_eq!(game.selected_cell, selected_cell);
+    }
 }
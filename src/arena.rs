@@ -0,0 +1,122 @@
+use crate::ai::mcts::MCTSAi;
+use crate::ai::random::RandomAI;
+use crate::ai::rng::seed_rng;
+use crate::ai::simple::SimpleAi;
+use crate::ai::{Engine, Game, play_ai_game};
+use crate::game::{GameState, Mark};
+
+/// Engines that can take part in a tournament.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EngineKind {
+    /// [`RandomAI`].
+    Random,
+    /// [`SimpleAi`].
+    Simple,
+    /// [`MCTSAi`].
+    Mcts,
+}
+
+impl EngineKind {
+    /// Every engine kind, from weakest to strongest.
+    pub const ALL: [EngineKind; 3] = [EngineKind::Random, EngineKind::Simple, EngineKind::Mcts];
+
+    /// Builds a fresh engine of this kind playing `mark`.
+    pub fn build<T>(&self, mark: Mark) -> Box<dyn Engine<T>>
+    where
+        T: Game + Clone + Default + PartialEq + 'static,
+    {
+        match self {
+            EngineKind::Random => Box::new(RandomAI),
+            EngineKind::Simple => Box::new(SimpleAi::new(mark)),
+            EngineKind::Mcts => Box::new(MCTSAi::new(T::default(), mark)),
+        }
+    }
+}
+
+/// Outcome of a round-robin tournament.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standings {
+    /// Seed the tournament was played with.
+    pub seed: u64,
+    /// Participating engines, in the order used by `wins`.
+    pub engines: Vec<EngineKind>,
+    /// `wins[i][j]` is the number of games engine `i` won against engine `j`.
+    pub wins: Vec<Vec<u32>>,
+}
+
+/// Plays every pair of engines against each other, alternating who plays X.
+///
+/// The engine randomness is reseeded from `seed` before each game, so a
+/// tournament played with the same seed produces the same results.
+///
+/// # Arguments
+/// * `engines` - The participating engines
+/// * `games_per_pair` - Number of games between each pair of engines
+/// * `seed` - Master seed for all engine randomness
+/// * `max_moves` - Move cap of a single game
+pub fn round_robin<T>(
+    engines: &[EngineKind],
+    games_per_pair: u32,
+    seed: u64,
+    max_moves: u32,
+) -> Standings
+where
+    T: Game + Clone + Default + PartialEq + 'static,
+{
+    let mut wins = vec![vec![0; engines.len()]; engines.len()];
+    let mut game_index = 0u64;
+    for first in 0..engines.len() {
+        for second in first + 1..engines.len() {
+            for game in 0..games_per_pair {
+                let (x, o) = if game % 2 == 0 {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                seed_rng(seed.wrapping_add(game_index));
+                game_index += 1;
+
+                let mut engine_x = engines[x].build::<T>(Mark::X);
+                let mut engine_o = engines[o].build::<T>(Mark::O);
+                let mut board = T::default();
+                let result = play_ai_game(
+                    &mut board,
+                    Mark::X,
+                    engine_x.as_mut(),
+                    engine_o.as_mut(),
+                    max_moves,
+                );
+                match result {
+                    Ok(GameState::Won(Mark::X)) => wins[x][o] += 1,
+                    Ok(GameState::Won(Mark::O)) => wins[o][x] += 1,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Standings {
+        seed,
+        engines: engines.to_vec(),
+        wins,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::MAX_MOVES_CLASSIC;
+    use crate::game::base::SmallBoard;
+
+    #[test]
+    fn test_same_seed_reproduces_standings() {
+        let engines = EngineKind::ALL;
+        let first = round_robin::<SmallBoard>(&engines, 4, 7, MAX_MOVES_CLASSIC);
+        let second = round_robin::<SmallBoard>(&engines, 4, 7, MAX_MOVES_CLASSIC);
+
+        assert_eq!(first, second);
+        assert_eq!(first.seed, 7);
+        let total: u32 = first.wins.iter().flatten().sum();
+        assert!(total <= 12);
+    }
+}
@@ -0,0 +1,58 @@
+use tic_tac_foe::ai::rng::new_seed;
+use tic_tac_foe::ai::{MAX_MOVES_CLASSIC, MAX_MOVES_ULTIMATE};
+use tic_tac_foe::arena::{EngineKind, Standings, round_robin};
+use tic_tac_foe::game::base::SmallBoard;
+use tic_tac_foe::game::ultimate::BigBoard;
+
+const DEFAULT_GAMES_PER_PAIR: u32 = 10;
+
+/// Runs a round-robin tournament between the AI engines and prints the results.
+///
+/// Usage: `arena [--seed <SEED>] [--games <GAMES>] [--ultimate]`
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut seed = None;
+    let mut games_per_pair = DEFAULT_GAMES_PER_PAIR;
+    let mut ultimate = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = Some(next_number(&mut args, &arg)?),
+            "--games" => games_per_pair = next_number(&mut args, &arg)? as u32,
+            "--ultimate" => ultimate = true,
+            _ => return Err(format!("unknown argument '{arg}'").into()),
+        }
+    }
+    let seed = seed.unwrap_or_else(new_seed);
+    println!("Seed: {seed}");
+
+    let standings = if ultimate {
+        round_robin::<BigBoard>(&EngineKind::ALL, games_per_pair, seed, MAX_MOVES_ULTIMATE)
+    } else {
+        round_robin::<SmallBoard>(&EngineKind::ALL, games_per_pair, seed, MAX_MOVES_CLASSIC)
+    };
+    print_standings(&standings);
+    Ok(())
+}
+
+fn next_number(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<u64, String> {
+    args.next()
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("{flag} expects a number"))
+}
+
+/// Prints the win matrix, one row per engine.
+fn print_standings(standings: &Standings) {
+    print!("{:>8}", "");
+    for engine in &standings.engines {
+        print!("{:>8}", format!("{engine:?}"));
+    }
+    println!();
+    for (engine, row) in standings.engines.iter().zip(&standings.wins) {
+        print!("{:>8}", format!("{engine:?}"));
+        for wins in row {
+            print!("{wins:>8}");
+        }
+        println!();
+    }
+}
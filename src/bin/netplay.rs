@@ -0,0 +1,62 @@
+use std::io::{self, BufRead, Write};
+use tic_tac_foe::cli::ask_move;
+use tic_tac_foe::game::{GameState, Mark};
+use tic_tac_foe::net::{self, RemoteGame};
+
+/// Plays a classic game against a friend over TCP, typing moves as cells 0 to 8.
+///
+/// Usage: `netplay host <ADDRESS>` waits for the friend and plays X, while
+/// `netplay join <ADDRESS>` connects to them and plays O.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let (role, address) = match (args.next(), args.next()) {
+        (Some(role), Some(address)) => (role, address),
+        _ => return Err("usage: netplay host|join <ADDRESS>".into()),
+    };
+    let (peer, mark) = match role.as_str() {
+        "host" => {
+            eprintln!("Waiting for a player on {address}...");
+            (net::listen(address.as_str())?, Mark::X)
+        }
+        "join" => (net::connect(address.as_str())?, Mark::O),
+        _ => return Err(format!("unknown role '{role}', use host or join").into()),
+    };
+    println!("Connected, you play {mark}.");
+
+    let mut game = RemoteGame::new(peer, mark);
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+    while game.game.board.state == GameState::Playing {
+        println!("\n{}", game.game.board);
+        if game.is_local_turn() {
+            play_local_turn(&mut game, &mut input, &mut output)?;
+        } else {
+            println!("Waiting for the opponent...");
+            game.receive()?;
+        }
+    }
+
+    println!("\n{}", game.game.board);
+    match game.game.board.state {
+        GameState::Won(winner) if winner == mark => println!("You win!"),
+        GameState::Won(_) => println!("You lose."),
+        _ => println!("Draw."),
+    }
+    Ok(())
+}
+
+/// Asks for moves until a playable cell is chosen and sent.
+fn play_local_turn<R: BufRead, W: Write>(
+    game: &mut RemoteGame<R, W>,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        let (row, col) = ask_move(input, output).ok_or("input ended, leaving the game")?;
+        if game.play_local(row, col)? {
+            return Ok(());
+        }
+        writeln!(output, "That cell is taken.")?;
+    }
+}
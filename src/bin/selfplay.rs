@@ -0,0 +1,61 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use tic_tac_foe::ai::rng::new_seed;
+use tic_tac_foe::arena::EngineKind;
+use tic_tac_foe::selfplay::{self_play, write_csv};
+
+const DEFAULT_GAMES: u32 = 100;
+
+/// Plays classic self-play games and writes position, move and outcome samples as CSV.
+///
+/// Usage: `selfplay [--seed <SEED>] [--games <GAMES>] [--engine random|simple|mcts]
+/// [--augment] [--output <FILE>]`
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut seed = None;
+    let mut games = DEFAULT_GAMES;
+    let mut engine = EngineKind::Mcts;
+    let mut augment = false;
+    let mut output = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = Some(next_number(&mut args, &arg)?),
+            "--games" => games = next_number(&mut args, &arg)? as u32,
+            "--engine" => engine = next_engine(&mut args)?,
+            "--augment" => augment = true,
+            "--output" => output = Some(args.next().ok_or("--output expects a path")?),
+            _ => return Err(format!("unknown argument '{arg}'").into()),
+        }
+    }
+    let seed = seed.unwrap_or_else(new_seed);
+    eprintln!("Seed: {seed}");
+
+    let data = self_play(engine, games, seed, augment)?;
+    let writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+    write_csv(&data.samples, writer)?;
+    eprintln!(
+        "Wrote {} samples from {} games",
+        data.samples.len(),
+        data.games.len()
+    );
+    Ok(())
+}
+
+fn next_number(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<u64, String> {
+    args.next()
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("{flag} expects a number"))
+}
+
+fn next_engine(args: &mut impl Iterator<Item = String>) -> Result<EngineKind, String> {
+    match args.next().as_deref() {
+        Some("random") => Ok(EngineKind::Random),
+        Some("simple") => Ok(EngineKind::Simple),
+        Some("mcts") => Ok(EngineKind::Mcts),
+        _ => Err("--engine expects random, simple or mcts".to_string()),
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::io::{BufRead, Read, Write};
+
+/// Longest input line, in bytes, that is considered for parsing.
+const MAX_LINE_LEN: usize = 64;
+
+/// Reason why a line of input was not accepted as a move.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputError {
+    /// The line was empty or contained only whitespace.
+    Empty,
+    /// The line was not valid UTF-8.
+    InvalidUtf8,
+    /// The line was longer than any valid move.
+    TooLong,
+    /// The line was not a number.
+    NotANumber(String),
+    /// The number does not name a cell.
+    OutOfRange(usize),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(formatter, "please enter a cell from 0 to 8"),
+            InputError::InvalidUtf8 => write!(formatter, "input is not valid text"),
+            InputError::TooLong => write!(formatter, "input is too long"),
+            InputError::NotANumber(token) => write!(formatter, "'{token}' is not a number"),
+            InputError::OutOfRange(index) => {
+                write!(formatter, "cell {index} does not exist, use 0 to 8")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Parses a classic move written as a cell index from 0 to 8 in reading order.
+///
+/// # Arguments
+/// * `line` - The raw input line, surrounding whitespace is ignored
+///
+/// # Returns
+/// The (row, col) of the cell, or the reason the line was rejected.
+pub fn parse_cell(line: &str) -> Result<(usize, usize), InputError> {
+    let token = line.trim();
+    if token.is_empty() {
+        return Err(InputError::Empty);
+    }
+    if token.len() > MAX_LINE_LEN {
+        return Err(InputError::TooLong);
+    }
+    let index = token
+        .parse::<usize>()
+        .map_err(|_| InputError::NotANumber(token.to_string()))?;
+    if index >= 9 {
+        return Err(InputError::OutOfRange(index));
+    }
+    Ok((index / 3, index % 3))
+}
+
+/// Prompts for a classic move until a valid cell is entered.
+///
+/// Invalid lines, including non-UTF-8 and oversized input, are reported on
+/// `output` and the prompt is repeated. At most one byte more than the longest
+/// accepted line is buffered; the rest of an oversized line is skipped.
+///
+/// # Arguments
+/// * `input` - Source of the lines typed by the player
+/// * `output` - Destination for the prompt and error messages
+///
+/// # Returns
+/// The (row, col) of the chosen cell, or None if the input ended or could not
+/// be read, meaning the player gave up.
+pub fn ask_move(input: &mut impl BufRead, output: &mut impl Write) -> Option<(usize, usize)> {
+    let mut buffer = Vec::new();
+    loop {
+        write!(output, "Your move (0-8): ").ok()?;
+        output.flush().ok()?;
+
+        buffer.clear();
+        let read = input
+            .by_ref()
+            .take(MAX_LINE_LEN as u64 + 1)
+            .read_until(b'\n', &mut buffer)
+            .ok()?;
+        if read == 0 {
+            return None;
+        }
+        let parsed = if buffer.len() > MAX_LINE_LEN {
+            if !buffer.ends_with(b"\n") {
+                input.skip_until(b'\n').ok()?;
+            }
+            Err(InputError::TooLong)
+        } else {
+            match std::str::from_utf8(&buffer) {
+                Ok(line) => parse_cell(line),
+                Err(_) => Err(InputError::InvalidUtf8),
+            }
+        };
+        match parsed {
+            Ok(cell) => return Some(cell),
+            Err(error) => writeln!(output, "{error}").ok()?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_cell() {
+        assert_eq!(parse_cell("4"), Ok((1, 1)));
+        assert_eq!(parse_cell("  8\n"), Ok((2, 2)));
+        assert_eq!(parse_cell(""), Err(InputError::Empty));
+        assert_eq!(parse_cell(" \t\n"), Err(InputError::Empty));
+        assert_eq!(parse_cell("9"), Err(InputError::OutOfRange(9)));
+        assert_eq!(
+            parse_cell("-1"),
+            Err(InputError::NotANumber("-1".to_string()))
+        );
+        assert_eq!(
+            parse_cell("x"),
+            Err(InputError::NotANumber("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_ask_move_reprompts_on_invalid_input() {
+        let mut input = Cursor::new(b"\n   \n42\n\xff\xfe\nabc\n7\n".to_vec());
+        let mut output = Vec::new();
+
+        assert_eq!(ask_move(&mut input, &mut output), Some((2, 1)));
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("Your move").count(), 6);
+        assert!(output.contains("cell 42 does not exist"));
+        assert!(output.contains("not valid text"));
+    }
+
+    #[test]
+    fn test_ask_move_rejects_oversized_input() {
+        let mut line = vec![b'1'; MAX_LINE_LEN * 2];
+        line.extend_from_slice(b"\n0\n");
+        let mut output = Vec::new();
+
+        assert_eq!(ask_move(&mut Cursor::new(line), &mut output), Some((0, 0)));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("too long"));
+        // The rest of the long line is skipped rather than read as more moves.
+        assert_eq!(output.matches("Your move").count(), 2);
+    }
+
+    #[test]
+    fn test_ask_move_gives_up_at_end_of_input() {
+        let mut output = Vec::new();
+        assert_eq!(
+            ask_move(&mut Cursor::new(b"foo\n".to_vec()), &mut output),
+            None
+        );
+    }
+}
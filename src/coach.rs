@@ -0,0 +1,301 @@
+use crate::ai::mcts::MCTSAi;
+use crate::ai::simple::SimpleAi;
+use crate::ai::solver::{move_value, perfect_move};
+use crate::ai::{Game, Move};
+use crate::game::base::SmallBoard;
+use crate::game::ultimate::BigBoard;
+use crate::game::{
+    Board, GameState, GameVariant, LINES, Mark, fork_move, open_threats, winning_move,
+};
+
+/// Contextual advice shown to beginners while they play.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tip {
+    /// The board is empty and the center is the strongest opening.
+    TakeCenter,
+    /// The player can complete a line with their next move.
+    WinNow,
+    /// The opponent threatens to complete a line.
+    BlockLine,
+    /// The player can create two threats at once.
+    Fork,
+}
+
+impl Tip {
+    /// Returns the message displayed for this tip.
+    pub fn message(&self) -> &'static str {
+        match self {
+            Tip::TakeCenter => "Take the center!",
+            Tip::WinNow => "You can win here!",
+            Tip::BlockLine => "Block that line!",
+            Tip::Fork => "You can fork here",
+        }
+    }
+}
+
+/// How strong the suggested moves are.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HintStrength {
+    /// Forgiving hints that only look one move ahead.
+    #[default]
+    Weak,
+    /// The best move the available engines can find.
+    Perfect,
+}
+
+impl HintStrength {
+    /// Returns the other hint strength.
+    pub fn toggle(self) -> Self {
+        match self {
+            HintStrength::Weak => HintStrength::Perfect,
+            HintStrength::Perfect => HintStrength::Weak,
+        }
+    }
+
+    /// Returns the label displayed for this strength.
+    pub fn label(&self) -> &'static str {
+        match self {
+            HintStrength::Weak => "Weak",
+            HintStrength::Perfect => "Perfect",
+        }
+    }
+}
+
+/// Engine used to compute a hint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HintSource {
+    /// The rule-based [`SimpleAi`].
+    Simple,
+    /// The exhaustive classic solver.
+    Solver,
+    /// Monte Carlo tree search.
+    Mcts,
+}
+
+/// Picks the engine used for hints of the given strength.
+///
+/// Solving Ultimate positions is not feasible, so perfect hints on an
+/// Ultimate board fall back to Monte Carlo tree search.
+pub fn hint_source(strength: HintStrength, variant: GameVariant) -> HintSource {
+    match (strength, variant) {
+        (HintStrength::Weak, _) => HintSource::Simple,
+        (HintStrength::Perfect, GameVariant::Classic) => HintSource::Solver,
+        (HintStrength::Perfect, GameVariant::Ultimate) => HintSource::Mcts,
+    }
+}
+
+/// Suggests a move for `mark` on a classic board.
+///
+/// # Panics
+/// Panics if there are no legal moves on the board.
+pub fn classic_hint(board: &SmallBoard, mark: Mark, strength: HintStrength) -> Move {
+    match hint_source(strength, GameVariant::Classic) {
+        HintSource::Solver => perfect_move(board, mark),
+        HintSource::Mcts => mcts_hint(board, mark),
+        HintSource::Simple => SimpleAi::new(mark).choose_move(board),
+    }
+}
+
+/// Suggests a move for `mark` on an Ultimate board.
+///
+/// # Panics
+/// Panics if there are no legal moves on the board.
+pub fn ultimate_hint(board: &BigBoard, mark: Mark, strength: HintStrength) -> Move {
+    match hint_source(strength, GameVariant::Ultimate) {
+        HintSource::Mcts => mcts_hint(board, mark),
+        HintSource::Simple | HintSource::Solver => SimpleAi::new(mark).choose_move(board),
+    }
+}
+
+/// Checks a classic move for a mistake.
+///
+/// A move is a mistake only if its outcome under perfect play is strictly
+/// worse than the best available one, e.g. a draw when a win was possible.
+/// Slower wins are not considered mistakes.
+///
+/// # Returns
+/// The optimal move that was missed, or None if `mv` was not a mistake.
+pub fn missed_move(board: &SmallBoard, mark: Mark, mv: Move) -> Option<Move> {
+    let best = perfect_move(board, mark);
+    let outcome = |mv: &Move| move_value(board, mark, mv).signum();
+    (outcome(&mv) < outcome(&best)).then_some(best)
+}
+
+/// Runs a fresh search rooted at `board` with `mark` to move.
+fn mcts_hint<T: Game + Clone + PartialEq>(board: &T, mark: Mark) -> Move {
+    MCTSAi::new(board.clone(), mark).choose_move(board)
+}
+
+/// Selects the most relevant tip for `mark` on the given board.
+///
+/// Tips are checked in priority order: winning, blocking, forking, and
+/// finally taking the center of an empty board. The selection only depends
+/// on the board, so the same position always produces the same tip.
+///
+/// # Arguments
+/// * `board` - A reference to any type implementing the Board trait
+/// * `mark` - The mark of the player receiving the tip
+///
+/// # Returns
+/// The selected tip, or None if no pattern applies.
+pub fn select_tip(board: &impl Board, mark: Mark) -> Option<Tip> {
+    if winning_move(board, mark).is_some() {
+        return Some(Tip::WinNow);
+    }
+    if open_threats(board, mark.switch()) > 0 {
+        return Some(Tip::BlockLine);
+    }
+    if fork_move(board, mark).is_some() {
+        return Some(Tip::Fork);
+    }
+    if is_empty(board) {
+        return Some(Tip::TakeCenter);
+    }
+    None
+}
+
+/// Weight of a line still open to one mark, by the number of marks it already holds.
+const LINE_WEIGHTS: [f32; 3] = [0.0, 0.1, 0.3];
+
+/// Estimates who is ahead from X's point of view.
+///
+/// A finished game scores its result. Otherwise every line that only one mark
+/// can still complete counts for that mark, two in a row much more than a
+/// single mark, and the sum is kept short of a certain result.
+///
+/// # Returns
+/// A value between -1.0, a win for O, and 1.0, a win for X.
+pub fn evaluate<T: Board + Game>(board: &T) -> f32 {
+    match board.get_state() {
+        GameState::Won(Mark::X) => return 1.0,
+        GameState::Won(Mark::O) => return -1.0,
+        GameState::Draw => return 0.0,
+        GameState::Playing => {}
+    }
+    let line_value = |line: &[(usize, usize); 3], mark: Mark| {
+        let open = line
+            .iter()
+            .all(|&(row, col)| board.get(row, col) == Some(mark) || board.is_playable(row, col));
+        let marks = line
+            .iter()
+            .filter(|&&(row, col)| board.get(row, col) == Some(mark))
+            .count();
+        if open {
+            LINE_WEIGHTS[marks.min(2)]
+        } else {
+            0.0
+        }
+    };
+    let score: f32 = LINES
+        .iter()
+        .map(|line| line_value(line, Mark::X) - line_value(line, Mark::O))
+        .sum();
+    score.clamp(-0.9, 0.9)
+}
+
+fn is_empty(board: &impl Board) -> bool {
+    (0..3).all(|row| (0..3).all(|col| board.get(row, col).is_none() && board.is_playable(row, col)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::base::SmallBoard;
+
+    #[test]
+    fn test_evaluate_favors_an_open_two_in_a_row() {
+        assert_eq!(evaluate(&SmallBoard::new()), 0.0);
+
+        let open = SmallBoard::from_str_grid("XX.|...|..O").unwrap();
+        let blocked = SmallBoard::from_str_grid("XXO|...|...").unwrap();
+        assert!(evaluate(&open) > 0.0);
+        assert!(evaluate(&open) > evaluate(&blocked));
+
+        let won = SmallBoard::from_str_grid("OO.|XXX|...").unwrap();
+        assert_eq!(evaluate(&won), 1.0);
+    }
+
+    #[test]
+    fn test_empty_board_suggests_center() {
+        let board = SmallBoard::new();
+        assert_eq!(select_tip(&board, Mark::X), Some(Tip::TakeCenter));
+    }
+
+    #[test]
+    fn test_opponent_threat_suggests_block() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+        assert_eq!(select_tip(&board, Mark::O), Some(Tip::BlockLine));
+    }
+
+    #[test]
+    fn test_win_takes_priority_over_block() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 0, Some(Mark::O));
+        board.set(1, 1, Some(Mark::O));
+        assert_eq!(select_tip(&board, Mark::X), Some(Tip::WinNow));
+        assert_eq!(select_tip(&board, Mark::O), Some(Tip::WinNow));
+    }
+
+    #[test]
+    fn test_fork_available() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(2, 2, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+        assert_eq!(select_tip(&board, Mark::X), Some(Tip::Fork));
+    }
+
+    #[test]
+    fn test_hint_source_matches_strength_per_variant() {
+        assert_eq!(
+            hint_source(HintStrength::Weak, GameVariant::Classic),
+            HintSource::Simple
+        );
+        assert_eq!(
+            hint_source(HintStrength::Weak, GameVariant::Ultimate),
+            HintSource::Simple
+        );
+        assert_eq!(
+            hint_source(HintStrength::Perfect, GameVariant::Classic),
+            HintSource::Solver
+        );
+        assert_eq!(
+            hint_source(HintStrength::Perfect, GameVariant::Ultimate),
+            HintSource::Mcts
+        );
+    }
+
+    #[test]
+    fn test_optimal_move_is_not_a_mistake() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        assert_eq!(missed_move(&board, Mark::O, Move::Base(1, 1)), None);
+        assert_eq!(
+            missed_move(&board, Mark::O, Move::Base(0, 1)),
+            Some(Move::Base(1, 1))
+        );
+    }
+
+    #[test]
+    fn test_slower_win_is_not_a_mistake() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 0, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+        board.set(2, 2, Some(Mark::O));
+        assert_eq!(missed_move(&board, Mark::X, Move::Base(2, 1)), None);
+    }
+
+    #[test]
+    fn test_quiet_position_has_no_tip() {
+        let mut board = SmallBoard::new();
+        board.set(1, 1, Some(Mark::X));
+        assert_eq!(select_tip(&board, Mark::O), None);
+    }
+}
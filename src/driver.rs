@@ -0,0 +1,215 @@
+use crate::ai::Move;
+use crate::app::Action;
+use crate::game::base::SmallBoard;
+use crate::game::{Board, GameState};
+use crate::scenes::{GameMode, GamePlayTTT, GamePlayUTT};
+use crate::utils::{
+    Position, move_selection_down_playable, move_selection_left_playable,
+    move_selection_right_playable, move_selection_up_playable, reset_position,
+};
+use std::collections::VecDeque;
+
+/// Runs a game without the terminal by feeding it the actions of the keys.
+///
+/// Actions go through the same selection and move logic as the terminal UI,
+/// so an Ultimate move outside the active board can never be played.
+pub enum GameDriver {
+    /// A classic game.
+    Classic(GamePlayTTT),
+    /// An Ultimate game.
+    Ultimate(GamePlayUTT),
+}
+
+impl GameDriver {
+    /// Starts a classic game with the specified mode.
+    pub fn classic(mode: GameMode) -> Self {
+        GameDriver::Classic(GamePlayTTT::new(mode))
+    }
+
+    /// Starts an Ultimate game with the specified mode.
+    pub fn ultimate(mode: GameMode) -> Self {
+        GameDriver::Ultimate(GamePlayUTT::new(mode))
+    }
+
+    /// Applies one action to the game, as if its key was pressed.
+    ///
+    /// # Returns
+    /// True if the action played a move.
+    pub fn apply_action(&mut self, action: Action) -> bool {
+        match self {
+            GameDriver::Classic(game) => match action {
+                Action::Left => game.input_left(),
+                Action::Right => game.input_right(),
+                Action::Up => game.input_up(),
+                Action::Down => game.input_down(),
+                Action::Confirm => return game.play_move(),
+                Action::Back => {}
+            },
+            GameDriver::Ultimate(game) => match action {
+                Action::Left => game.input_left(),
+                Action::Right => game.input_right(),
+                Action::Up => game.input_up(),
+                Action::Down => game.input_down(),
+                Action::Confirm => return game.input_enter(),
+                Action::Back => game.input_esc(),
+            },
+        }
+        false
+    }
+
+    /// Applies each action in turn.
+    ///
+    /// # Returns
+    /// The number of moves played by the actions.
+    pub fn apply_actions(&mut self, actions: impl IntoIterator<Item = Action>) -> usize {
+        actions
+            .into_iter()
+            .filter(|action| self.apply_action(*action))
+            .count()
+    }
+
+    /// Returns the state of the game.
+    pub fn state(&self) -> GameState {
+        match self {
+            GameDriver::Classic(game) => game.board.state,
+            GameDriver::Ultimate(game) => game.big_board.state,
+        }
+    }
+
+    /// Returns the board as text, one row per line with `.` for empty cells.
+    ///
+    /// Ultimate rows separate small boards with `|` and board rows with `---+---+---`.
+    pub fn snapshot(&self) -> String {
+        match self {
+            GameDriver::Classic(game) => board_rows(&game.board).join("\n"),
+            GameDriver::Ultimate(game) => {
+                let mut lines = Vec::new();
+                for board_row in 0..3 {
+                    if board_row > 0 {
+                        lines.push("---+---+---".to_string());
+                    }
+                    let rows: Vec<[String; 3]> = (0..3)
+                        .map(|board_col| board_rows(game.big_board.get_board(board_row, board_col)))
+                        .collect();
+                    for cell_row in 0..3 {
+                        let line: Vec<&str> =
+                            rows.iter().map(|row| row[cell_row].as_str()).collect();
+                        lines.push(line.join("|"));
+                    }
+                }
+                lines.join("\n")
+            }
+        }
+    }
+
+    /// Finds the actions playing `mv` from the current selection.
+    ///
+    /// # Returns
+    /// The actions, ending with the confirming one, or None if the move cannot
+    /// be reached, for instance outside the active Ultimate board or the grid.
+    pub fn actions_for(&self, mv: Move) -> Option<Vec<Action>> {
+        match (self, mv) {
+            (GameDriver::Classic(game), Move::Base(row, col)) => {
+                let mut actions =
+                    selection_path(&game.board, game.selected, Position { row, col })?;
+                actions.push(Action::Confirm);
+                Some(actions)
+            }
+            (
+                GameDriver::Ultimate(game),
+                Move::Ultimate(board_row, board_col, cell_row, cell_col),
+            ) => {
+                let target_board = Position {
+                    row: board_row,
+                    col: board_col,
+                };
+                let target_cell = Position {
+                    row: cell_row,
+                    col: cell_col,
+                };
+                // The coordinates come from the caller, so check them all first.
+                game.big_board
+                    .try_get(board_row, board_col, cell_row, cell_col)
+                    .ok()?;
+                let small_board = game.big_board.get_board(board_row, board_col);
+                let mut actions = Vec::new();
+                let from_cell = match game.selected_cell {
+                    Some(cell) if game.selected_board == target_board => cell,
+                    Some(_) if game.big_board.active_board.is_some() => return None,
+                    selected_cell => {
+                        if selected_cell.is_some() {
+                            actions.push(Action::Back);
+                        }
+                        actions.extend(selection_path(
+                            &game.big_board,
+                            game.selected_board,
+                            target_board,
+                        )?);
+                        actions.push(Action::Confirm);
+                        let mut cell = Position { row: 0, col: 0 };
+                        reset_position(small_board, &mut cell);
+                        cell
+                    }
+                };
+                actions.extend(selection_path(small_board, from_cell, target_cell)?);
+                actions.push(Action::Confirm);
+                Some(actions)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the rows of a classic board with `.` for empty cells.
+fn board_rows(board: &SmallBoard) -> [String; 3] {
+    [0, 1, 2].map(|row| {
+        (0..3)
+            .map(|col| {
+                board
+                    .get(row, col)
+                    .map_or('.', |mark| mark.to_string().remove(0))
+            })
+            .collect()
+    })
+}
+
+/// Moves a selection to another playable position of a board.
+type SelectionStep<B> = fn(&B, &mut Position);
+
+/// Finds the shortest sequence of arrow actions moving the selection from `from` to `to`.
+fn selection_path<B: Board>(board: &B, from: Position, to: Position) -> Option<Vec<Action>> {
+    let steps: [(Action, SelectionStep<B>); 4] = [
+        (Action::Left, move_selection_left_playable),
+        (Action::Right, move_selection_right_playable),
+        (Action::Up, move_selection_up_playable),
+        (Action::Down, move_selection_down_playable),
+    ];
+    if !board.is_playable(to.row, to.col) {
+        return None;
+    }
+    // Selection and action that first reached each cell.
+    let mut previous: [[Option<(Position, Action)>; 3]; 3] = [[None; 3]; 3];
+    let mut queue = VecDeque::from([from]);
+    while let Some(position) = queue.pop_front() {
+        if position == to {
+            let mut actions = Vec::new();
+            let mut current = position;
+            while current != from {
+                let (before, action) = previous[current.row][current.col]?;
+                actions.push(action);
+                current = before;
+            }
+            actions.reverse();
+            return Some(actions);
+        }
+        for (action, step) in steps {
+            let mut next = position;
+            step(board, &mut next);
+            if next != from && previous[next.row][next.col].is_none() {
+                previous[next.row][next.col] = Some((position, action));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
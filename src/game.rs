@@ -1,8 +1,13 @@
 /// Classic 3x3 tic-tac-toe board.
 pub mod base;
+/// Square boards of any size won by n marks in a row, as in Gomoku.
+pub mod generic;
+/// Single-line notation for recording and replaying games.
+pub mod notation;
 /// Ultimate tic-tac-toe board composed of nine classic boards.
 pub mod ultimate;
 
+use crate::utils::Position;
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
@@ -17,8 +22,37 @@ pub enum GameVariant {
     Ultimate,
 }
 
+/// Decides who a completed line counts for on a classic board.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleSet {
+    /// Completing a line wins the game.
+    #[default]
+    Standard,
+    /// Misère rules: completing a line loses the game.
+    Misere,
+}
+
+impl RuleSet {
+    /// Returns the name of the rules as shown in menus.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuleSet::Standard => "Standard",
+            RuleSet::Misere => "Misère",
+        }
+    }
+
+    /// Returns the other rule set.
+    pub fn switch(&self) -> RuleSet {
+        match self {
+            RuleSet::Standard => RuleSet::Misere,
+            RuleSet::Misere => RuleSet::Standard,
+        }
+    }
+}
+
 /// Represents a player's mark (X or O) on the tic-tac-toe board.
-#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Mark {
     /// The X mark.
     X,
@@ -46,7 +80,7 @@ impl fmt::Display for Mark {
 }
 
 /// Represents the current state of a tic-tac-toe game.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum GameState {
     /// The game is still in progress.
     Playing,
@@ -56,6 +90,56 @@ pub enum GameState {
     Draw,
 }
 
+/// Reason a move cannot be played on a board.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MoveError {
+    /// The board is already won or drawn.
+    GameOver,
+    /// The cell already holds a mark.
+    Occupied,
+    /// The move is outside the Ultimate board the previous move sent the player to.
+    WrongBoard,
+    /// A row or column index is larger than 2.
+    OutOfBounds,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            MoveError::GameOver => "tried making a move on a completed board",
+            MoveError::Occupied => "tried making a move on an occupied position",
+            MoveError::WrongBoard => {
+                "tried making a move on a board different than the active board"
+            }
+            MoveError::OutOfBounds => "tried making a move out of bounds",
+        };
+        formatter.write_str(reason)
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Error returned when a board or cell position has a row or column larger than 2.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutOfBounds {
+    /// Row index that was asked for.
+    pub row: usize,
+    /// Column index that was asked for.
+    pub col: usize,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "position ({}, {}) is out of bounds",
+            self.row, self.col
+        )
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
 /// Trait for types that can act as a tic-tac-toe board.
 ///
 /// Implementors must provide a method to get the mark at a specific position.
@@ -79,82 +163,96 @@ pub trait Board {
     /// # Returns
     /// True if the position is playable, else False.
     fn is_playable(&self, row: usize, col: usize) -> bool;
+
+    /// Returns the number of rows and columns, 3 by 3 unless the board is larger.
+    fn dims(&self) -> (usize, usize) {
+        (3, 3)
+    }
 }
 
-/// Checks if the specified row has three matching marks.
+/// Returns the mark filling every cell of `cells`.
+///
+/// # Returns
+/// The mark, or None if the cells hold different marks or any is empty.
+fn check_cells(board: &impl Board, cells: impl IntoIterator<Item = Position>) -> Option<Mark> {
+    let mut cells = cells.into_iter();
+    let first = cells.next()?;
+    let mark_0 = board.get(first.row, first.col)?;
+    for cell in cells {
+        if board.get(cell.row, cell.col)? != mark_0 {
+            return None;
+        }
+    }
+    Some(mark_0)
+}
+
+/// Checks if the specified row is filled with one mark.
 ///
 /// # Arguments
 /// * `board` - A reference to any type implementing the Board trait
-/// * `row` - Row index (0-2)
+/// * `row` - Row index, below the number of rows of the board
 ///
 /// # Returns
-/// The winning mark if all three cells in the row match,
+/// The winning mark if all cells in the row match,
 /// or None if they don't match or any cell is empty.
 pub fn check_row(board: &impl Board, row: usize) -> Option<Mark> {
-    let mark_0 = board.get(row, 0)?;
-    for i in 1..3 {
-        let mark_i = board.get(row, i)?;
-        if mark_i != mark_0 {
-            return None;
-        }
-    }
-    Some(mark_0)
+    let (_, cols) = board.dims();
+    check_cells(board, (0..cols).map(|col| Position { row, col }))
 }
 
-/// Checks if the specified column has three matching marks.
+/// Checks if the specified column is filled with one mark.
 ///
 /// # Arguments
 /// * `board` - A reference to any type implementing the Board trait
-/// * `col` - Column index (0-2)
+/// * `col` - Column index, below the number of columns of the board
 ///
 /// # Returns
-/// The winning mark if all three cells in the column match,
+/// The winning mark if all cells in the column match,
 /// or None if they don't match or any cell is empty.
 pub fn check_col(board: &impl Board, col: usize) -> Option<Mark> {
-    let mark_0 = board.get(0, col)?;
-    for i in 1..3 {
-        let mark_i = board.get(i, col)?;
-        if mark_i != mark_0 {
-            return None;
-        }
-    }
-    Some(mark_0)
+    let (rows, _) = board.dims();
+    check_cells(board, (0..rows).map(|row| Position { row, col }))
+}
+
+/// Returns the cells of the top-left to bottom-right diagonal of a square
+/// board with `size` rows and columns, from the top.
+pub fn diag_dexter_cells(size: usize) -> impl Iterator<Item = Position> {
+    (0..size).map(|i| Position { row: i, col: i })
+}
+
+/// Returns the cells of the top-right to bottom-left diagonal of a square
+/// board with `size` rows and columns, from the top.
+///
+/// For a 3x3 board these are (0, 2), (1, 1) and (2, 0).
+pub fn diag_sinister_cells(size: usize) -> impl Iterator<Item = Position> {
+    (0..size).map(move |i| Position {
+        row: i,
+        col: size - 1 - i,
+    })
 }
 
-/// Checks the top-left to bottom-right diagonal for three matching marks.
+/// Checks the top-left to bottom-right diagonal for matching marks.
 ///
 /// # Arguments
 /// * `board` - A reference to any type implementing the Board trait
 ///
 /// # Returns
-/// The winning mark if all three cells match, or None otherwise.
+/// The winning mark if all cells of the diagonal match, or None otherwise.
 pub fn check_diag_dexter(board: &impl Board) -> Option<Mark> {
-    let mark_0 = board.get(0, 0)?;
-    for i in 1..3 {
-        let mark_i = board.get(i, i)?;
-        if mark_i != mark_0 {
-            return None;
-        }
-    }
-    Some(mark_0)
+    let (rows, _) = board.dims();
+    check_cells(board, diag_dexter_cells(rows))
 }
 
-/// Checks the top-right to bottom-left diagonal for three matching marks.
+/// Checks the top-right to bottom-left diagonal for matching marks.
 ///
 /// # Arguments
 /// * `board` - A reference to any type implementing the Board trait
 ///
 /// # Returns
-/// The winning mark if all three cells match, or None otherwise.
+/// The winning mark if all cells of the diagonal match, or None otherwise.
 pub fn check_diag_sinister(board: &impl Board) -> Option<Mark> {
-    let mark_0 = board.get(0, 2)?;
-    for i in 1..3 {
-        let mark_i = board.get(i, 2 - i)?;
-        if mark_i != mark_0 {
-            return None;
-        }
-    }
-    Some(mark_0)
+    let (rows, _) = board.dims();
+    check_cells(board, diag_sinister_cells(rows))
 }
 
 /// Checks all possible winning conditions (rows, columns, and diagonals).
@@ -172,16 +270,27 @@ pub fn check_win(board: &impl Board) -> Option<Mark> {
     if let Some(mark) = check_diag_sinister(board) {
         return Some(mark);
     }
-    for i in 0..3 {
-        if let Some(mark) = check_row(board, i) {
-            return Some(mark);
-        }
-        if let Some(mark) = check_col(board, i) {
-            return Some(mark);
-        }
-    }
+    let (rows, cols) = board.dims();
+    (0..rows)
+        .find_map(|row| check_row(board, row))
+        .or_else(|| (0..cols).find_map(|col| check_col(board, col)))
+}
 
-    None
+/// Checks for a completed line and credits the game according to `rules`.
+///
+/// # Arguments
+/// * `board` - A reference to any type implementing the Board trait
+/// * `rules` - Whether the line wins the game for its mark or loses it
+///
+/// # Returns
+/// The winning mark, which under [`RuleSet::Misere`] is the opponent of the
+/// mark completing the line, or None if no line is complete.
+pub fn check_win_with_rules(board: &impl Board, rules: RuleSet) -> Option<Mark> {
+    let mark = check_win(board)?;
+    Some(match rules {
+        RuleSet::Standard => mark,
+        RuleSet::Misere => mark.switch(),
+    })
 }
 
 /// Checks if all cells on the board are filled.
@@ -199,6 +308,141 @@ pub fn check_complete(board: &impl Board) -> bool {
     true
 }
 
+/// Every winning line of a 3x3 board, as three `(row, col)` positions.
+pub const LINES: [[(usize, usize); 3]; 8] = [
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(0, 2), (1, 1), (2, 0)],
+];
+
+/// Returns the index in [`LINES`] of the first line completed by one mark.
+pub(crate) fn winning_line_index(board: &impl Board) -> Option<usize> {
+    LINES.iter().position(|line| {
+        let [first, second, third] = line.map(|(row, col)| board.get(row, col));
+        first.is_some() && first == second && first == third
+    })
+}
+
+/// Returns the positions of the line at `index` in [`LINES`].
+pub(crate) fn line_positions(index: usize) -> [Position; 3] {
+    LINES[index].map(|(row, col)| Position { row, col })
+}
+
+/// Finds a playable position that immediately completes a line for `mark`.
+///
+/// Positions are scanned in row-major order, so the result is deterministic.
+///
+/// # Arguments
+/// * `board` - A reference to any type implementing the Board trait
+/// * `mark` - The mark looking for a winning move
+///
+/// # Returns
+/// The first winning position, or None if `mark` cannot win in one move.
+pub fn winning_move(board: &impl Board, mark: Mark) -> Option<(usize, usize)> {
+    for row in 0..3 {
+        for col in 0..3 {
+            if board.is_playable(row, col) && count_lines_through(board, row, col, mark, 2) > 0 {
+                return Some((row, col));
+            }
+        }
+    }
+    None
+}
+
+/// Counts the lines in which `mark` holds two cells and the third is playable.
+///
+/// # Arguments
+/// * `board` - A reference to any type implementing the Board trait
+/// * `mark` - The mark whose threats are counted
+///
+/// # Returns
+/// The number of lines `mark` could complete with its next move.
+pub fn open_threats(board: &impl Board, mark: Mark) -> usize {
+    LINES
+        .iter()
+        .filter(|line| {
+            let owned = line
+                .iter()
+                .filter(|&&(row, col)| board.get(row, col) == Some(mark))
+                .count();
+            let playable = line
+                .iter()
+                .filter(|&&(row, col)| board.is_playable(row, col))
+                .count();
+            owned == 2 && playable == 1
+        })
+        .count()
+}
+
+/// Finds a playable position that creates at least two open threats for `mark`.
+///
+/// Positions are scanned in row-major order, so the result is deterministic.
+///
+/// # Returns
+/// The first forking position, or None if no fork is available.
+pub fn fork_move(board: &impl Board, mark: Mark) -> Option<(usize, usize)> {
+    for row in 0..3 {
+        for col in 0..3 {
+            if board.is_playable(row, col) && count_lines_through(board, row, col, mark, 1) >= 2 {
+                return Some((row, col));
+            }
+        }
+    }
+    None
+}
+
+/// Infers the mark to move from the cells played so far, assuming the marks alternate.
+///
+/// # Returns
+/// The mark with fewer cells, or None if both marks hold the same number.
+pub(crate) fn next_player(cells: impl IntoIterator<Item = Option<Mark>>) -> Option<Mark> {
+    let (mut x_count, mut o_count) = (0, 0);
+    for cell in cells {
+        match cell {
+            Some(Mark::X) => x_count += 1,
+            Some(Mark::O) => o_count += 1,
+            None => {}
+        }
+    }
+    match x_count.cmp(&o_count) {
+        std::cmp::Ordering::Greater => Some(Mark::O),
+        std::cmp::Ordering::Less => Some(Mark::X),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Counts lines through `(row, col)` whose other two cells hold exactly `owned`
+/// marks of `mark` and are otherwise playable.
+fn count_lines_through(
+    board: &impl Board,
+    row: usize,
+    col: usize,
+    mark: Mark,
+    owned: usize,
+) -> usize {
+    LINES
+        .iter()
+        .filter(|line| line.contains(&(row, col)))
+        .filter(|line| {
+            let others = line.iter().filter(|&&position| position != (row, col));
+            let mut owned_count = 0;
+            for &(other_row, other_col) in others {
+                if board.get(other_row, other_col) == Some(mark) {
+                    owned_count += 1;
+                } else if !board.is_playable(other_row, other_col) {
+                    return false;
+                }
+            }
+            owned_count == owned
+        })
+        .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::base::*;
@@ -267,6 +511,41 @@ mod tests {
         assert_eq!(check_diag_sinister(&board), Some(Mark::O));
     }
 
+    #[test]
+    fn test_anti_diagonal_win_is_not_taken_for_the_main_diagonal() {
+        let mut board = SmallBoard::new();
+        for (row, col) in [(0, 2), (1, 1), (2, 0)] {
+            board.set(row, col, Some(Mark::O));
+        }
+        assert_eq!(check_diag_sinister(&board), Some(Mark::O));
+        assert_eq!(check_diag_dexter(&board), None);
+        assert_eq!(check_win(&board), Some(Mark::O));
+
+        let cells: Vec<(usize, usize)> = diag_sinister_cells(3)
+            .map(|cell| (cell.row, cell.col))
+            .collect();
+        assert_eq!(cells, [(0, 2), (1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn test_line_checks_follow_the_board_size() {
+        let mut board = generic::GenericBoard::new(4, 4);
+        for (row, col) in [(0, 3), (1, 2), (2, 1)] {
+            board.make_move(row, col, Mark::X);
+        }
+        // Three marks only cover a 3x3 anti-diagonal.
+        assert_eq!(check_diag_sinister(&board), None);
+        assert_eq!(check_win(&board), None);
+
+        board.make_move(3, 0, Mark::X);
+        assert_eq!(check_diag_sinister(&board), Some(Mark::X));
+        assert_eq!(check_diag_dexter(&board), None);
+        let cells: Vec<(usize, usize)> = diag_sinister_cells(4)
+            .map(|cell| (cell.row, cell.col))
+            .collect();
+        assert_eq!(cells, [(0, 3), (1, 2), (2, 1), (3, 0)]);
+    }
+
     #[test]
     fn test_check_win() {
         let mut board = SmallBoard::new();
@@ -290,4 +569,42 @@ mod tests {
         board.set(2, 0, Some(Mark::O));
         assert_eq!(check_win(&board), Some(Mark::O));
     }
+
+    #[test]
+    fn test_winning_move() {
+        let mut board = SmallBoard::new();
+        assert_eq!(winning_move(&board, Mark::X), None);
+
+        board.set_row(0, [Some(Mark::X), None, Some(Mark::X)]);
+        assert_eq!(winning_move(&board, Mark::X), Some((0, 1)));
+        assert_eq!(winning_move(&board, Mark::O), None);
+
+        board.set(0, 1, Some(Mark::O));
+        assert_eq!(winning_move(&board, Mark::X), None);
+    }
+
+    #[test]
+    fn test_open_threats() {
+        let mut board = SmallBoard::new();
+        assert_eq!(open_threats(&board, Mark::X), 0);
+
+        board.set_row(0, [Some(Mark::X), Some(Mark::X), None]);
+        board.set(1, 0, Some(Mark::X));
+        assert_eq!(open_threats(&board, Mark::X), 2);
+
+        board.set(2, 0, Some(Mark::O));
+        assert_eq!(open_threats(&board, Mark::X), 1);
+    }
+
+    #[test]
+    fn test_fork_move() {
+        let mut board = SmallBoard::new();
+        assert_eq!(fork_move(&board, Mark::X), None);
+
+        board.set(0, 0, Some(Mark::X));
+        board.set(2, 2, Some(Mark::X));
+        board.set(1, 1, Some(Mark::O));
+        assert_eq!(fork_move(&board, Mark::X), Some((0, 2)));
+        assert_eq!(fork_move(&board, Mark::O), None);
+    }
 }
@@ -1,27 +1,50 @@
 use super::*;
 use crate::ai::Move::Base;
 use crate::ai::{Game, Move};
+use crate::utils::Position;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 /// A 3x3 tic-tac-toe board.
 ///
 /// The board is represented as a flat array of 9 cells, where each cell
 /// can contain either a mark (X or O) or be empty (None).
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct SmallBoard {
     cells: [Option<Mark>; 9],
     /// Current completion state of the board.
     pub state: GameState,
+    /// Index in [`LINES`] of the line that won the board, kept small so
+    /// boards stay cheap to copy during search.
+    winning_line: Option<u8>,
+    /// Whether a completed line wins or loses the board.
+    #[serde(default)]
+    rules: RuleSet,
 }
 
 impl SmallBoard {
     /// Creates a new empty board with all cells set to None.
     pub fn new() -> Self {
+        SmallBoard::with_rules(RuleSet::Standard)
+    }
+
+    /// Creates a new empty board played with the given rules.
+    ///
+    /// # Arguments
+    /// * `rules` - Whether completing a line wins or loses the board
+    pub fn with_rules(rules: RuleSet) -> Self {
         SmallBoard {
             cells: [None; 9],
             state: GameState::Playing,
+            winning_line: None,
+            rules,
         }
     }
 
+    /// Returns the rules the board is played with.
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
     /// Sets the mark at the specified position.
     ///
     /// # Arguments
@@ -30,9 +53,9 @@ impl SmallBoard {
     /// * `mark` - The mark to place (Some(Mark::X), Some(Mark::O), or None)
     ///
     /// # Panics
-    /// Panics if row or col is greater than 3.
+    /// Panics if row or col is greater than 2.
     pub fn set(&mut self, row: usize, col: usize, mark: Option<Mark>) {
-        if row > 3 || col > 3 {
+        if row >= 3 || col >= 3 {
             panic!("Tried to access board position ({row}, {col}) which is out of bounds.");
         }
         self.cells[row * 3 + col] = mark;
@@ -50,21 +73,236 @@ impl SmallBoard {
     ///
     /// # Panics
     /// * Panics if the game is already over (state is not GameState::Playing)
-    /// * Panics if the specified position is already occupied
+    /// * Panics if the specified position is out of bounds or already occupied
     pub fn make_move(&mut self, row: usize, col: usize, mark: Mark) {
+        if let Err(error) = self.try_make_move(row, col, mark) {
+            panic!("Error: {error}.");
+        }
+    }
+
+    /// Makes a move on the board, leaving it unchanged if the move is illegal.
+    ///
+    /// # Arguments
+    /// * `row` - Row index (0-2)
+    /// * `col` - Column index (0-2)
+    /// * `mark` - The mark to place (Mark::X or Mark::O)
+    ///
+    /// # Errors
+    /// Returns a [`MoveError`] if the board is finished, the position is out
+    /// of bounds or the cell is occupied.
+    pub fn try_make_move(&mut self, row: usize, col: usize, mark: Mark) -> Result<(), MoveError> {
         if self.state != GameState::Playing {
-            panic!("Error: tried making a move on a completed board.");
+            return Err(MoveError::GameOver);
+        }
+        if row >= 3 || col >= 3 {
+            return Err(MoveError::OutOfBounds);
         }
         if self.get(row, col).is_some() {
-            panic!("Error: tried making a move on an occupied position.");
+            return Err(MoveError::Occupied);
         }
         self.set(row, col, Some(mark));
+        self.update_state();
+        Ok(())
+    }
+
+    /// Recomputes the state and winning line from the cells.
+    fn update_state(&mut self) {
         if check_complete(self) {
             self.state = GameState::Draw;
         }
-        if let Some(mark) = check_win(self) {
+        self.winning_line = winning_line_index(self).map(|index| index as u8);
+        if let Some(mark) = check_win_with_rules(self, self.rules) {
             self.state = GameState::Won(mark);
+        }
+    }
+
+    /// Builds a board from a compact grid such as `"X.O|..X|O.."`.
+    ///
+    /// Cells are read in row-major order: `X` and `O` are marks, while `.`
+    /// or a space is an empty cell. Rows may be separated by `|` or newlines.
+    /// The state is recomputed from the cells, as if the marks were played.
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] for an unknown character, a grid without
+    /// exactly nine cells, or mark counts that no game could reach.
+    pub fn from_str_grid(grid: &str) -> Result<SmallBoard, ParseError> {
+        let mut cells = Vec::with_capacity(9);
+        for character in grid.chars() {
+            match character {
+                'X' => cells.push(Some(Mark::X)),
+                'O' => cells.push(Some(Mark::O)),
+                '.' | ' ' => cells.push(None),
+                '|' | '\n' | '\r' => {}
+                _ => return Err(ParseError::InvalidCharacter(character)),
+            }
+        }
+        let cells: [Option<Mark>; 9] = cells
+            .try_into()
+            .map_err(|cells: Vec<_>| ParseError::WrongCellCount(cells.len()))?;
+
+        let count = |mark| cells.iter().filter(|cell| **cell == Some(mark)).count();
+        let (x, o) = (count(Mark::X), count(Mark::O));
+        if x.abs_diff(o) > 1 {
+            return Err(ParseError::IllegalMarkCount { x, o });
+        }
+
+        let mut board = SmallBoard {
+            cells,
+            state: GameState::Playing,
+            winning_line: None,
+            rules: RuleSet::Standard,
         };
+        board.update_state();
+        Ok(board)
+    }
+
+    /// Writes the board as a compact grid that [`SmallBoard::from_str_grid`]
+    /// reads back, e.g. `"X.O|..X|O.."`.
+    pub fn to_str_grid(&self) -> String {
+        self.cells
+            .chunks(3)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Some(Mark::X) => 'X',
+                        Some(Mark::O) => 'O',
+                        None => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
+    /// Returns the cells of the line that won the board.
+    ///
+    /// # Returns
+    /// The three positions in reading order, or None unless the state is
+    /// [`GameState::Won`].
+    pub fn winning_line(&self) -> Option<[Position; 3]> {
+        self.winning_line
+            .map(|index| line_positions(usize::from(index)))
+    }
+
+    /// Returns every empty cell that can still be played, in reading order.
+    ///
+    /// # Returns
+    /// The playable positions, or an empty vector once the board is won or drawn.
+    pub fn available_moves(&self) -> Vec<Position> {
+        if self.state != GameState::Playing {
+            return Vec::new();
+        }
+        (0..9)
+            .map(|index| Position {
+                row: index / 3,
+                col: index % 3,
+            })
+            .filter(|position| self.is_playable(position.row, position.col))
+            .collect()
+    }
+
+    /// Returns the board transformed by one of its eight symmetries.
+    ///
+    /// # Arguments
+    /// * `rotations` - Number of clockwise quarter turns to apply
+    /// * `mirrored` - Whether to mirror the rotated board left to right
+    pub fn transform(&self, rotations: usize, mirrored: bool) -> SmallBoard {
+        let mut board = *self;
+        for _ in 0..rotations % 4 {
+            let previous = board.cells;
+            for row in 0..3 {
+                for col in 0..3 {
+                    board.cells[row * 3 + col] = previous[(2 - col) * 3 + row];
+                }
+            }
+        }
+        if mirrored {
+            let previous = board.cells;
+            for row in 0..3 {
+                for col in 0..3 {
+                    board.cells[row * 3 + col] = previous[row * 3 + 2 - col];
+                }
+            }
+        }
+        if board.winning_line.is_some() {
+            board.winning_line = winning_line_index(&board).map(|index| index as u8);
+        }
+        board
+    }
+
+    /// Returns the canonical representative among the board's symmetries.
+    ///
+    /// The canonical form is the lexicographically smallest symmetric board,
+    /// reading cells in row-major order with empty < X < O. Boards that are
+    /// rotations or reflections of each other share the same canonical form.
+    pub fn canonical(&self) -> SmallBoard {
+        (0..4)
+            .flat_map(|rotations| [false, true].map(|mirrored| self.transform(rotations, mirrored)))
+            .min_by_key(|board| {
+                board.cells.map(|cell| match cell {
+                    None => 0,
+                    Some(Mark::X) => 1,
+                    Some(Mark::O) => 2,
+                })
+            })
+            .unwrap()
+    }
+
+    /// Checks whether two boards are rotations or reflections of each other.
+    pub fn is_symmetric_to(&self, other: &SmallBoard) -> bool {
+        self.canonical() == other.canonical()
+    }
+
+    /// Returns the outcome of the board under perfect play, with `to_move` moving next.
+    ///
+    /// The whole game tree is searched with minimax, so the result is exact:
+    /// `Draw` for a position neither player can force a win from.
+    pub fn forced_result(&self, to_move: Mark) -> GameState {
+        crate::ai::solver::forced_result(self, to_move)
+    }
+
+    /// Counts the marks of the board and reports its outcome.
+    ///
+    /// Works on boards still being played as well as on won and drawn ones.
+    pub fn summary(&self) -> BoardSummary {
+        let count = |cell| self.cells.iter().filter(|c| **c == cell).count();
+        let x_marks = count(Some(Mark::X));
+        let o_marks = count(Some(Mark::O));
+        BoardSummary {
+            x_marks,
+            o_marks,
+            empty_cells: count(None),
+            line: self
+                .winning_line()
+                .and_then(|[first, ..]| self.get(first.row, first.col)),
+            moves: x_marks + o_marks,
+            state: self.state,
+        }
+    }
+}
+
+/// Mark counts and outcome of a [`SmallBoard`], as returned by [`SmallBoard::summary`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoardSummary {
+    /// Number of X marks on the board.
+    pub x_marks: usize,
+    /// Number of O marks on the board.
+    pub o_marks: usize,
+    /// Number of empty cells.
+    pub empty_cells: usize,
+    /// Mark filling a complete line, or None if no line is complete.
+    ///
+    /// Under [`RuleSet::Misere`] this is the mark that lost the board.
+    pub line: Option<Mark>,
+    /// Number of moves played, one per mark on the board.
+    pub moves: usize,
+    /// Completion state of the board.
+    pub state: GameState,
+}
+
+impl Default for SmallBoard {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -76,9 +314,9 @@ impl Board for SmallBoard {
     /// * `col` - Column index (0-2)
     ///
     /// # Panics
-    /// Panics if row or col is greater than 3.
+    /// Panics if row or col is greater than 2.
     fn get(&self, row: usize, col: usize) -> Option<Mark> {
-        if row > 3 || col > 3 {
+        if row >= 3 || col >= 3 {
             panic!("Tried to access board position ({row}, {col}) which is out of bounds.");
         }
         self.cells[row * 3 + col]
@@ -93,7 +331,7 @@ impl Board for SmallBoard {
     /// # Returns
     /// True if the cell is empty, else False.
     fn is_playable(&self, row: usize, col: usize) -> bool {
-        self.get(row, col) == None
+        self.get(row, col).is_none()
     }
 }
 
@@ -126,18 +364,10 @@ impl Game for SmallBoard {
     }
 
     fn get_possible_moves(&self) -> Vec<Move> {
-        let mut possible_moves = Vec::new();
-        if self.state != GameState::Playing {
-            return possible_moves;
-        }
-        for row in 0..3 {
-            for col in 0..3 {
-                if self.is_playable(row, col) {
-                    possible_moves.push(Base(row, col));
-                }
-            }
-        }
-        possible_moves
+        self.available_moves()
+            .into_iter()
+            .map(|position| Base(position.row, position.col))
+            .collect()
     }
 
     fn score(&self, mark: Mark) -> i8 {
@@ -150,8 +380,49 @@ impl Game for SmallBoard {
     fn get_state(&self) -> GameState {
         self.state
     }
+
+    fn current_player(&self) -> Option<Mark> {
+        next_player(self.cells)
+    }
 }
 
+/// Error returned when a grid cannot be read by [`SmallBoard::from_str_grid`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The grid contains a character that is neither a cell nor a separator.
+    InvalidCharacter(char),
+    /// The grid does not contain exactly nine cells.
+    WrongCellCount(usize),
+    /// The number of X and O marks differs by more than one.
+    IllegalMarkCount {
+        /// Number of X marks.
+        x: usize,
+        /// Number of O marks.
+        o: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidCharacter(character) => {
+                write!(
+                    formatter,
+                    "unexpected character '{character}' in board grid"
+                )
+            }
+            ParseError::WrongCellCount(count) => {
+                write!(formatter, "board grid has {count} cells instead of 9")
+            }
+            ParseError::IllegalMarkCount { x, o } => {
+                write!(formatter, "board grid has {x} X and {o} O marks")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,31 +430,45 @@ mod tests {
     impl SmallBoard {
         /// Test helper: Sets an entire row with the provided marks.
         pub fn set_row(&mut self, row: usize, marks: [Option<Mark>; 3]) {
-            for col in 0..3 {
-                self.set(row, col, marks[col]);
+            for (col, mark) in marks.into_iter().enumerate() {
+                self.set(row, col, mark);
             }
         }
 
         /// Test helper: Sets an entire column with the provided marks.
         pub fn set_col(&mut self, col: usize, marks: [Option<Mark>; 3]) {
-            for row in 0..3 {
-                self.set(row, col, marks[row]);
+            for (row, mark) in marks.into_iter().enumerate() {
+                self.set(row, col, mark);
             }
         }
     }
 
+    #[test]
+    fn test_boards_compare_by_cells_and_state() {
+        let mut played = SmallBoard::new();
+        played.make_move(1, 1, Mark::X);
+        played.make_move(0, 0, Mark::O);
+        let parsed = SmallBoard::from_str_grid("O..|.X.|...").unwrap();
+        assert_eq!(played, parsed);
+
+        let mut other = SmallBoard::new();
+        other.make_move(1, 1, Mark::X);
+        other.make_move(0, 1, Mark::O);
+        assert_ne!(played, other);
+    }
+
     #[test]
     fn test_check_complete() {
         let mut board = SmallBoard::new();
-        assert_eq!(check_complete(&board), false);
+        assert!(!check_complete(&board));
 
         board.set_row(0, [Some(Mark::X), Some(Mark::O), Some(Mark::O)]);
         board.set_row(1, [Some(Mark::X), None, Some(Mark::X)]);
         board.set_row(2, [Some(Mark::O), Some(Mark::O), Some(Mark::X)]);
-        assert_eq!(check_complete(&board), false);
+        assert!(!check_complete(&board));
 
         board.set(1, 1, Some(Mark::X));
-        assert_eq!(check_complete(&board), true);
+        assert!(check_complete(&board));
     }
 
     #[test]
@@ -202,6 +487,22 @@ mod tests {
         assert_eq!(board.state, GameState::Won(Mark::X));
     }
 
+    #[test]
+    fn test_misere_line_credits_the_opponent() {
+        let mut board = SmallBoard::with_rules(RuleSet::Misere);
+        board.make_move(0, 0, Mark::X);
+        board.make_move(1, 0, Mark::O);
+        board.make_move(0, 1, Mark::X);
+        board.make_move(1, 1, Mark::O);
+        assert_eq!(board.state, GameState::Playing);
+        board.make_move(0, 2, Mark::X);
+
+        assert_eq!(board.rules(), RuleSet::Misere);
+        assert_eq!(board.state, GameState::Won(Mark::O));
+        assert!(board.winning_line().is_some());
+        assert_eq!(board.score(Mark::X), -1);
+    }
+
     #[test]
     fn test_make_move_draw() {
         let mut board = SmallBoard::new();
@@ -220,6 +521,232 @@ mod tests {
         assert_eq!(board.state, GameState::Draw);
     }
 
+    #[test]
+    fn test_forced_result_under_perfect_play() {
+        assert_eq!(SmallBoard::new().forced_result(Mark::X), GameState::Draw);
+
+        let win_in_one = SmallBoard::from_str_grid("XX.|OO.|...").unwrap();
+        assert_eq!(win_in_one.forced_result(Mark::X), GameState::Won(Mark::X));
+        assert_eq!(win_in_one.forced_result(Mark::O), GameState::Won(Mark::O));
+
+        // X has no immediate win, but a1 threatens both a3 and c3.
+        let win_in_three = SmallBoard::from_str_grid("...|XXO|.O.").unwrap();
+        for mv in win_in_three.get_possible_moves() {
+            let mut next = win_in_three;
+            next.play(&mv, Mark::X);
+            assert_eq!(next.state, GameState::Playing);
+        }
+        assert_eq!(win_in_three.forced_result(Mark::X), GameState::Won(Mark::X));
+
+        let finished = SmallBoard::from_str_grid("XXX|OO.|...").unwrap();
+        assert_eq!(finished.forced_result(Mark::O), GameState::Won(Mark::X));
+    }
+
+    #[test]
+    fn test_summary_of_empty_and_mid_game_boards() {
+        let summary = SmallBoard::new().summary();
+        assert_eq!(
+            summary,
+            BoardSummary {
+                x_marks: 0,
+                o_marks: 0,
+                empty_cells: 9,
+                line: None,
+                moves: 0,
+                state: GameState::Playing,
+            }
+        );
+
+        let summary = SmallBoard::from_str_grid("XO.|.X.|..O").unwrap().summary();
+        assert_eq!((summary.x_marks, summary.o_marks), (2, 2));
+        assert_eq!(summary.empty_cells, 5);
+        assert_eq!(summary.line, None);
+        assert_eq!(summary.moves, 4);
+        assert_eq!(summary.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_summary_of_finished_boards() {
+        let won = SmallBoard::from_str_grid("XXX|OO.|...").unwrap().summary();
+        assert_eq!((won.x_marks, won.o_marks, won.empty_cells), (3, 2, 4));
+        assert_eq!(won.line, Some(Mark::X));
+        assert_eq!(won.moves, 5);
+        assert_eq!(won.state, GameState::Won(Mark::X));
+
+        let mut misere = SmallBoard::with_rules(RuleSet::Misere);
+        for (index, (row, col)) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]
+            .into_iter()
+            .enumerate()
+        {
+            let mark = if index % 2 == 0 { Mark::X } else { Mark::O };
+            misere.make_move(row, col, mark);
+        }
+        let lost = misere.summary();
+        assert_eq!(lost.line, Some(Mark::X));
+        assert_eq!(lost.state, GameState::Won(Mark::O));
+
+        let drawn = SmallBoard::from_str_grid("XOX|XOO|OXX").unwrap().summary();
+        assert_eq!((drawn.x_marks, drawn.o_marks, drawn.empty_cells), (5, 4, 0));
+        assert_eq!(drawn.line, None);
+        assert_eq!(drawn.moves, 9);
+        assert_eq!(drawn.state, GameState::Draw);
+    }
+
+    /// Plays `moves` alternately starting with X and returns the winning line.
+    fn winning_line_after(moves: &[(usize, usize)]) -> Option<[Position; 3]> {
+        let mut board = SmallBoard::new();
+        let mut mark = Mark::X;
+        for &(row, col) in moves {
+            board.make_move(row, col, mark);
+            mark = mark.switch();
+        }
+        board.winning_line()
+    }
+
+    fn line(cells: [(usize, usize); 3]) -> Option<[Position; 3]> {
+        Some(cells.map(|(row, col)| Position { row, col }))
+    }
+
+    #[test]
+    fn test_winning_line_is_recorded() {
+        assert_eq!(
+            winning_line_after(&[(1, 0), (0, 0), (1, 1), (0, 1), (1, 2)]),
+            line([(1, 0), (1, 1), (1, 2)])
+        );
+        assert_eq!(
+            winning_line_after(&[(0, 2), (0, 0), (1, 2), (1, 1), (2, 2)]),
+            line([(0, 2), (1, 2), (2, 2)])
+        );
+        assert_eq!(
+            winning_line_after(&[(0, 0), (0, 1), (1, 1), (0, 2), (2, 2)]),
+            line([(0, 0), (1, 1), (2, 2)])
+        );
+        assert_eq!(
+            winning_line_after(&[(0, 2), (0, 0), (1, 1), (0, 1), (2, 0)]),
+            line([(0, 2), (1, 1), (2, 0)])
+        );
+        assert_eq!(
+            winning_line_after(&[(1, 0), (0, 0), (1, 1), (0, 1), (2, 2)]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_draw_has_no_winning_line() {
+        let board = SmallBoard::from_str_grid("XOX|XOO|OXX").unwrap();
+        assert_eq!(board.state, GameState::Draw);
+        assert_eq!(board.winning_line(), None);
+    }
+
+    #[test]
+    fn test_transform_moves_winning_line() {
+        let board = SmallBoard::from_str_grid("XXX|OO.|...").unwrap();
+        let rotated = board.transform(1, false);
+        assert_eq!(rotated.winning_line(), line([(0, 2), (1, 2), (2, 2)]));
+        assert!(rotated.is_symmetric_to(&board));
+    }
+
+    #[test]
+    fn test_str_grid_round_trips() {
+        let board = SmallBoard::from_str_grid("X.O|..X|O..").unwrap();
+        assert_eq!(board.get(0, 0), Some(Mark::X));
+        assert_eq!(board.get(0, 2), Some(Mark::O));
+        assert_eq!(board.get(1, 2), Some(Mark::X));
+        assert_eq!(board.get(2, 0), Some(Mark::O));
+        assert_eq!(board.available_moves().len(), 5);
+        assert_eq!(board.state, GameState::Playing);
+        assert_eq!(board.to_str_grid(), "X.O|..X|O..");
+
+        let spaced = SmallBoard::from_str_grid("X O\n  X\nO  \n").unwrap();
+        assert_eq!(spaced, board);
+        assert_eq!(
+            SmallBoard::from_str_grid("").err(),
+            Some(ParseError::WrongCellCount(0))
+        );
+        assert_eq!(SmallBoard::new().to_str_grid(), "...|...|...");
+    }
+
+    #[test]
+    fn test_str_grid_recomputes_state() {
+        let won = SmallBoard::from_str_grid("XXX|OO.|...").unwrap();
+        assert_eq!(won.state, GameState::Won(Mark::X));
+
+        let drawn = SmallBoard::from_str_grid("XOX|XOO|OXX").unwrap();
+        assert_eq!(drawn.state, GameState::Draw);
+    }
+
+    #[test]
+    fn test_str_grid_rejects_malformed_input() {
+        assert_eq!(
+            SmallBoard::from_str_grid("X.O|..Z|O.."),
+            Err(ParseError::InvalidCharacter('Z'))
+        );
+        assert_eq!(
+            SmallBoard::from_str_grid("X.O|..X|O."),
+            Err(ParseError::WrongCellCount(8))
+        );
+        assert_eq!(
+            SmallBoard::from_str_grid("X.O|..X|O...."),
+            Err(ParseError::WrongCellCount(11))
+        );
+        assert_eq!(
+            SmallBoard::from_str_grid("XXX|XX.|..."),
+            Err(ParseError::IllegalMarkCount { x: 5, o: 0 })
+        );
+    }
+
+    #[test]
+    fn test_available_moves() {
+        let mut board = SmallBoard::new();
+        assert_eq!(board.available_moves().len(), 9);
+
+        // One move from full, with no line completed
+        board.set_row(0, [Some(Mark::X), Some(Mark::O), Some(Mark::X)]);
+        board.set_row(1, [Some(Mark::X), Some(Mark::O), Some(Mark::O)]);
+        board.set_row(2, [Some(Mark::O), Some(Mark::X), None]);
+        assert_eq!(board.available_moves(), vec![Position { row: 2, col: 2 }]);
+
+        let mut board = SmallBoard::new();
+        board.make_move(0, 0, Mark::X);
+        board.make_move(1, 0, Mark::O);
+        board.make_move(0, 1, Mark::X);
+        board.make_move(1, 1, Mark::O);
+        board.make_move(0, 2, Mark::X);
+        assert_eq!(board.state, GameState::Won(Mark::X));
+        assert!(board.available_moves().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to access board position (3, 0) which is out of bounds.")]
+    fn test_get_row_three_is_out_of_bounds() {
+        SmallBoard::new().get(3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to access board position (0, 3) which is out of bounds.")]
+    fn test_set_col_three_is_out_of_bounds() {
+        SmallBoard::new().set(0, 3, Some(Mark::X));
+    }
+
+    #[test]
+    fn test_try_make_move_reports_illegal_moves() {
+        let mut board = SmallBoard::from_str_grid("XX.|OO.|...").unwrap();
+        assert_eq!(board.try_make_move(0, 0, Mark::X), Err(MoveError::Occupied));
+        assert_eq!(
+            board.try_make_move(3, 0, Mark::X),
+            Err(MoveError::OutOfBounds)
+        );
+        assert_eq!(
+            board.try_make_move(0, 3, Mark::X),
+            Err(MoveError::OutOfBounds)
+        );
+        assert_eq!(board.to_str_grid(), "XX.|OO.|...");
+
+        assert_eq!(board.try_make_move(0, 2, Mark::X), Ok(()));
+        assert_eq!(board.try_make_move(1, 2, Mark::O), Err(MoveError::GameOver));
+        assert_eq!(board.state, GameState::Won(Mark::X));
+    }
+
     #[test]
     #[should_panic(expected = "tried making a move on an occupied position")]
     fn test_make_move_occupied_position() {
@@ -264,4 +791,54 @@ mod tests {
         // Try to make a move after draw
         board.make_move(0, 0, Mark::X); // Should panic
     }
+
+    #[test]
+    fn test_current_player_is_inferred_from_mark_counts() {
+        let mut board = SmallBoard::new();
+        assert_eq!(board.current_player(), None);
+        board.make_move(1, 1, Mark::X);
+        assert_eq!(board.current_player(), Some(Mark::O));
+        board.make_move(0, 0, Mark::O);
+        assert_eq!(board.current_player(), None);
+        board.make_move(2, 2, Mark::O);
+        assert_eq!(board.current_player(), Some(Mark::X));
+    }
+
+    #[test]
+    fn test_transform_rotates_clockwise_and_mirrors() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::O));
+
+        let rotated = board.transform(1, false);
+        assert_eq!(rotated.get(0, 2), Some(Mark::X));
+        assert_eq!(rotated.get(1, 2), Some(Mark::O));
+
+        let mirrored = board.transform(0, true);
+        assert_eq!(mirrored.get(0, 2), Some(Mark::X));
+        assert_eq!(mirrored.get(0, 1), Some(Mark::O));
+
+        assert_eq!(board.transform(4, false), board);
+    }
+
+    #[test]
+    fn test_all_symmetries_share_canonical_form() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::O));
+        board.set(1, 2, Some(Mark::X));
+        let canonical = board.canonical();
+
+        for rotations in 0..4 {
+            for mirrored in [false, true] {
+                let symmetric = board.transform(rotations, mirrored);
+                assert_eq!(symmetric.canonical(), canonical);
+                assert!(symmetric.is_symmetric_to(&board));
+            }
+        }
+
+        let mut other = SmallBoard::new();
+        other.set(1, 1, Some(Mark::X));
+        assert!(!other.is_symmetric_to(&board));
+    }
 }
@@ -0,0 +1,214 @@
+use super::*;
+
+/// Directions a line can run in: across, down, and along both diagonals.
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// A square board of any size, won by placing `win_len` marks in a row.
+///
+/// The cells are stored row by row in a flat vector of `size * size` cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenericBoard {
+    /// Number of rows and columns.
+    pub size: usize,
+    /// Number of marks in a row needed to win.
+    pub win_len: usize,
+    /// Cells in row-major order.
+    pub cells: Vec<Option<Mark>>,
+    /// Current completion state of the board.
+    pub state: GameState,
+}
+
+impl GenericBoard {
+    /// Creates an empty board.
+    ///
+    /// # Arguments
+    /// * `size` - Number of rows and columns
+    /// * `win_len` - Number of marks in a row needed to win
+    ///
+    /// # Panics
+    /// Panics if `win_len` is zero or larger than `size`.
+    pub fn new(size: usize, win_len: usize) -> Self {
+        if win_len == 0 || win_len > size {
+            panic!("Cannot win with {win_len} in a row on a {size}x{size} board.");
+        }
+        GenericBoard {
+            size,
+            win_len,
+            cells: vec![None; size * size],
+            state: GameState::Playing,
+        }
+    }
+
+    /// Makes a move on the board at the specified position.
+    ///
+    /// Places the given mark, then checks whether the move won the game or
+    /// filled the board and updates the game state.
+    ///
+    /// # Panics
+    /// * Panics if the game is already over (state is not GameState::Playing)
+    /// * Panics if the position is out of bounds or already occupied
+    pub fn make_move(&mut self, row: usize, col: usize, mark: Mark) {
+        if self.state != GameState::Playing {
+            panic!("Error: tried making a move on a completed board.");
+        }
+        if row >= self.size || col >= self.size {
+            panic!("Tried to access board position ({row}, {col}) which is out of bounds.");
+        }
+        if self.get(row, col).is_some() {
+            panic!("Error: tried making a move on an occupied position.");
+        }
+        self.cells[row * self.size + col] = Some(mark);
+        if self.has_line(mark) {
+            self.state = GameState::Won(mark);
+        } else if self.cells.iter().all(Option::is_some) {
+            self.state = GameState::Draw;
+        }
+    }
+
+    /// Returns whether `mark` fills any window of `win_len` consecutive cells.
+    fn has_line(&self, mark: Mark) -> bool {
+        (0..self.size).any(|row| {
+            (0..self.size).any(|col| {
+                DIRECTIONS
+                    .iter()
+                    .any(|&direction| self.window_filled(row, col, direction, mark))
+            })
+        })
+    }
+
+    /// Returns whether the window starting at (`row`, `col`) in `direction`
+    /// fits on the board and holds only `mark`.
+    fn window_filled(
+        &self,
+        row: usize,
+        col: usize,
+        (row_step, col_step): (isize, isize),
+        mark: Mark,
+    ) -> bool {
+        (0..self.win_len as isize).all(|offset| {
+            let cell_row = row as isize + offset * row_step;
+            let cell_col = col as isize + offset * col_step;
+            (0..self.size as isize).contains(&cell_row)
+                && (0..self.size as isize).contains(&cell_col)
+                && self.get(cell_row as usize, cell_col as usize) == Some(mark)
+        })
+    }
+}
+
+impl Board for GenericBoard {
+    fn get(&self, row: usize, col: usize) -> Option<Mark> {
+        if row >= self.size || col >= self.size {
+            panic!("Tried to access board position ({row}, {col}) which is out of bounds.");
+        }
+        self.cells[row * self.size + col]
+    }
+
+    fn is_playable(&self, row: usize, col: usize) -> bool {
+        self.state == GameState::Playing && self.get(row, col).is_none()
+    }
+
+    fn dims(&self) -> (usize, usize) {
+        (self.size, self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays the moves alternately from X on a 5x5 board with 4 in a row.
+    fn play(moves: &[(usize, usize)]) -> GenericBoard {
+        let mut board = GenericBoard::new(5, 4);
+        let mut mark = Mark::X;
+        for &(row, col) in moves {
+            board.make_move(row, col, mark);
+            mark = mark.switch();
+        }
+        board
+    }
+
+    #[test]
+    fn test_horizontal_win() {
+        let board = play(&[(2, 1), (0, 0), (2, 2), (0, 1), (2, 3), (4, 4), (2, 4)]);
+        assert_eq!(board.state, GameState::Won(Mark::X));
+    }
+
+    #[test]
+    fn test_vertical_win() {
+        let board = play(&[
+            (0, 0),
+            (0, 4),
+            (1, 1),
+            (1, 4),
+            (2, 2),
+            (2, 4),
+            (4, 0),
+            (3, 4),
+        ]);
+        assert_eq!(board.state, GameState::Won(Mark::O));
+    }
+
+    #[test]
+    fn test_diagonal_win() {
+        let board = play(&[(1, 1), (0, 4), (2, 2), (0, 3), (3, 3), (4, 0), (4, 4)]);
+        assert_eq!(board.state, GameState::Won(Mark::X));
+    }
+
+    #[test]
+    fn test_anti_diagonal_win() {
+        let board = play(&[(0, 3), (0, 0), (1, 2), (0, 1), (2, 1), (4, 4), (3, 0)]);
+        assert_eq!(board.state, GameState::Won(Mark::X));
+    }
+
+    #[test]
+    fn test_three_in_a_row_does_not_win() {
+        let board = play(&[(0, 0), (4, 4), (0, 1), (4, 3), (0, 2)]);
+        assert_eq!(board.state, GameState::Playing);
+        assert!(board.is_playable(0, 3));
+    }
+
+    #[test]
+    fn test_full_board_without_line_is_a_draw() {
+        // Pairs of columns alternate marks, and the pattern shifts every row,
+        // so no row, column or diagonal holds four equal marks.
+        let mut board = GenericBoard::new(5, 4);
+        for row in 0..5 {
+            for col in 0..5 {
+                let mark = if (col / 2 + row) % 2 == 0 {
+                    Mark::X
+                } else {
+                    Mark::O
+                };
+                board.make_move(row, col, mark);
+                if (row, col) != (4, 4) {
+                    assert_eq!(board.state, GameState::Playing, "won at ({row}, {col})");
+                }
+            }
+        }
+        assert_eq!(board.state, GameState::Draw);
+        assert!(!board.is_playable(4, 4));
+    }
+
+    #[test]
+    fn test_four_by_four_needs_four_in_a_row() {
+        let mut board = GenericBoard::new(4, 4);
+        let moves = [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2), (3, 1)];
+        let mut mark = Mark::X;
+        for (row, col) in moves {
+            board.make_move(row, col, mark);
+            mark = mark.switch();
+        }
+        // Three on the diagonal do not win a 4x4 board.
+        assert_eq!(board.state, GameState::Playing);
+
+        board.make_move(3, 3, Mark::X);
+        assert_eq!(board.state, GameState::Won(Mark::X));
+        assert_eq!(board.dims(), (4, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot win with 6 in a row")]
+    fn test_win_len_must_fit_the_board() {
+        GenericBoard::new(5, 6);
+    }
+}
@@ -0,0 +1,384 @@
+use super::base::SmallBoard;
+use super::ultimate::BigBoard;
+use super::{GameState, GameVariant, Mark, MoveError, RuleSet};
+use crate::ai::{Game, Move};
+use std::fmt;
+
+/// A complete game written as a single notation line.
+///
+/// The line lists the variant, the mark that moved first, and every move in
+/// order, e.g. `classic X 4 0 8` or `ultimate O 4.4 4.0`. Classic moves and
+/// Ultimate board/cell indices are numbered 0-8 in row-major order. Classic
+/// games played with misère rules follow the variant with `misere`, e.g.
+/// `classic misere X 4 0 8`; without it the game used standard rules.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRecord {
+    /// Variant the game was played in.
+    pub variant: GameVariant,
+    /// Whether a completed line won or lost a classic game.
+    pub rules: RuleSet,
+    /// Mark that played the first move.
+    pub first_player: Mark,
+    /// Moves in the order they were played.
+    pub moves: Vec<Move>,
+}
+
+/// Board reconstructed from a [`GameRecord`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecordBoard {
+    /// A classic board.
+    Classic(SmallBoard),
+    /// An Ultimate board.
+    Ultimate(BigBoard),
+}
+
+impl RecordBoard {
+    /// Returns the completion state of the reconstructed board.
+    pub fn state(&self) -> GameState {
+        match self {
+            RecordBoard::Classic(board) => board.state,
+            RecordBoard::Ultimate(board) => board.state,
+        }
+    }
+}
+
+impl GameRecord {
+    /// Creates a record without any moves.
+    pub fn new(variant: GameVariant, first_player: Mark) -> Self {
+        Self {
+            variant,
+            rules: RuleSet::Standard,
+            first_player,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Parses a notation line into a record.
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if the line is not valid notation.
+    pub fn parse(line: &str) -> Result<Self, NotationError> {
+        let mut tokens = line.split_whitespace().peekable();
+        let variant = match tokens.next() {
+            Some("classic") => GameVariant::Classic,
+            Some("ultimate") => GameVariant::Ultimate,
+            Some(token) => return Err(NotationError::new(format!("unknown variant '{token}'"))),
+            None => return Err(NotationError::new("empty line")),
+        };
+        let rules = if tokens.next_if_eq(&"misere").is_some() {
+            if variant == GameVariant::Ultimate {
+                return Err(NotationError::new(
+                    "misère rules only apply to classic games",
+                ));
+            }
+            RuleSet::Misere
+        } else {
+            RuleSet::Standard
+        };
+        let first_player = match tokens.next() {
+            Some("X") => Mark::X,
+            Some("O") => Mark::O,
+            Some(token) => return Err(NotationError::new(format!("unknown mark '{token}'"))),
+            None => return Err(NotationError::new("missing first player")),
+        };
+        let moves = tokens
+            .map(|token| parse_move(variant, token))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            variant,
+            rules,
+            first_player,
+            moves,
+        })
+    }
+
+    /// Parses a transcript written by [`write_transcript`] into a record.
+    ///
+    /// An empty transcript is a game that X has not started yet. The moves
+    /// are not checked against the rules, see [`GameRecord::board_at`].
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if a move is malformed or the marks do not alternate.
+    pub fn from_transcript(variant: GameVariant, transcript: &str) -> Result<Self, NotationError> {
+        let mut record = Self::new(variant, Mark::X);
+        for (index, token) in transcript.split_whitespace().enumerate() {
+            let invalid = || NotationError::new(format!("invalid move '{token}'"));
+            let (mark, coordinates) = token.split_once(':').ok_or_else(invalid)?;
+            let mark = match mark {
+                "X" => Mark::X,
+                "O" => Mark::O,
+                _ => return Err(NotationError::new(format!("unknown mark '{mark}'"))),
+            };
+            if index == 0 {
+                record.first_player = mark;
+            } else if (index % 2 == 0) != (mark == record.first_player) {
+                return Err(NotationError::new(format!(
+                    "move {} is played out of turn",
+                    index + 1
+                )));
+            }
+            let mv = match variant {
+                GameVariant::Classic => parse_algebraic(coordinates),
+                GameVariant::Ultimate => coordinates.split_once('/').and_then(|(board, cell)| {
+                    let (board_row, board_col) = parse_row_col(board)?;
+                    let (cell_row, cell_col) = parse_row_col(cell)?;
+                    Some(Move::Ultimate(board_row, board_col, cell_row, cell_col))
+                }),
+            };
+            record.moves.push(mv.ok_or_else(invalid)?);
+        }
+        Ok(record)
+    }
+
+    /// Rebuilds the board after the first `ply` moves of the record.
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if any of those moves is illegal.
+    pub fn board_at(&self, ply: usize) -> Result<RecordBoard, NotationError> {
+        match self.variant {
+            GameVariant::Classic => {
+                let board = self.replay(SmallBoard::with_rules(self.rules), ply)?;
+                Ok(RecordBoard::Classic(board))
+            }
+            GameVariant::Ultimate => {
+                let board = self.replay(BigBoard::new(), ply)?;
+                Ok(RecordBoard::Ultimate(board))
+            }
+        }
+    }
+
+    fn replay<T: Game>(&self, mut board: T, ply: usize) -> Result<T, NotationError> {
+        let mut active_player = self.first_player;
+        for (index, mv) in self.moves.iter().take(ply).enumerate() {
+            if !board.get_possible_moves().contains(mv) {
+                return Err(NotationError::new(format!("move {} is illegal", index + 1)));
+            }
+            board.play(mv, active_player);
+            active_player = active_player.switch();
+        }
+        Ok(board)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let variant = match self.variant {
+            GameVariant::Classic => "classic",
+            GameVariant::Ultimate => "ultimate",
+        };
+        write!(f, "{variant}")?;
+        if self.rules == RuleSet::Misere {
+            write!(f, " misere")?;
+        }
+        write!(f, " {}", self.first_player)?;
+        for mv in &self.moves {
+            match mv {
+                Move::Base(row, col) => write!(f, " {}", row * 3 + col)?,
+                Move::Ultimate(board_row, board_col, cell_row, cell_col) => write!(
+                    f,
+                    " {}.{}",
+                    board_row * 3 + board_col,
+                    cell_row * 3 + cell_col
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes moves as a transcript such as `X:b2 O:a1 X:c3`, each move prefixed by its mark.
+///
+/// Classic cells are a column letter from `a` to `c` followed by a row number
+/// from `1` to `3`, counted from the top. Ultimate moves give the row and
+/// column of the small board, then of the cell, such as `X:0,0/1,1`.
+///
+/// # Arguments
+/// * `first_player` - Mark that played the first move
+/// * `moves` - Moves in the order they were played
+pub fn write_transcript(first_player: Mark, moves: &[Move]) -> String {
+    let mut mark = first_player;
+    let mut tokens = Vec::with_capacity(moves.len());
+    for mv in moves {
+        let coordinates = match *mv {
+            Move::Base(row, col) => format!("{}{}", char::from(b'a' + col as u8), row + 1),
+            Move::Ultimate(board_row, board_col, cell_row, cell_col) => {
+                format!("{board_row},{board_col}/{cell_row},{cell_col}")
+            }
+        };
+        tokens.push(format!("{mark}:{coordinates}"));
+        mark = mark.switch();
+    }
+    tokens.join(" ")
+}
+
+/// Parses a classic cell such as `b2` into a move.
+fn parse_algebraic(coordinates: &str) -> Option<Move> {
+    let &[column, row] = coordinates.as_bytes() else {
+        return None;
+    };
+    if !(b'a'..=b'c').contains(&column) || !(b'1'..=b'3').contains(&row) {
+        return None;
+    }
+    Some(Move::Base((row - b'1') as usize, (column - b'a') as usize))
+}
+
+/// Parses a position such as `0,2` on a 3x3 grid.
+fn parse_row_col(coordinates: &str) -> Option<(usize, usize)> {
+    let (row, col) = coordinates.split_once(',')?;
+    let row = row.parse::<usize>().ok().filter(|row| *row < 3)?;
+    let col = col.parse::<usize>().ok().filter(|col| *col < 3)?;
+    Some((row, col))
+}
+
+fn parse_move(variant: GameVariant, token: &str) -> Result<Move, NotationError> {
+    let invalid = || NotationError::new(format!("invalid move '{token}'"));
+    match variant {
+        GameVariant::Classic => {
+            let index = parse_index(token).ok_or_else(invalid)?;
+            Ok(Move::Base(index / 3, index % 3))
+        }
+        GameVariant::Ultimate => {
+            let (board, cell) = token.split_once('.').ok_or_else(invalid)?;
+            let board = parse_index(board).ok_or_else(invalid)?;
+            let cell = parse_index(cell).ok_or_else(invalid)?;
+            Ok(Move::Ultimate(board / 3, board % 3, cell / 3, cell % 3))
+        }
+    }
+}
+
+fn parse_index(token: &str) -> Option<usize> {
+    token.parse::<usize>().ok().filter(|index| *index < 9)
+}
+
+/// Error returned when a notation line cannot be parsed or replayed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotationError {
+    reason: String,
+}
+
+impl NotationError {
+    fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "invalid game notation: {}", self.reason)
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl From<MoveError> for NotationError {
+    fn from(error: MoveError) -> Self {
+        Self::new(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Board;
+
+    #[test]
+    fn test_classic_record_round_trips() {
+        let record = GameRecord::parse("classic X 4 0 8").unwrap();
+        assert_eq!(record.variant, GameVariant::Classic);
+        assert_eq!(record.rules, RuleSet::Standard);
+        assert_eq!(record.first_player, Mark::X);
+        assert_eq!(
+            record.moves,
+            vec![Move::Base(1, 1), Move::Base(0, 0), Move::Base(2, 2)]
+        );
+        assert_eq!(record.to_string(), "classic X 4 0 8");
+    }
+
+    #[test]
+    fn test_transcript_round_trips() {
+        let moves = vec![Move::Base(1, 1), Move::Base(0, 0), Move::Base(2, 2)];
+        let transcript = write_transcript(Mark::X, &moves);
+        assert_eq!(transcript, "X:b2 O:a1 X:c3");
+        let record = GameRecord::from_transcript(GameVariant::Classic, &transcript).unwrap();
+        assert_eq!(record.first_player, Mark::X);
+        assert_eq!(record.moves, moves);
+
+        let moves = vec![Move::Ultimate(0, 0, 1, 1), Move::Ultimate(1, 1, 0, 2)];
+        let transcript = write_transcript(Mark::O, &moves);
+        assert_eq!(transcript, "O:0,0/1,1 X:1,1/0,2");
+        let record = GameRecord::from_transcript(GameVariant::Ultimate, &transcript).unwrap();
+        assert_eq!(record.first_player, Mark::O);
+        assert_eq!(record.moves, moves);
+    }
+
+    #[test]
+    fn test_invalid_transcripts_are_rejected() {
+        for transcript in ["X:d1", "X:b4", "X-b2", "Y:b2", "X:b2 X:a1", "X:0,0/1,1"] {
+            assert!(
+                GameRecord::from_transcript(GameVariant::Classic, transcript).is_err(),
+                "accepted '{transcript}'"
+            );
+        }
+        assert!(GameRecord::from_transcript(GameVariant::Ultimate, "X:0,3/1,1").is_err());
+        assert!(GameRecord::from_transcript(GameVariant::Ultimate, "X:b2").is_err());
+    }
+
+    #[test]
+    fn test_ultimate_record_round_trips() {
+        let record = GameRecord::parse("ultimate O 4.5 5.0").unwrap();
+        assert_eq!(
+            record.moves,
+            vec![Move::Ultimate(1, 1, 1, 2), Move::Ultimate(1, 2, 0, 0)]
+        );
+        assert_eq!(record.to_string(), "ultimate O 4.5 5.0");
+    }
+
+    #[test]
+    fn test_malformed_lines_are_rejected() {
+        assert!(GameRecord::parse("").is_err());
+        assert!(GameRecord::parse("chess X e4").is_err());
+        assert!(GameRecord::parse("classic Z 4").is_err());
+        assert!(GameRecord::parse("classic X 9").is_err());
+        assert!(GameRecord::parse("ultimate X 4").is_err());
+    }
+
+    #[test]
+    fn test_logged_line_reconstructs_final_state() {
+        let record = GameRecord::parse("classic X 0 3 1 4 2").unwrap();
+        let RecordBoard::Classic(board) = record.board_at(record.moves.len()).unwrap() else {
+            panic!("expected classic board");
+        };
+        assert_eq!(board.state, GameState::Won(Mark::X));
+        assert_eq!(board.get(1, 1), Some(Mark::O));
+
+        let RecordBoard::Classic(board) = record.board_at(2).unwrap() else {
+            panic!("expected classic board");
+        };
+        assert_eq!(board.state, GameState::Playing);
+        assert_eq!(board.get(0, 1), None);
+    }
+
+    #[test]
+    fn test_misere_line_replays_with_misere_rules() {
+        let record = GameRecord::parse("classic misere X 0 3 1 4 2").unwrap();
+        assert_eq!(record.rules, RuleSet::Misere);
+        assert_eq!(record.to_string(), "classic misere X 0 3 1 4 2");
+        // X completes the top row and so loses.
+        assert_eq!(
+            record.board_at(record.moves.len()).unwrap().state(),
+            GameState::Won(Mark::O)
+        );
+
+        assert!(GameRecord::parse("ultimate misere X 4.4").is_err());
+    }
+
+    #[test]
+    fn test_illegal_move_is_reported() {
+        let record = GameRecord::parse("classic X 0 0").unwrap();
+        assert!(record.board_at(1).is_ok());
+        assert!(record.board_at(2).is_err());
+    }
+}
@@ -1,12 +1,14 @@
 use super::base::SmallBoard;
 use super::*;
 use crate::ai::Move::Ultimate;
-use crate::ai::{Game, Move};
+use crate::ai::{Game, Move, opening_book};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// A 3x3 grid of tic-tac-toe boards for Ultimate Tic-Tac-Toe.
 ///
 /// The board is represented as a flat array of 9 small boards.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BigBoard {
     boards: [SmallBoard; 9],
     /// Current completion state of the Ultimate board.
@@ -32,9 +34,9 @@ impl BigBoard {
     /// * `board_col` - Column index of the small board (0-2)
     ///
     /// # Panics
-    /// Panics if board_row or board_col is greater than 3.
+    /// Panics if board_row or board_col is greater than 2.
     pub fn get_board(&self, board_row: usize, board_col: usize) -> &SmallBoard {
-        if board_row > 3 || board_col > 3 {
+        if board_row >= 3 || board_col >= 3 {
             panic!(
                 "Error: tried to access board({board_row}, {board_col}) which is out of bounds."
             );
@@ -42,6 +44,82 @@ impl BigBoard {
         &self.boards[board_row * 3 + board_col]
     }
 
+    /// Gets a reference to the small board at the specified position, if there is one.
+    ///
+    /// # Arguments
+    /// * `board_row` - Row index of the small board (0-2)
+    /// * `board_col` - Column index of the small board (0-2)
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`] if board_row or board_col is greater than 2.
+    pub fn try_get_board(
+        &self,
+        board_row: usize,
+        board_col: usize,
+    ) -> Result<&SmallBoard, OutOfBounds> {
+        if board_row >= 3 || board_col >= 3 {
+            return Err(OutOfBounds {
+                row: board_row,
+                col: board_col,
+            });
+        }
+        Ok(&self.boards[board_row * 3 + board_col])
+    }
+
+    /// Gets the completion state of the small board at the specified position.
+    ///
+    /// # Arguments
+    /// * `board_row` - Row index of the small board (0-2)
+    /// * `board_col` - Column index of the small board (0-2)
+    ///
+    /// # Panics
+    /// Panics if board_row or board_col is greater than 2.
+    pub fn sub_state(&self, board_row: usize, board_col: usize) -> GameState {
+        if board_row >= 3 || board_col >= 3 {
+            panic!(
+                "Error: tried to access board({board_row}, {board_col}) which is out of bounds."
+            );
+        }
+        self.boards[board_row * 3 + board_col].state
+    }
+
+    /// Gets the result of every small board, indexed by board row and column.
+    ///
+    /// # Returns
+    /// `Won(mark)` or `Draw` for completed small boards, None for those still in play.
+    pub fn results(&self) -> [[Option<GameState>; 3]; 3] {
+        std::array::from_fn(|board_row| {
+            std::array::from_fn(|board_col| match self.sub_state(board_row, board_col) {
+                GameState::Playing => None,
+                state => Some(state),
+            })
+        })
+    }
+
+    /// Counts the meta-lines `mark` is one small board away from completing.
+    ///
+    /// Won small boards act as marks on the meta-grid, drawn ones block their lines.
+    ///
+    /// # Returns
+    /// The number of meta-lines holding two boards won by `mark` and one still in play.
+    pub fn meta_threats(&self, mark: Mark) -> usize {
+        let results = self.results();
+        LINES
+            .iter()
+            .filter(|line| {
+                let won = line
+                    .iter()
+                    .filter(|&&(row, col)| results[row][col] == Some(GameState::Won(mark)))
+                    .count();
+                let open = line
+                    .iter()
+                    .filter(|&&(row, col)| results[row][col].is_none())
+                    .count();
+                won == 2 && open == 1
+            })
+            .count()
+    }
+
     /// Gets the mark at the specified position within a specific small board.
     ///
     /// # Arguments
@@ -54,7 +132,7 @@ impl BigBoard {
     /// The mark at the specified position, or None if the cell is empty.
     ///
     /// # Panics
-    /// Panics if any index is greater than 3.
+    /// Panics if any index is greater than 2.
     pub fn get(
         &mut self,
         board_row: usize,
@@ -65,6 +143,38 @@ impl BigBoard {
         self.get_board(board_row, board_col).get(cell_row, cell_col)
     }
 
+    /// Gets the mark at the specified position within a specific small board,
+    /// without panicking on user-supplied coordinates.
+    ///
+    /// # Arguments
+    /// * `board_row` - Row index of the small board (0-2)
+    /// * `board_col` - Column index of the small board (0-2)
+    /// * `cell_row` - Row index within the small board (0-2)
+    /// * `cell_col` - Column index within the small board (0-2)
+    ///
+    /// # Returns
+    /// The mark at the specified position, or None if the cell is empty.
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`] with the first offending pair of indices if any
+    /// index is greater than 2.
+    pub fn try_get(
+        &self,
+        board_row: usize,
+        board_col: usize,
+        cell_row: usize,
+        cell_col: usize,
+    ) -> Result<Option<Mark>, OutOfBounds> {
+        let board = self.try_get_board(board_row, board_col)?;
+        if cell_row >= 3 || cell_col >= 3 {
+            return Err(OutOfBounds {
+                row: cell_row,
+                col: cell_col,
+            });
+        }
+        Ok(board.get(cell_row, cell_col))
+    }
+
     /// Checks if all small boards are either won or complete (draw).
     ///
     /// # Returns
@@ -94,9 +204,9 @@ impl BigBoard {
     /// * `mark` - The mark to place (Mark::X or Mark::O)
     ///
     /// # Panics
-    /// * Panics if the BigBoard game is already over (state is not GameState::Playing)
+    /// * Panics if the BigBoard game or the small board is already over
     /// * Panics if there is an active board constraint and the move is attempted on a different board
-    /// * Panics if the specified position is already occupied (delegated to SmallBoard::make_move)
+    /// * Panics if the specified position is out of bounds or already occupied
     pub fn make_move(
         &mut self,
         board_row: usize,
@@ -105,30 +215,193 @@ impl BigBoard {
         cell_col: usize,
         mark: Mark,
     ) {
+        if let Err(error) = self.try_make_move(board_row, board_col, cell_row, cell_col, mark) {
+            panic!("Error: {error}.");
+        }
+    }
+
+    /// Makes a move on the BigBoard, leaving it unchanged if the move is illegal.
+    ///
+    /// # Arguments
+    /// * `board_row` - Row index of the small board (0-2)
+    /// * `board_col` - Column index of the small board (0-2)
+    /// * `cell_row` - Row index within the small board (0-2)
+    /// * `cell_col` - Column index within the small board (0-2)
+    /// * `mark` - The mark to place (Mark::X or Mark::O)
+    ///
+    /// # Errors
+    /// Returns a [`MoveError`] if the game or the small board is finished, an
+    /// index is out of bounds, the move is outside the active board or the
+    /// cell is occupied.
+    pub fn try_make_move(
+        &mut self,
+        board_row: usize,
+        board_col: usize,
+        cell_row: usize,
+        cell_col: usize,
+        mark: Mark,
+    ) -> Result<(), MoveError> {
         if self.state != GameState::Playing {
-            panic!("Error: tried making a move on a completed big board.");
+            return Err(MoveError::GameOver);
         }
-        if let Some(active_board) = self.active_board {
-            if (board_row, board_col) != active_board {
-                panic!("Error: tried making a move on a board different than the active board.");
-            }
+        if board_row >= 3 || board_col >= 3 {
+            return Err(MoveError::OutOfBounds);
         }
-
-        self.boards[board_row * 3 + board_col].make_move(cell_row, cell_col, mark);
-        if self.check_complete() {
-            self.state = GameState::Draw;
+        if let Some(active_board) = self.active_board
+            && (board_row, board_col) != active_board
+        {
+            return Err(MoveError::WrongBoard);
         }
+
+        self.boards[board_row * 3 + board_col].try_make_move(cell_row, cell_col, mark)?;
+        // A meta line wins even if it is completed by the last open board.
         if let Some(mark) = check_win(self) {
             self.state = GameState::Won(mark);
-        };
+        } else if self.check_complete() {
+            self.state = GameState::Draw;
+        }
 
-        self.active_board = match self.get_board(cell_row, cell_col).state {
+        self.active_board = match self.sub_state(cell_row, cell_col) {
             GameState::Playing => Some((cell_row, cell_col)),
             _ => None,
+        };
+        Ok(())
+    }
+
+    /// Serializes the nine small boards, the Ultimate state and the active
+    /// board as JSON, for web front-ends and analysis tools.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a BigBoard always serializes to JSON")
+    }
+
+    /// Reads a board written by [`BigBoard::to_json`].
+    ///
+    /// # Errors
+    /// Returns a [`BoardJsonError`] if the JSON does not describe a board, if
+    /// the state of a small board does not follow from its cells, if its
+    /// Ultimate state does not follow from the small boards, or if its active
+    /// board is out of bounds, finished or set after the game ended.
+    pub fn from_json(json: &str) -> Result<Self, BoardJsonError> {
+        let board: BigBoard = serde_json::from_str(json)
+            .map_err(|error| BoardJsonError::Malformed(error.to_string()))?;
+        board.validate()?;
+        Ok(board)
+    }
+
+    /// Checks that the small board states, the Ultimate state and the active
+    /// board are reachable.
+    fn validate(&self) -> Result<(), BoardJsonError> {
+        for (index, board) in self.boards.iter().enumerate() {
+            let expected_state = if let Some(mark) = check_win_with_rules(board, board.rules()) {
+                GameState::Won(mark)
+            } else if check_complete(board) {
+                GameState::Draw
+            } else {
+                GameState::Playing
+            };
+            if board.state != expected_state {
+                return Err(BoardJsonError::Invalid(format!(
+                    "small board ({}, {}) is {:?} but its cells make it {:?}",
+                    index / 3,
+                    index % 3,
+                    board.state,
+                    expected_state
+                )));
+            }
+        }
+        let expected_state = if let Some(mark) = check_win(self) {
+            GameState::Won(mark)
+        } else if self.check_complete() {
+            GameState::Draw
+        } else {
+            GameState::Playing
+        };
+        if self.state != expected_state {
+            return Err(BoardJsonError::Invalid(format!(
+                "the game is {:?} but its small boards make it {:?}",
+                self.state, expected_state
+            )));
+        }
+        let Some((board_row, board_col)) = self.active_board else {
+            return Ok(());
+        };
+        if board_row >= 3 || board_col >= 3 {
+            return Err(BoardJsonError::Invalid(format!(
+                "active board ({board_row}, {board_col}) is out of bounds"
+            )));
+        }
+        if self.state != GameState::Playing {
+            return Err(BoardJsonError::Invalid(
+                "a finished game has no active board".to_string(),
+            ));
+        }
+        if self.sub_state(board_row, board_col) != GameState::Playing {
+            return Err(BoardJsonError::Invalid(format!(
+                "active board ({board_row}, {board_col}) is already finished"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Failure encountered while reading a [`BigBoard`] from JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoardJsonError {
+    /// The JSON does not describe a board.
+    Malformed(String),
+    /// The board described cannot be reached in a game.
+    Invalid(String),
+}
+
+impl fmt::Display for BoardJsonError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(error) => write!(formatter, "malformed board JSON: {error}"),
+            Self::Invalid(reason) => write!(formatter, "impossible board: {reason}"),
         }
     }
 }
 
+impl std::error::Error for BoardJsonError {}
+
+impl Default for BigBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws the nine small boards as a grid, with `.` for empty cells.
+///
+/// A captured small board is filled with the mark that won it, so the
+/// meta-grid can be read at a glance.
+impl fmt::Display for BigBoard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..9 {
+            if row > 0 && row % 3 == 0 {
+                writeln!(f, "------+-------+------")?;
+            }
+            for col in 0..9 {
+                let board = self.get_board(row / 3, col / 3);
+                let cell = match board.state {
+                    GameState::Won(mark) => Some(mark),
+                    _ => board.get(row % 3, col % 3),
+                };
+                if col > 0 {
+                    write!(f, "{}", if col % 3 == 0 { " | " } else { " " })?;
+                }
+                match cell {
+                    Some(mark) => write!(f, "{mark}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+            if row < 8 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Board for BigBoard {
     /// Gets the winning mark for a small board at the specified position.
     ///
@@ -145,9 +418,9 @@ impl Board for BigBoard {
     /// or None if it's still playing or ended in a draw.
     ///
     /// # Panics
-    /// Panics if board_row or board_col is greater than 3.
+    /// Panics if board_row or board_col is greater than 2.
     fn get(&self, board_row: usize, board_col: usize) -> Option<Mark> {
-        if let GameState::Won(mark) = self.get_board(board_row, board_col).state {
+        if let GameState::Won(mark) = self.sub_state(board_row, board_col) {
             Some(mark)
         } else {
             None
@@ -163,7 +436,7 @@ impl Board for BigBoard {
     /// # Returns
     /// True if the board is not complete, else False.
     fn is_playable(&self, row: usize, col: usize) -> bool {
-        self.get_board(row, col).state == GameState::Playing
+        self.sub_state(row, col) == GameState::Playing
     }
 }
 
@@ -181,10 +454,10 @@ impl Game for BigBoard {
         for board_row in 0..3 {
             for board_col in 0..3 {
                 // if there is an active board skip inactive boards
-                if let Some(active_board) = self.active_board {
-                    if (board_row, board_col) != active_board {
-                        continue;
-                    }
+                if let Some(active_board) = self.active_board
+                    && (board_row, board_col) != active_board
+                {
+                    continue;
                 }
 
                 let board_moves = self.get_board(board_row, board_col).get_possible_moves();
@@ -210,6 +483,18 @@ impl Game for BigBoard {
     fn get_state(&self) -> GameState {
         self.state
     }
+
+    fn current_player(&self) -> Option<Mark> {
+        next_player(
+            self.boards
+                .iter()
+                .flat_map(|board| (0..9).map(|index| board.get(index / 3, index % 3))),
+        )
+    }
+
+    fn book_move(&self) -> Option<Move> {
+        opening_book::book_move(self)
+    }
 }
 
 #[cfg(test)]
@@ -220,7 +505,7 @@ mod tests {
     fn test_check_complete() {
         let mut board = BigBoard::new();
 
-        assert_eq!(board.check_complete(), false);
+        assert!(!board.check_complete());
 
         // Win the first small board
         board.boards[0].make_move(0, 0, Mark::X);
@@ -230,7 +515,7 @@ mod tests {
         board.boards[0].make_move(0, 2, Mark::X);
 
         assert_eq!(board.boards[0].state, GameState::Won(Mark::X));
-        assert_eq!(board.check_complete(), false);
+        assert!(!board.check_complete());
 
         // Win all other 8 small boards
         for i in 1..9 {
@@ -241,7 +526,99 @@ mod tests {
             board.boards[i].make_move(0, 2, Mark::X);
         }
 
-        assert_eq!(board.check_complete(), true);
+        assert!(board.check_complete());
+    }
+
+    #[test]
+    fn test_boards_compare_by_cells_state_and_active_board() {
+        let mut first = BigBoard::new();
+        let mut second = BigBoard::new();
+        first.make_move(1, 1, 0, 2, Mark::X);
+        second.make_move(1, 1, 0, 2, Mark::X);
+        assert_eq!(first, second);
+
+        let mut other = BigBoard::new();
+        other.make_move(1, 1, 0, 1, Mark::X);
+        assert_ne!(first, other);
+
+        // Same cells, but a different board must receive the next move.
+        let mut unconstrained = first;
+        unconstrained.active_board = None;
+        assert_ne!(first, unconstrained);
+    }
+
+    /// Recomputes the score of `mark` from the cells alone.
+    fn full_score(board: &BigBoard, mark: Mark) -> i8 {
+        let mut score = 0;
+        for board_row in 0..3 {
+            for board_col in 0..3 {
+                let small = board.get_board(board_row, board_col);
+                score += match check_win(small) {
+                    Some(winner) if winner == mark => 1,
+                    Some(_) => -1,
+                    None => 0,
+                };
+            }
+        }
+        score
+    }
+
+    #[test]
+    fn test_cached_states_match_a_full_recomputation() {
+        use rand::prelude::*;
+
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..200 {
+            let mut board = BigBoard::new();
+            let mut mark = Mark::X;
+            while let Some(mv) = board.get_possible_moves().choose(&mut rng).copied() {
+                board.play(&mv, mark);
+                mark = mark.switch();
+
+                for mark in [Mark::X, Mark::O] {
+                    assert_eq!(board.score(mark), full_score(&board, mark));
+                }
+                let expected_state = if let Some(winner) = check_win(&board) {
+                    GameState::Won(winner)
+                } else if board.check_complete() {
+                    GameState::Draw
+                } else {
+                    GameState::Playing
+                };
+                assert_eq!(board.get_state(), expected_state);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_fills_captured_boards() {
+        let mut board = BigBoard::new();
+        for (board_index, cell_row, cell_col, mark) in [
+            (4, 1, 1, Mark::X),
+            (4, 0, 0, Mark::O),
+            (0, 0, 0, Mark::X),
+            (0, 1, 0, Mark::O),
+            (0, 0, 1, Mark::X),
+            (0, 1, 1, Mark::O),
+            (0, 0, 2, Mark::X),
+            (8, 2, 2, Mark::O),
+        ] {
+            board.boards[board_index].make_move(cell_row, cell_col, mark);
+        }
+
+        let expected = "\
+X X X | . . . | . . .
+X X X | . . . | . . .
+X X X | . . . | . . .
+------+-------+------
+. . . | O . . | . . .
+. . . | . X . | . . .
+. . . | . . . | . . .
+------+-------+------
+. . . | . . . | . . .
+. . . | . . . | . . .
+. . . | . . . | . . O";
+        assert_eq!(board.to_string(), expected);
     }
 
     #[test]
@@ -308,6 +685,96 @@ mod tests {
         assert_eq!(board.state, GameState::Draw);
     }
 
+    /// Completes every small board but the bottom-right one, so that only
+    /// X's main diagonal can still form a meta line.
+    fn board_one_result_from_the_end() -> BigBoard {
+        let mut board = BigBoard::new();
+        for (index, state) in [
+            (0, GameState::Won(Mark::X)),
+            (1, GameState::Won(Mark::O)),
+            (2, GameState::Draw),
+            (3, GameState::Won(Mark::O)),
+            (4, GameState::Won(Mark::X)),
+            (5, GameState::Won(Mark::X)),
+            (6, GameState::Draw),
+            (7, GameState::Won(Mark::X)),
+        ] {
+            board.boards[index].state = state;
+        }
+        board.boards[8].make_move(0, 0, Mark::X);
+        board.boards[8].make_move(1, 0, Mark::O);
+        board.boards[8].make_move(0, 1, Mark::X);
+        board.boards[8].make_move(1, 1, Mark::O);
+        board
+    }
+
+    #[test]
+    fn test_last_board_completing_a_meta_line_wins() {
+        let mut board = board_one_result_from_the_end();
+        board.make_move(2, 2, 0, 2, Mark::X);
+
+        assert!(board.check_complete());
+        assert_eq!(board.state, GameState::Won(Mark::X));
+    }
+
+    #[test]
+    fn test_all_boards_complete_without_meta_line_is_draw() {
+        let mut board = board_one_result_from_the_end();
+        board.make_move(2, 2, 1, 2, Mark::O);
+
+        assert_eq!(board.sub_state(2, 2), GameState::Won(Mark::O));
+        assert_eq!(board.state, GameState::Draw);
+    }
+
+    #[test]
+    fn test_meta_line_wins_before_all_boards_finish() {
+        let mut board = board_one_result_from_the_end();
+        board.boards[0].state = GameState::Playing;
+        board.boards[8].state = GameState::Won(Mark::X);
+        board.boards[0].make_move(0, 0, Mark::X);
+        board.boards[0].make_move(1, 0, Mark::O);
+        board.boards[0].make_move(0, 1, Mark::X);
+        board.boards[0].make_move(1, 1, Mark::O);
+        board.boards[2].state = GameState::Playing;
+        board.make_move(0, 0, 0, 2, Mark::X);
+
+        assert!(!board.check_complete());
+        assert_eq!(board.state, GameState::Won(Mark::X));
+    }
+
+    #[test]
+    fn test_try_make_move_reports_each_error() {
+        let mut board = BigBoard::new();
+        assert_eq!(board.try_make_move(1, 1, 0, 0, Mark::X), Ok(()));
+        let played = board;
+
+        assert_eq!(
+            board.try_make_move(1, 1, 1, 1, Mark::O),
+            Err(MoveError::WrongBoard)
+        );
+        assert_eq!(
+            board.try_make_move(0, 0, 3, 0, Mark::O),
+            Err(MoveError::OutOfBounds)
+        );
+        assert_eq!(
+            board.try_make_move(3, 0, 0, 0, Mark::O),
+            Err(MoveError::OutOfBounds)
+        );
+        board.active_board = Some((1, 1));
+        assert_eq!(
+            board.try_make_move(1, 1, 0, 0, Mark::O),
+            Err(MoveError::Occupied)
+        );
+        board.active_board = played.active_board;
+        assert_eq!(board, played);
+
+        board.state = GameState::Draw;
+        assert_eq!(
+            board.try_make_move(0, 0, 1, 1, Mark::O),
+            Err(MoveError::GameOver)
+        );
+    }
+
     #[test]
     #[should_panic(expected = "tried making a move on a board different than the active board")]
     fn test_make_move_wrong_active_board() {
@@ -337,4 +804,181 @@ mod tests {
         let moves = board.get_possible_moves();
         assert!(moves.is_empty());
     }
+
+    #[test]
+    fn test_json_round_trips_boards_state_and_active_board() {
+        let mut board = BigBoard::new();
+        // O takes the top row of the top-left board.
+        for (cell, mark) in [
+            ((1, 0), Mark::X),
+            ((0, 0), Mark::O),
+            ((1, 1), Mark::X),
+            ((0, 1), Mark::O),
+            ((2, 2), Mark::X),
+            ((0, 2), Mark::O),
+        ] {
+            board.boards[0].make_move(cell.0, cell.1, mark);
+        }
+        board.make_move(1, 1, 2, 2, Mark::X);
+
+        let loaded = BigBoard::from_json(&board.to_json()).unwrap();
+
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.active_board, Some((2, 2)));
+        assert_eq!(loaded.sub_state(0, 0), GameState::Won(Mark::O));
+    }
+
+    #[test]
+    fn test_json_with_impossible_active_board_is_rejected() {
+        let mut board = BigBoard::new();
+        for col in 0..3 {
+            board.boards[0].make_move(0, col, Mark::X);
+        }
+        board.active_board = Some((0, 0));
+        assert!(matches!(
+            BigBoard::from_json(&board.to_json()),
+            Err(BoardJsonError::Invalid(_))
+        ));
+
+        board.active_board = Some((3, 0));
+        assert!(matches!(
+            BigBoard::from_json(&board.to_json()),
+            Err(BoardJsonError::Invalid(_))
+        ));
+
+        board.active_board = None;
+        board.state = GameState::Won(Mark::X);
+        assert!(matches!(
+            BigBoard::from_json(&board.to_json()),
+            Err(BoardJsonError::Invalid(_))
+        ));
+
+        assert!(matches!(
+            BigBoard::from_json("{\"state\": \"Playing\"}"),
+            Err(BoardJsonError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_json_with_small_board_state_not_matching_its_cells_is_rejected() {
+        // An empty small board claimed as won.
+        let mut board = BigBoard::new();
+        board.boards[4].state = GameState::Won(Mark::O);
+        assert!(matches!(
+            BigBoard::from_json(&board.to_json()),
+            Err(BoardJsonError::Invalid(_))
+        ));
+
+        // A small board with a completed line still claimed as playing.
+        let mut board = BigBoard::new();
+        for col in 0..3 {
+            board.boards[0].make_move(0, col, Mark::X);
+        }
+        board.boards[0].state = GameState::Playing;
+        assert!(matches!(
+            BigBoard::from_json(&board.to_json()),
+            Err(BoardJsonError::Invalid(_))
+        ));
+
+        board.boards[0].state = GameState::Won(Mark::X);
+        assert!(BigBoard::from_json(&board.to_json()).is_ok());
+    }
+
+    #[test]
+    fn test_sub_state_reports_small_board_results() {
+        let mut board = BigBoard::new();
+        board.boards[4].state = GameState::Won(Mark::O);
+        board.boards[8].state = GameState::Draw;
+
+        assert_eq!(board.sub_state(0, 0), GameState::Playing);
+        assert_eq!(board.sub_state(1, 1), GameState::Won(Mark::O));
+        assert_eq!(board.sub_state(2, 2), GameState::Draw);
+    }
+
+    #[test]
+    fn test_results_of_partially_completed_board() {
+        let mut board = BigBoard::new();
+        board.boards[0].state = GameState::Won(Mark::X);
+        board.boards[5].state = GameState::Draw;
+        board.boards[7].state = GameState::Won(Mark::O);
+        board.make_move(1, 1, 0, 0, Mark::X);
+
+        assert_eq!(
+            board.results(),
+            [
+                [Some(GameState::Won(Mark::X)), None, None],
+                [None, None, Some(GameState::Draw)],
+                [None, Some(GameState::Won(Mark::O)), None],
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "tried to access board(3, 0) which is out of bounds.")]
+    fn test_get_board_row_three_is_out_of_bounds() {
+        BigBoard::new().get_board(3, 0);
+    }
+
+    #[test]
+    fn test_safe_getters_report_out_of_bounds_positions() {
+        let mut board = BigBoard::new();
+        board.make_move(2, 1, 0, 2, Mark::X);
+
+        assert_eq!(board.try_get_board(2, 1), Ok(board.get_board(2, 1)));
+        assert_eq!(board.try_get(2, 1, 0, 2), Ok(Some(Mark::X)));
+        assert_eq!(board.try_get(2, 2, 2, 2), Ok(None));
+
+        assert_eq!(
+            board.try_get_board(3, 0),
+            Err(OutOfBounds { row: 3, col: 0 })
+        );
+        assert_eq!(
+            board.try_get_board(0, 7),
+            Err(OutOfBounds { row: 0, col: 7 })
+        );
+        assert_eq!(
+            board.try_get(4, 0, 0, 0),
+            Err(OutOfBounds { row: 4, col: 0 })
+        );
+        assert_eq!(
+            board.try_get(0, 0, 1, 3),
+            Err(OutOfBounds { row: 1, col: 3 })
+        );
+        assert_eq!(
+            OutOfBounds { row: 1, col: 3 }.to_string(),
+            "position (1, 3) is out of bounds"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_sub_state_out_of_bounds() {
+        let board = BigBoard::new();
+        board.sub_state(3, 0);
+    }
+
+    #[test]
+    fn test_meta_threats_count_lines_one_board_from_completion() {
+        let mut board = BigBoard::new();
+        assert_eq!(board.meta_threats(Mark::X), 0);
+
+        for (index, state) in [
+            (0, GameState::Won(Mark::X)),
+            (1, GameState::Won(Mark::X)),
+            (2, GameState::Draw),
+            (4, GameState::Won(Mark::X)),
+            (6, GameState::Won(Mark::O)),
+            (7, GameState::Won(Mark::O)),
+        ] {
+            board.boards[index].state = state;
+        }
+
+        // The top row is blocked by a draw and the middle column by O.
+        assert_eq!(board.meta_threats(Mark::X), 1);
+        assert_eq!(board.meta_threats(Mark::O), 1);
+
+        board.boards[8].state = GameState::Won(Mark::O);
+        assert_eq!(board.meta_threats(Mark::X), 0);
+        assert_eq!(board.meta_threats(Mark::O), 0);
+    }
 }
@@ -0,0 +1,348 @@
+use crate::app::{Command, key_command};
+use crate::save::APP_DIR_NAME;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Name of the key bindings file inside the config directory.
+const KEY_BINDINGS_FILE_NAME: &str = "keys.toml";
+
+/// Returns where key binding overrides are read from, inside the platform config directory.
+///
+/// # Returns
+/// The key bindings file path, or None if the platform has no config directory.
+pub fn default_key_bindings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(KEY_BINDINGS_FILE_NAME))
+}
+
+/// Keys bound to the commands that can be remapped.
+///
+/// Every other command keeps the key listed by [`key_command`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    /// Keys moving the selection up.
+    pub up: Vec<KeyCode>,
+    /// Keys moving the selection down.
+    pub down: Vec<KeyCode>,
+    /// Keys moving the selection left.
+    pub left: Vec<KeyCode>,
+    /// Keys moving the selection right.
+    pub right: Vec<KeyCode>,
+    /// Keys confirming the selection.
+    pub confirm: Vec<KeyCode>,
+    /// Keys starting a new game with the same mode.
+    pub reset: Vec<KeyCode>,
+    /// Keys exiting the application.
+    pub quit: Vec<KeyCode>,
+    /// Keys returning to the main menu.
+    pub menu: Vec<KeyCode>,
+    /// Keys going back or cancelling.
+    pub back: Vec<KeyCode>,
+}
+
+impl Default for KeyBindings {
+    /// Arrow keys and Vim-style `hjkl`, as listed by [`key_command`].
+    fn default() -> Self {
+        Self {
+            up: vec![KeyCode::Up, KeyCode::Char('k')],
+            down: vec![KeyCode::Down, KeyCode::Char('j')],
+            left: vec![KeyCode::Left, KeyCode::Char('h')],
+            right: vec![KeyCode::Right, KeyCode::Char('l')],
+            confirm: vec![KeyCode::Enter, KeyCode::Char(' ')],
+            reset: vec![KeyCode::Char('r'), KeyCode::Char('R')],
+            quit: vec![KeyCode::Char('q'), KeyCode::Char('Q')],
+            menu: vec![KeyCode::Char('m'), KeyCode::Char('M')],
+            back: vec![KeyCode::Esc],
+        }
+    }
+}
+
+/// Key binding overrides as written in the TOML file, one optional key list per command.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct KeyOverrides {
+    up: Option<Vec<String>>,
+    down: Option<Vec<String>>,
+    left: Option<Vec<String>>,
+    right: Option<Vec<String>>,
+    confirm: Option<Vec<String>>,
+    reset: Option<Vec<String>>,
+    quit: Option<Vec<String>>,
+    menu: Option<Vec<String>>,
+    back: Option<Vec<String>>,
+    letter_movement: Option<bool>,
+}
+
+impl KeyBindings {
+    /// Reads key binding overrides from TOML.
+    ///
+    /// Each command listed replaces all of its default keys, for example
+    /// `up = ["w", "Up"]`. Keys are single characters or one of `Space`,
+    /// `Enter`, `Esc`, `Backspace`, `Tab`, `Up`, `Down`, `Left`, `Right` and
+    /// `F1` to `F12`. Setting `letter_movement = false` leaves only the
+    /// non-letter movement keys, see [`KeyBindings::disable_letter_movement`].
+    ///
+    /// # Errors
+    /// Returns a [`KeyBindingsError`] if the TOML is malformed, names an unknown
+    /// command, or lists an unknown key.
+    pub fn from_toml(toml: &str) -> Result<Self, KeyBindingsError> {
+        let overrides: KeyOverrides =
+            toml::from_str(toml).map_err(|error| KeyBindingsError::Syntax(error.to_string()))?;
+        let mut bindings = Self::default();
+        for (keys, names) in [
+            (&mut bindings.up, overrides.up),
+            (&mut bindings.down, overrides.down),
+            (&mut bindings.left, overrides.left),
+            (&mut bindings.right, overrides.right),
+            (&mut bindings.confirm, overrides.confirm),
+            (&mut bindings.reset, overrides.reset),
+            (&mut bindings.quit, overrides.quit),
+            (&mut bindings.menu, overrides.menu),
+            (&mut bindings.back, overrides.back),
+        ] {
+            if let Some(names) = names {
+                *keys = names
+                    .iter()
+                    .map(|name| parse_key(name))
+                    .collect::<Result<_, _>>()?;
+            }
+        }
+        if overrides.letter_movement == Some(false) {
+            bindings.disable_letter_movement();
+        }
+        Ok(bindings)
+    }
+
+    /// Unbinds the character keys moving the selection, such as the Vim-style
+    /// `hjkl`, so only the arrow keys navigate and the letters are free.
+    pub fn disable_letter_movement(&mut self) {
+        for keys in [
+            &mut self.up,
+            &mut self.down,
+            &mut self.left,
+            &mut self.right,
+        ] {
+            keys.retain(|code| !matches!(code, KeyCode::Char(_)));
+        }
+    }
+
+    /// Returns the remappable command bound to `code`, if any.
+    fn command(&self, code: KeyCode) -> Option<Command> {
+        [
+            (&self.up, Command::Up),
+            (&self.down, Command::Down),
+            (&self.left, Command::Left),
+            (&self.right, Command::Right),
+            (&self.confirm, Command::Enter),
+            (&self.reset, Command::Reset),
+            (&self.quit, Command::Quit),
+            (&self.menu, Command::MainMenu),
+            (&self.back, Command::Esc),
+        ]
+        .into_iter()
+        .find(|(keys, _)| keys.contains(&code))
+        .map(|(_, command)| command)
+    }
+}
+
+/// Returns whether `command` is bound through [`KeyBindings`] rather than a fixed key.
+fn is_remappable(command: Command) -> bool {
+    matches!(
+        command,
+        Command::Up
+            | Command::Down
+            | Command::Left
+            | Command::Right
+            | Command::Enter
+            | Command::Reset
+            | Command::Quit
+            | Command::MainMenu
+            | Command::Esc
+    )
+}
+
+/// Maps a key press to the command bound to it.
+///
+/// Keys held with Ctrl keep their fixed meaning. Otherwise `bindings` take
+/// precedence over the fixed keys of the other commands, so a remapped key
+/// such as `s` for down shadows its default command.
+///
+/// # Arguments
+/// * `key` - The key pressed
+/// * `bindings` - Keys of the remappable commands
+///
+/// # Returns
+/// The command, or None if the key is not bound.
+pub fn resolve_action(key: KeyEvent, bindings: &KeyBindings) -> Option<Command> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return key_command(key);
+    }
+    bindings
+        .command(key.code)
+        .or_else(|| key_command(key).filter(|command| !is_remappable(*command)))
+}
+
+/// Reads the key bindings, falling back to the defaults if `path` does not exist.
+///
+/// # Errors
+/// Returns a [`KeyBindingsError`] if the file exists but cannot be read or parsed.
+pub fn load_key_bindings(path: &Path) -> Result<KeyBindings, KeyBindingsError> {
+    match fs::read_to_string(path) {
+        Ok(toml) => KeyBindings::from_toml(&toml),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(KeyBindings::default()),
+        Err(error) => Err(KeyBindingsError::Io(error.to_string())),
+    }
+}
+
+/// Parses a key name of the key bindings file.
+fn parse_key(name: &str) -> Result<KeyCode, KeyBindingsError> {
+    let mut characters = name.chars();
+    if let (Some(character), None) = (characters.next(), characters.next()) {
+        return Ok(KeyCode::Char(character));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "backspace" => KeyCode::Backspace,
+        "tab" => KeyCode::Tab,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        lower => match lower
+            .strip_prefix('f')
+            .and_then(|number| number.parse::<u8>().ok())
+        {
+            Some(number @ 1..=12) => KeyCode::F(number),
+            _ => return Err(KeyBindingsError::UnknownKey(name.to_string())),
+        },
+    };
+    Ok(code)
+}
+
+/// Failure encountered while loading the key bindings file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyBindingsError {
+    /// The key bindings file could not be read.
+    Io(String),
+    /// The key bindings file is not valid TOML or names an unknown command.
+    Syntax(String),
+    /// A key name is not recognized.
+    UnknownKey(String),
+}
+
+impl fmt::Display for KeyBindingsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(formatter, "cannot read key bindings: {error}"),
+            Self::Syntax(error) => write!(formatter, "invalid key bindings: {error}"),
+            Self::UnknownKey(name) => write!(formatter, "unknown key '{name}'"),
+        }
+    }
+}
+
+impl std::error::Error for KeyBindingsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_default_bindings_resolve_expected_commands() {
+        let bindings = KeyBindings::default();
+        for (code, command) in [
+            (KeyCode::Up, Command::Up),
+            (KeyCode::Char('k'), Command::Up),
+            (KeyCode::Char('j'), Command::Down),
+            (KeyCode::Char('h'), Command::Left),
+            (KeyCode::Right, Command::Right),
+            (KeyCode::Char(' '), Command::Enter),
+            (KeyCode::Char('R'), Command::Reset),
+            (KeyCode::Char('q'), Command::Quit),
+            (KeyCode::Char('m'), Command::MainMenu),
+            (KeyCode::Esc, Command::Esc),
+            (KeyCode::Char('s'), Command::PlaySecond),
+            (KeyCode::F(1), Command::Help),
+        ] {
+            assert_eq!(resolve_action(key(code), &bindings), Some(command));
+        }
+        assert_eq!(
+            resolve_action(
+                KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+                &bindings
+            ),
+            Some(Command::Redo)
+        );
+        assert_eq!(resolve_action(key(KeyCode::Char('z')), &bindings), None);
+    }
+
+    #[test]
+    fn test_overridden_binding_takes_effect() {
+        let bindings = KeyBindings::from_toml(
+            "up = [\"w\", \"Up\"]\ndown = [\"s\"]\nconfirm = [\"Space\", \"F5\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(bindings.up, vec![KeyCode::Char('w'), KeyCode::Up]);
+        assert_eq!(
+            resolve_action(key(KeyCode::Char('w')), &bindings),
+            Some(Command::Up)
+        );
+        // A remapped key shadows its fixed command, and replaced keys are unbound.
+        assert_eq!(
+            resolve_action(key(KeyCode::Char('s')), &bindings),
+            Some(Command::Down)
+        );
+        assert_eq!(resolve_action(key(KeyCode::Char('k')), &bindings), None);
+        assert_eq!(resolve_action(key(KeyCode::Enter), &bindings), None);
+        assert_eq!(
+            resolve_action(key(KeyCode::F(5)), &bindings),
+            Some(Command::Enter)
+        );
+        assert_eq!(
+            resolve_action(key(KeyCode::Char('q')), &bindings),
+            Some(Command::Quit)
+        );
+    }
+
+    #[test]
+    fn test_disabled_letter_movement_leaves_arrow_keys() {
+        let bindings = KeyBindings::from_toml("letter_movement = false").unwrap();
+
+        assert_eq!(bindings.left, vec![KeyCode::Left]);
+        assert_eq!(resolve_action(key(KeyCode::Char('h')), &bindings), None);
+        assert_eq!(resolve_action(key(KeyCode::Char('k')), &bindings), None);
+        assert_eq!(
+            resolve_action(key(KeyCode::Left), &bindings),
+            Some(Command::Left)
+        );
+        assert_eq!(
+            KeyBindings::from_toml("letter_movement = true"),
+            Ok(KeyBindings::default())
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_errors() {
+        assert!(matches!(
+            KeyBindings::from_toml("jump = [\"x\"]"),
+            Err(KeyBindingsError::Syntax(_))
+        ));
+        assert_eq!(
+            KeyBindings::from_toml("up = [\"PageUp\"]"),
+            Err(KeyBindingsError::UnknownKey("PageUp".to_string()))
+        );
+        assert_eq!(
+            load_key_bindings(Path::new("/nonexistent/tic-tac-foe/keys.toml")),
+            Ok(KeyBindings::default())
+        );
+    }
+}
@@ -8,12 +8,36 @@ extern crate core;
 pub mod ai;
 /// Top-level application state and input handling.
 pub mod app;
+/// Round-robin tournaments between the AI engines.
+pub mod arena;
+/// Line-based move input for playing without the terminal UI.
+pub mod cli;
+/// Contextual tips for beginners based on board patterns.
+pub mod coach;
+/// Headless game driver for integration tests and scripts.
+pub mod driver;
 /// Classic and Ultimate tic-tac-toe board models.
 pub mod game;
+/// Remappable key bindings and their optional config file.
+pub mod keymap;
+/// Classic games against a remote player over plain TCP.
+pub mod net;
 /// Peer-to-peer networking and wire protocol support.
 pub mod network;
+/// Classic puzzles with a unique best move, used by the Solitaire mode.
+pub mod puzzle;
+/// The application loop and the sources of its input events.
+pub mod runner;
+/// Saving and resuming games in progress.
+pub mod save;
 /// Menu and gameplay scene state.
 pub mod scenes;
+/// Self-play data export for machine-learning experiments.
+pub mod selfplay;
+/// Session statistics aggregated from finished games.
+pub mod stats;
+/// Color themes of the terminal interface.
+pub mod theme;
 /// Terminal user-interface rendering.
 pub mod ui;
 /// Shared board-selection utilities.
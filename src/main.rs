@@ -1,21 +1,50 @@
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use ratatui::Terminal;
 use std::time::Duration;
-use tic_tac_foe::app::App;
-use tic_tac_foe::ui;
-
-const TICK_RATE: Duration = Duration::from_millis(50);
+use tic_tac_foe::app::{App, IdleAction, IdleTimeout};
+use tic_tac_foe::game::Mark;
+use tic_tac_foe::net::{self, RemoteLink};
+use tic_tac_foe::runner::{TerminalInput, run_app};
+use tic_tac_foe::{keymap, save};
 
 /// Entry point for the Tic-Tac-Toe TUI application.
 ///
 /// Initializes the terminal, runs the main event loop, and properly
 /// restores the terminal state on exit.
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let options = parse_options(std::env::args().skip(1))?;
+    let mut key_bindings = match keymap::default_key_bindings_path() {
+        Some(path) => keymap::load_key_bindings(&path)?,
+        None => keymap::KeyBindings::default(),
+    };
+    if options.arrows_only {
+        key_bindings.disable_letter_movement();
+    }
+    // The connection is opened before the terminal is taken over, so the
+    // wait for the other player can be reported and interrupted.
+    let remote = match (&options.tcp_host, &options.tcp_join) {
+        (Some(address), _) => {
+            eprintln!("Waiting for a player on {address}...");
+            Some((net::listen(address.as_str())?, Mark::X))
+        }
+        (None, Some(address)) => Some((net::connect(address.as_str())?, Mark::O)),
+        (None, None) => None,
+    };
+
     let mut terminal = ratatui::init();
 
     let mut app = App::new();
+    app.idle_timeout = options.idle_timeout;
+    app.auto_draw = options.auto_draw;
+    app.move_time = options.move_time;
+    app.game_time = options.game_time;
+    app.save_path = save::default_save_path();
+    app.game_log_path = save::default_game_log_path();
+    app.key_bindings = key_bindings;
+    app.go_to_main_menu();
+    if let Some((peer, mark)) = remote {
+        app.start_remote_game(RemoteLink::start(peer), mark);
+    }
 
-    let result = run_app(&mut terminal, &mut app);
+    let result = run_app(&mut terminal, &mut app, &mut TerminalInput);
 
     ratatui::restore();
 
@@ -26,78 +55,85 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-/// Main application loop that handles rendering and input events.
-///
-/// Continuously draws the UI and processes keyboard input until the
-/// user quits the application.
+/// Settings read from the command-line arguments.
+#[derive(Default)]
+struct Options {
+    idle_timeout: Option<IdleTimeout>,
+    auto_draw: bool,
+    move_time: Option<Duration>,
+    game_time: Option<Duration>,
+    arrows_only: bool,
+    tcp_host: Option<String>,
+    tcp_join: Option<String>,
+}
+
+/// Reads the settings from the command-line arguments.
 ///
-/// # Arguments
-/// * `terminal` - The terminal backend to render to
-/// * `app` - The application state
+/// `--idle-quit <SECONDS>` exits after that many seconds without input, while
+/// `--idle-menu <SECONDS>` returns to the main menu instead. `--auto-draw`
+/// ends classic games as soon as neither player can win, and
+/// `--move-time <SECONDS>` shows a countdown for each move. `--game-time
+/// <SECONDS>` gives each player of a classic game that much time in total,
+/// losing the game once it runs out. `--arrows-only` unbinds the `hjkl`
+/// movement keys. `--tcp-host <ADDRESS>` waits on that address for a friend
+/// to play a classic game over TCP as X, while `--tcp-join <ADDRESS>` joins
+/// them as O.
 ///
 /// # Returns
-/// Result indicating success or any error encountered
-fn run_app<B: ratatui::backend::Backend>(
-    terminal: &mut Terminal<B>,
-    app: &mut App,
-) -> Result<(), Box<dyn std::error::Error>>
-where
-    B::Error: 'static,
-{
-    loop {
-        app.poll_network_events();
-        terminal.draw(|f| ui::render(f, app))?;
-
-        if event::poll(TICK_RATE)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char(character)
-                            if app.handle_text_input(&character.to_string()) => {}
-                        KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            app.quit();
-                        }
-                        KeyCode::Char('r') | KeyCode::Char('R') => {
-                            app.handle_reset();
-                        }
-                        KeyCode::Char('m') | KeyCode::Char('M') => {
-                            app.handle_main_menu();
-                        }
-                        KeyCode::Char('s') | KeyCode::Char('S') => {
-                            app.handle_second();
-                        }
-                        KeyCode::Char('c') | KeyCode::Char('C') => {
-                            app.handle_concede();
-                        }
-                        KeyCode::Left | KeyCode::Char('h') => {
-                            app.handle_left();
-                        }
-                        KeyCode::Right | KeyCode::Char('l') => {
-                            app.handle_right();
-                        }
-                        KeyCode::Up | KeyCode::Char('k') => {
-                            app.handle_up();
-                        }
-                        KeyCode::Down | KeyCode::Char('j') => {
-                            app.handle_down();
-                        }
-                        KeyCode::Enter | KeyCode::Char(' ') => {
-                            app.handle_enter();
-                        }
-                        KeyCode::Esc => {
-                            app.handle_esc();
-                        }
-                        KeyCode::Backspace => {
-                            app.handle_backspace();
-                        }
-                        _ => {}
-                    }
-                }
+/// The parsed settings, or an error message for an invalid argument.
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        if arg == "--auto-draw" {
+            options.auto_draw = true;
+            continue;
+        }
+        if arg == "--arrows-only" {
+            options.arrows_only = true;
+            continue;
+        }
+        if matches!(arg.as_str(), "--tcp-host" | "--tcp-join") {
+            let address = args
+                .next()
+                .ok_or_else(|| format!("{arg} expects an address such as 0.0.0.0:7878"))?;
+            if options.tcp_host.is_some() || options.tcp_join.is_some() {
+                return Err("use only one of --tcp-host and --tcp-join".to_string());
+            }
+            if arg == "--tcp-host" {
+                options.tcp_host = Some(address);
+            } else {
+                options.tcp_join = Some(address);
             }
+            continue;
         }
-
-        if app.should_quit {
-            return Ok(());
+        if !matches!(
+            arg.as_str(),
+            "--idle-quit" | "--idle-menu" | "--move-time" | "--game-time"
+        ) {
+            return Err(format!("unknown argument '{arg}'"));
+        }
+        let seconds = args
+            .next()
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|seconds| *seconds > 0)
+            .ok_or_else(|| format!("{arg} expects a positive number of seconds"))?;
+        let duration = Duration::from_secs(seconds);
+        match arg.as_str() {
+            "--idle-quit" => {
+                options.idle_timeout = Some(IdleTimeout {
+                    after: duration,
+                    action: IdleAction::Quit,
+                })
+            }
+            "--idle-menu" => {
+                options.idle_timeout = Some(IdleTimeout {
+                    after: duration,
+                    action: IdleAction::MainMenu,
+                })
+            }
+            "--move-time" => options.move_time = Some(duration),
+            _ => options.game_time = Some(duration),
         }
     }
+    Ok(options)
 }
@@ -0,0 +1,446 @@
+//! Classic games against a remote player over a plain TCP connection.
+//!
+//! The protocol is line based. Both ends first send [`GREETING`], then each
+//! move is sent as a one-move transcript such as `X:b2`, written by
+//! [`write_transcript`]. The host plays X and the joining player plays O.
+//!
+//! The TUI plays such games through a [`RemoteLink`], which reads the remote
+//! moves on a thread so the render loop never waits for them.
+
+use crate::ai::Move;
+use crate::game::notation::{GameRecord, write_transcript};
+use crate::game::{GameState, GameVariant, Mark};
+use crate::scenes::{GameMode, GamePlayTTT};
+use crate::utils::Position;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// First line sent by both ends, naming the game and the protocol version.
+pub const GREETING: &str = "tic-tac-foe classic 1";
+/// Longest line, in bytes, accepted from the remote player.
+const MAX_LINE_LEN: usize = 64;
+
+/// Failure of a remote game.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NetError {
+    /// The connection could not be opened, read or written.
+    Io(String),
+    /// The remote player closed the connection.
+    Disconnected,
+    /// The remote player sent a line that is not a valid message or move.
+    Protocol(String),
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(formatter, "network error: {error}"),
+            Self::Disconnected => write!(formatter, "the opponent disconnected"),
+            Self::Protocol(reason) => write!(formatter, "invalid message from opponent: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<io::Error> for NetError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+/// One end of a connection, reading lines from `reader` and writing to `writer`.
+pub struct RemotePeer<R, W> {
+    reader: R,
+    writer: W,
+}
+
+/// A [`RemotePeer`] over a TCP stream.
+pub type TcpPeer = RemotePeer<BufReader<TcpStream>, TcpStream>;
+
+impl<R: BufRead, W: Write> RemotePeer<R, W> {
+    /// Wraps the two halves of a connection without sending anything.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Sends [`GREETING`] and checks that the remote end answers with it.
+    ///
+    /// # Errors
+    /// Returns a [`NetError`] if the connection fails or the remote end is
+    /// not a compatible game.
+    pub fn handshake(&mut self) -> Result<(), NetError> {
+        self.send_line(GREETING)?;
+        let line = self.read_line()?;
+        if line != GREETING {
+            return Err(NetError::Protocol(format!("unexpected greeting '{line}'")));
+        }
+        Ok(())
+    }
+
+    /// Sends the move `mark` played at (`row`, `col`).
+    ///
+    /// # Errors
+    /// Returns a [`NetError`] if the line cannot be written.
+    pub fn send_move(&mut self, mark: Mark, row: usize, col: usize) -> Result<(), NetError> {
+        self.send_line(&write_transcript(mark, &[Move::Base(row, col)]))
+    }
+
+    /// Waits for the next move of the remote player.
+    ///
+    /// # Returns
+    /// The mark and the (row, col) of the move.
+    ///
+    /// # Errors
+    /// Returns [`NetError::Disconnected`] once the remote end closes the
+    /// connection, or another [`NetError`] if the line is not a single move.
+    pub fn receive_move(&mut self) -> Result<(Mark, usize, usize), NetError> {
+        let line = self.read_line()?;
+        let record = GameRecord::from_transcript(GameVariant::Classic, &line)
+            .map_err(|error| NetError::Protocol(error.to_string()))?;
+        match record.moves.as_slice() {
+            [Move::Base(row, col)] => Ok((record.first_player, *row, *col)),
+            _ => Err(NetError::Protocol(format!(
+                "expected one move, got '{line}'"
+            ))),
+        }
+    }
+
+    fn send_line(&mut self, line: &str) -> Result<(), NetError> {
+        writeln!(self.writer, "{line}")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads the next line, buffering at most one byte more than
+    /// [`MAX_LINE_LEN`] so a peer cannot exhaust memory.
+    fn read_line(&mut self) -> Result<String, NetError> {
+        let mut line = Vec::new();
+        let read = self
+            .reader
+            .by_ref()
+            .take(MAX_LINE_LEN as u64 + 1)
+            .read_until(b'\n', &mut line)?;
+        if read == 0 {
+            return Err(NetError::Disconnected);
+        }
+        if line.len() > MAX_LINE_LEN {
+            return Err(NetError::Protocol(format!(
+                "line longer than {MAX_LINE_LEN} bytes"
+            )));
+        }
+        let line = String::from_utf8(line)
+            .map_err(|_| NetError::Protocol("line is not valid text".to_string()))?;
+        Ok(line.trim().to_string())
+    }
+}
+
+/// Waits on `address` for one player to join, then greets them.
+///
+/// # Errors
+/// Returns a [`NetError`] if the address cannot be bound or the handshake fails.
+pub fn listen(address: impl ToSocketAddrs) -> Result<TcpPeer, NetError> {
+    let listener = TcpListener::bind(address)?;
+    let (stream, _) = listener.accept()?;
+    tcp_peer(stream)
+}
+
+/// Joins the player hosting on `address`, then greets them.
+///
+/// # Errors
+/// Returns a [`NetError`] if the connection or the handshake fails.
+pub fn connect(address: impl ToSocketAddrs) -> Result<TcpPeer, NetError> {
+    tcp_peer(TcpStream::connect(address)?)
+}
+
+fn tcp_peer(stream: TcpStream) -> Result<TcpPeer, NetError> {
+    let mut peer = RemotePeer::new(BufReader::new(stream.try_clone()?), stream);
+    peer.handshake()?;
+    Ok(peer)
+}
+
+/// A move received from the remote player: its mark, row and column.
+pub type RemoteMove = (Mark, usize, usize);
+
+/// A connection whose remote moves are read on a background thread.
+///
+/// The application sends its moves with [`RemoteLink::send_move`] and polls
+/// the remote ones with [`RemoteLink::try_receive`] without blocking.
+pub struct RemoteLink {
+    sender: RemotePeer<io::Empty, Box<dyn Write + Send>>,
+    moves: Receiver<Result<RemoteMove, NetError>>,
+}
+
+impl RemoteLink {
+    /// Starts reading the moves of `peer` on a new thread.
+    ///
+    /// The thread stops after the first error, such as a disconnect, which
+    /// is then returned by [`RemoteLink::try_receive`].
+    pub fn start<R, W>(peer: RemotePeer<R, W>) -> Self
+    where
+        R: BufRead + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let RemotePeer { reader, writer } = peer;
+        let (move_sender, moves) = mpsc::channel();
+        thread::spawn(move || {
+            let mut receiver = RemotePeer::new(reader, io::sink());
+            loop {
+                let received = receiver.receive_move();
+                let failed = received.is_err();
+                if move_sender.send(received).is_err() || failed {
+                    return;
+                }
+            }
+        });
+        Self {
+            sender: RemotePeer::new(io::empty(), Box::new(writer)),
+            moves,
+        }
+    }
+
+    /// Sends the move `mark` played at (`row`, `col`).
+    ///
+    /// # Errors
+    /// Returns a [`NetError`] if the line cannot be written.
+    pub fn send_move(&mut self, mark: Mark, row: usize, col: usize) -> Result<(), NetError> {
+        self.sender.send_move(mark, row, col)
+    }
+
+    /// Returns the next move received from the remote player, if any.
+    ///
+    /// # Returns
+    /// None if no move is waiting, or the error that stopped the reading
+    /// thread, which is [`NetError::Disconnected`] if it ended unexpectedly.
+    pub fn try_receive(&self) -> Option<Result<RemoteMove, NetError>> {
+        match self.moves.try_recv() {
+            Ok(received) => Some(received),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(NetError::Disconnected)),
+        }
+    }
+}
+
+/// A classic game between a local player and a [`RemotePeer`].
+///
+/// The game runs in [`GameMode::OnlinePvP`], which already refuses local
+/// moves on the remote player's turn.
+pub struct RemoteGame<R, W> {
+    /// The game, as seen by the local player.
+    pub game: GamePlayTTT,
+    peer: RemotePeer<R, W>,
+}
+
+impl<R: BufRead, W: Write> RemoteGame<R, W> {
+    /// Starts a game in which the local player plays `local_mark`.
+    pub fn new(peer: RemotePeer<R, W>, local_mark: Mark) -> Self {
+        Self {
+            game: GamePlayTTT::new(GameMode::OnlinePvP(local_mark)),
+            peer,
+        }
+    }
+
+    /// Returns whether the game waits for a local move.
+    pub fn is_local_turn(&self) -> bool {
+        self.game.board.state == GameState::Playing
+            && self.game.mode == GameMode::OnlinePvP(self.game.active_player)
+    }
+
+    /// Plays the local move at (`row`, `col`) and sends it to the remote player.
+    ///
+    /// # Returns
+    /// True if the move was played, false if it is not the local player's
+    /// turn or the cell cannot be played.
+    ///
+    /// # Errors
+    /// Returns a [`NetError`] if the move cannot be sent.
+    pub fn play_local(&mut self, row: usize, col: usize) -> Result<bool, NetError> {
+        let mark = self.game.active_player;
+        self.game.selected = Position { row, col };
+        if !self.game.play_move() {
+            return Ok(false);
+        }
+        self.peer.send_move(mark, row, col)?;
+        Ok(true)
+    }
+
+    /// Waits for the remote player's move and plays it.
+    ///
+    /// # Errors
+    /// Returns a [`NetError`] if the connection fails, or
+    /// [`NetError::Protocol`] if the move is played out of turn or on an
+    /// unplayable cell.
+    pub fn receive(&mut self) -> Result<(), NetError> {
+        let (mark, row, col) = self.peer.receive_move()?;
+        if mark != self.game.active_player || !self.game.play_remote_move(row, col) {
+            return Err(NetError::Protocol(format!(
+                "illegal move {}",
+                write_transcript(mark, &[Move::Base(row, col)])
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::base::SmallBoard;
+    use std::sync::mpsc::{Receiver, Sender, channel};
+    use std::thread;
+
+    /// Receiving half of an in-memory pipe.
+    struct PipeReader {
+        receiver: Receiver<Vec<u8>>,
+        pending: Vec<u8>,
+    }
+
+    impl Read for PipeReader {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() {
+                match self.receiver.recv() {
+                    Ok(bytes) => self.pending = bytes,
+                    Err(_) => return Ok(0),
+                }
+            }
+            let length = buffer.len().min(self.pending.len());
+            buffer[..length].copy_from_slice(&self.pending[..length]);
+            self.pending.drain(..length);
+            Ok(length)
+        }
+    }
+
+    /// Sending half of an in-memory pipe.
+    struct PipeWriter(Sender<Vec<u8>>);
+
+    impl Write for PipeWriter {
+        fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+            self.0
+                .send(buffer.to_vec())
+                .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+            Ok(buffer.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    type PipePeer = RemotePeer<BufReader<PipeReader>, PipeWriter>;
+
+    /// Returns the two ends of an in-memory duplex connection.
+    fn duplex() -> (PipePeer, PipePeer) {
+        let (host_sender, guest_receiver) = channel();
+        let (guest_sender, host_receiver) = channel();
+        let end = |receiver, sender| {
+            RemotePeer::new(
+                BufReader::new(PipeReader {
+                    receiver,
+                    pending: Vec::new(),
+                }),
+                PipeWriter(sender),
+            )
+        };
+        (
+            end(host_receiver, host_sender),
+            end(guest_receiver, guest_sender),
+        )
+    }
+
+    /// Plays `moves` alternately from X, sending the local ones and waiting
+    /// for the others, and returns the final board.
+    fn play_side(mut peer: PipePeer, mark: Mark, moves: &[(usize, usize)]) -> SmallBoard {
+        peer.handshake().unwrap();
+        let mut game = RemoteGame::new(peer, mark);
+        for &(row, col) in moves {
+            if game.is_local_turn() {
+                assert!(game.play_local(row, col).unwrap());
+            } else {
+                game.receive().unwrap();
+            }
+        }
+        game.game.board
+    }
+
+    #[test]
+    fn test_both_ends_converge_to_the_same_board() {
+        let (host, guest) = duplex();
+        let moves = [(1, 1), (0, 0), (0, 2), (2, 0), (1, 0), (1, 2), (2, 1)];
+
+        let guest = thread::spawn(move || play_side(guest, Mark::O, &moves));
+        let host_board = play_side(host, Mark::X, &moves);
+        let guest_board = guest.join().unwrap();
+
+        assert_eq!(host_board, guest_board);
+        assert_eq!(host_board.to_str_grid(), "O.X|XXO|OX.");
+        assert_eq!(host_board.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_moves_are_sent_as_transcript_lines() {
+        let (mut host, mut guest) = duplex();
+        host.send_move(Mark::X, 1, 2).unwrap();
+        assert_eq!(guest.read_line().unwrap(), "X:c2");
+
+        guest.send_line("X:b2 O:a1").unwrap();
+        assert!(matches!(host.receive_move(), Err(NetError::Protocol(_))));
+        guest.send_line("hello").unwrap();
+        assert!(matches!(host.receive_move(), Err(NetError::Protocol(_))));
+    }
+
+    #[test]
+    fn test_out_of_turn_moves_and_disconnects_are_errors() {
+        let (host, mut guest) = duplex();
+        let mut game = RemoteGame::new(host, Mark::X);
+        // The guest moves for X, who is on turn at the host.
+        guest.send_move(Mark::X, 0, 0).unwrap();
+        assert!(matches!(game.receive(), Err(NetError::Protocol(_))));
+        assert_eq!(game.game.board, SmallBoard::new());
+        assert!(game.play_local(0, 0).unwrap());
+        assert!(!game.play_local(1, 1).unwrap());
+
+        drop(guest);
+        assert_eq!(game.receive(), Err(NetError::Disconnected));
+    }
+
+    /// Polls `link` until a move or an error arrives.
+    fn wait_for(link: &RemoteLink) -> Result<RemoteMove, NetError> {
+        for _ in 0..500 {
+            if let Some(received) = link.try_receive() {
+                return received;
+            }
+            thread::sleep(std::time::Duration::from_millis(2));
+        }
+        panic!("nothing received from the remote player");
+    }
+
+    #[test]
+    fn test_link_reads_moves_without_blocking_and_reports_disconnects() {
+        let (host, mut guest) = duplex();
+        let mut link = RemoteLink::start(host);
+        assert!(link.try_receive().is_none());
+
+        link.send_move(Mark::X, 1, 1).unwrap();
+        assert_eq!(guest.receive_move(), Ok((Mark::X, 1, 1)));
+        guest.send_move(Mark::O, 0, 2).unwrap();
+        assert_eq!(wait_for(&link), Ok((Mark::O, 0, 2)));
+
+        drop(guest);
+        assert_eq!(wait_for(&link), Err(NetError::Disconnected));
+    }
+
+    #[test]
+    fn test_oversized_line_is_a_protocol_error_that_closes_the_link() {
+        let (host, mut guest) = duplex();
+        let link = RemoteLink::start(host);
+
+        // A line with no end in sight, as a hostile peer might send.
+        guest.writer.write_all(&[b'x'; MAX_LINE_LEN * 100]).unwrap();
+        assert!(matches!(wait_for(&link), Err(NetError::Protocol(_))));
+        // The reading thread stopped, so the link reports it as closed.
+        assert_eq!(wait_for(&link), Err(NetError::Disconnected));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::ai::rng::with_rng;
+use crate::ai::solver::move_value;
+use crate::ai::{Game, Move};
+use crate::game::base::SmallBoard;
+use crate::game::{GameState, Mark};
+use rand::prelude::*;
+
+/// Outcome that the solution of a puzzle secures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PuzzleGoal {
+    /// The solution forces a win.
+    Win,
+    /// The solution is the only move that avoids a loss.
+    Draw,
+}
+
+/// A classic position in which exactly one move secures the best outcome.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Puzzle {
+    /// Position to solve.
+    pub board: SmallBoard,
+    /// Mark to move, always X.
+    pub mark: Mark,
+    /// The only move reaching the goal.
+    pub solution: Move,
+    /// Outcome secured by the solution.
+    pub goal: PuzzleGoal,
+}
+
+impl Puzzle {
+    /// Builds a puzzle from a position, if it has a unique best move.
+    ///
+    /// # Returns
+    /// The puzzle, or None if the game is over, `mark` cannot avoid losing,
+    /// or several moves share the best outcome.
+    pub fn from_position(board: SmallBoard, mark: Mark) -> Option<Self> {
+        if board.state != GameState::Playing {
+            return None;
+        }
+        let outcomes: Vec<(Move, i8)> = board
+            .get_possible_moves()
+            .into_iter()
+            .map(|mv| (mv, move_value(&board, mark, &mv).signum()))
+            .collect();
+        let best = outcomes.iter().map(|(_, outcome)| *outcome).max()?;
+        let mut best_moves = outcomes.iter().filter(|(_, outcome)| *outcome == best);
+        let (solution, _) = *best_moves.next()?;
+        if best < 0 || best_moves.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            board,
+            mark,
+            solution,
+            goal: if best > 0 {
+                PuzzleGoal::Win
+            } else {
+                PuzzleGoal::Draw
+            },
+        })
+    }
+
+    /// Returns the instruction displayed with the puzzle.
+    pub fn prompt(&self) -> String {
+        match self.goal {
+            PuzzleGoal::Win => format!("{} to move and win", self.mark),
+            PuzzleGoal::Draw => format!("{} to move and draw", self.mark),
+        }
+    }
+}
+
+/// Generates a random puzzle with X to move.
+///
+/// Random games of two, four or six moves are played until one of them
+/// reaches a position with a unique best move, so the generated puzzle
+/// always has a solver-verified solution.
+pub fn generate_puzzle() -> Puzzle {
+    loop {
+        let moves = with_rng(|rng| rng.random_range(1..=3)) * 2;
+        let mut board = SmallBoard::new();
+        let mut mark = Mark::X;
+        for _ in 0..moves {
+            let possible_moves = board.get_possible_moves();
+            let Some(mv) = with_rng(|rng| possible_moves.choose(rng).copied()) else {
+                break;
+            };
+            board.play(&mv, mark);
+            mark = mark.switch();
+        }
+        if let Some(puzzle) = Puzzle::from_position(board, Mark::X) {
+            return puzzle;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::rng::seed_rng;
+    use crate::ai::solver::perfect_move;
+
+    #[test]
+    fn test_generated_puzzles_have_verified_unique_solution() {
+        seed_rng(7);
+        for _ in 0..20 {
+            let puzzle = generate_puzzle();
+            assert_eq!(puzzle.mark, Mark::X);
+            assert_eq!(puzzle.board.state, GameState::Playing);
+            assert_eq!(perfect_move(&puzzle.board, Mark::X), puzzle.solution);
+
+            let solution_value = move_value(&puzzle.board, Mark::X, &puzzle.solution);
+            match puzzle.goal {
+                PuzzleGoal::Win => assert!(solution_value > 0),
+                PuzzleGoal::Draw => assert_eq!(solution_value, 0),
+            }
+            for mv in puzzle.board.get_possible_moves() {
+                if mv != puzzle.solution {
+                    assert!(
+                        move_value(&puzzle.board, Mark::X, &mv).signum() < solution_value.signum()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_position_requires_unique_best_move() {
+        assert_eq!(Puzzle::from_position(SmallBoard::new(), Mark::X), None);
+
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        board.set(0, 1, Some(Mark::X));
+        board.set(1, 0, Some(Mark::O));
+        board.set(1, 1, Some(Mark::O));
+        let puzzle = Puzzle::from_position(board, Mark::X).unwrap();
+        assert_eq!(puzzle.solution, Move::Base(0, 2));
+        assert_eq!(puzzle.goal, PuzzleGoal::Win);
+        assert_eq!(puzzle.prompt(), "X to move and win");
+    }
+}
@@ -0,0 +1,112 @@
+use crate::app::App;
+use crate::ui;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Terminal;
+use ratatui::backend::Backend;
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Longest wait for input before the screen is redrawn.
+pub const TICK_RATE: Duration = Duration::from_millis(50);
+
+/// Source of the terminal events read by [`run_app`].
+pub trait InputSource {
+    /// Waits up to `timeout` for the next event.
+    ///
+    /// # Returns
+    /// The event, or None if none arrived in time.
+    ///
+    /// # Errors
+    /// Returns an error if no more events can be read.
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>>;
+}
+
+/// Events typed in the real terminal, read through crossterm.
+pub struct TerminalInput;
+
+impl InputSource for TerminalInput {
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        if event::poll(timeout)? {
+            Ok(Some(event::read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// A fixed sequence of events, for driving the application without a terminal.
+pub struct ScriptedInput {
+    events: VecDeque<Event>,
+}
+
+impl ScriptedInput {
+    /// Creates a script that yields `events` in order, without waiting.
+    pub fn new(events: impl IntoIterator<Item = Event>) -> Self {
+        Self {
+            events: events.into_iter().collect(),
+        }
+    }
+
+    /// Creates a script of key presses without modifiers.
+    pub fn from_keys(codes: impl IntoIterator<Item = KeyCode>) -> Self {
+        Self::new(
+            codes
+                .into_iter()
+                .map(|code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE))),
+        )
+    }
+}
+
+impl InputSource for ScriptedInput {
+    /// Yields the next scripted event right away.
+    ///
+    /// # Errors
+    /// Returns [`io::ErrorKind::UnexpectedEof`] once the script is exhausted,
+    /// so a script that never quits ends the loop instead of hanging it.
+    fn next_event(&mut self, _timeout: Duration) -> io::Result<Option<Event>> {
+        match self.events.pop_front() {
+            Some(event) => Ok(Some(event)),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the input script ended before the application quit",
+            )),
+        }
+    }
+}
+
+/// Main application loop that handles rendering and input events.
+///
+/// Continuously draws the UI and processes the events of `input` until the
+/// user quits the application. Input is polled with a short timeout, so the
+/// screen is redrawn right after any event, including a terminal resize.
+///
+/// # Arguments
+/// * `terminal` - The terminal backend to render to
+/// * `app` - The application state
+/// * `input` - Source of the key presses and other terminal events
+///
+/// # Errors
+/// Returns an error if drawing fails or the input can no longer be read.
+pub fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    input: &mut impl InputSource,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    B::Error: 'static,
+{
+    loop {
+        app.poll_network_events();
+        app.handle_tick();
+        terminal.draw(|f| ui::render(f, app))?;
+
+        if let Some(event) = input.next_event(TICK_RATE)? {
+            app.handle_event(event, Instant::now());
+        }
+
+        if app.should_quit {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use crate::ai::AI;
+use crate::ai::imitator::{ImitatorAi, MoveMemory};
+use crate::ai::mcts::MCTSAi;
+use crate::ai::minimax::MinimaxAi;
+use crate::ai::simple::{Difficulty, SimpleAi};
+use crate::game::base::SmallBoard;
+use crate::game::notation::GameRecord;
+use crate::game::ultimate::BigBoard;
+use crate::game::{GameVariant, Mark};
+use crate::scenes::GameMode;
+use crate::utils::Position;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Name of the directory holding the save file inside the config directory.
+pub(crate) const APP_DIR_NAME: &str = "tic-tac-foe";
+/// Name of the save file.
+const SAVE_FILE_NAME: &str = "save.json";
+/// Name of the log of finished games.
+const GAME_LOG_FILE_NAME: &str = "games.log";
+
+/// Returns where the game is saved, inside the platform config directory.
+///
+/// # Returns
+/// The save file path, or None if the platform has no config directory.
+pub fn default_save_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(SAVE_FILE_NAME))
+}
+
+/// Returns where finished games are logged, inside the platform config directory.
+///
+/// # Returns
+/// The game log path, or None if the platform has no config directory.
+pub fn default_game_log_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(GAME_LOG_FILE_NAME))
+}
+
+/// A game in progress written to disk so it can be resumed later.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedGame {
+    /// A classic game.
+    Classic(SavedClassic),
+    /// An Ultimate game.
+    Ultimate(SavedUltimate),
+}
+
+/// Saved state of a classic game.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SavedClassic {
+    /// Board being played.
+    pub board: SmallBoard,
+    /// Mark whose turn is active.
+    pub active_player: Mark,
+    /// Mark that moved first.
+    pub starting_player: Mark,
+    /// Number of moves played.
+    pub turn: u32,
+    /// Players of the game.
+    pub mode: SavedMode,
+    /// Selected cell.
+    pub selected: Position,
+}
+
+/// Saved state of an Ultimate game.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SavedUltimate {
+    /// Board being played, including the board the next move is bound to.
+    pub big_board: BigBoard,
+    /// Mark whose turn is active.
+    pub active_player: Mark,
+    /// Mark that moved first.
+    pub starting_player: Mark,
+    /// Number of moves played.
+    pub turn: u32,
+    /// Players of the game.
+    pub mode: SavedMode,
+    /// Selected small board.
+    pub selected_board: Position,
+    /// Selected cell, or `None` while choosing a small board.
+    pub selected_cell: Option<Position>,
+}
+
+/// Players of a saved game.
+///
+/// Online and Solitaire games cannot be saved.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedMode {
+    /// Two humans sharing the terminal.
+    LocalPvP,
+    /// A human against the AI.
+    PvE(SavedAi),
+    /// Two AIs against each other.
+    EvE(SavedAi, SavedAi),
+}
+
+/// AI player of a saved game, without any search state.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedAi {
+    /// [`AI::Weak`] playing the mark.
+    Weak(Mark),
+    /// [`AI::Medium`] playing the mark at the difficulty.
+    Medium(Mark, Difficulty),
+    /// [`AI::StrongTTT`] or [`AI::StrongUTT`] playing the mark.
+    Strong(Mark),
+    /// [`AI::Imitator`] playing the mark.
+    Imitator(Mark),
+    /// [`AI::Minimax`] playing the mark.
+    Minimax(Mark),
+}
+
+impl SavedMode {
+    /// Describes the players of `mode`.
+    ///
+    /// # Returns
+    /// The saved mode, or None for modes that cannot be saved.
+    pub fn from_mode(mode: &GameMode) -> Option<Self> {
+        match mode {
+            GameMode::LocalPvP => Some(SavedMode::LocalPvP),
+            GameMode::PvE(ai) => Some(SavedMode::PvE(SavedAi::from_ai(ai))),
+            GameMode::EvE(ai_x, ai_o) => Some(SavedMode::EvE(
+                SavedAi::from_ai(ai_x),
+                SavedAi::from_ai(ai_o),
+            )),
+            GameMode::OnlinePvP(_) | GameMode::Solitaire => None,
+        }
+    }
+
+    /// Rebuilds the players of a game of the given variant.
+    ///
+    /// # Arguments
+    /// * `variant` - Rule set of the saved game
+    /// * `imitator_memory` - Moves learned by imitator AIs this session
+    ///
+    /// # Errors
+    /// Returns a [`SaveError`] if an AI cannot play `variant`.
+    pub fn to_mode(
+        self,
+        variant: GameVariant,
+        imitator_memory: &Rc<RefCell<MoveMemory>>,
+    ) -> Result<GameMode, SaveError> {
+        Ok(match self {
+            SavedMode::LocalPvP => GameMode::LocalPvP,
+            SavedMode::PvE(ai) => GameMode::PvE(ai.to_ai(variant, imitator_memory)?),
+            SavedMode::EvE(ai_x, ai_o) => GameMode::EvE(
+                ai_x.to_ai(variant, imitator_memory)?,
+                ai_o.to_ai(variant, imitator_memory)?,
+            ),
+        })
+    }
+}
+
+impl SavedAi {
+    /// Describes `ai`.
+    pub fn from_ai(ai: &AI) -> Self {
+        match ai {
+            AI::Weak(mark) => SavedAi::Weak(*mark),
+            AI::Medium(ai) => SavedAi::Medium(ai.ai_mark, ai.difficulty),
+            AI::StrongTTT(ai) => SavedAi::Strong(ai.ai_mark),
+            AI::StrongUTT(ai) => SavedAi::Strong(ai.ai_mark),
+            AI::Imitator(ai) => SavedAi::Imitator(ai.ai_mark),
+            AI::Minimax(ai) => SavedAi::Minimax(ai.ai_mark),
+        }
+    }
+
+    /// Rebuilds a fresh AI for a game of the given variant.
+    ///
+    /// # Errors
+    /// Returns a [`SaveError`] if an imitator or a minimax AI is saved in an
+    /// Ultimate game.
+    pub fn to_ai(
+        self,
+        variant: GameVariant,
+        imitator_memory: &Rc<RefCell<MoveMemory>>,
+    ) -> Result<AI, SaveError> {
+        Ok(match (self, variant) {
+            (SavedAi::Weak(mark), _) => AI::Weak(mark),
+            (SavedAi::Medium(mark, difficulty), _) => {
+                AI::Medium(SimpleAi::with_difficulty(mark, difficulty))
+            }
+            (SavedAi::Strong(mark), GameVariant::Classic) => {
+                AI::StrongTTT(MCTSAi::new(SmallBoard::new(), mark))
+            }
+            (SavedAi::Strong(mark), GameVariant::Ultimate) => {
+                AI::StrongUTT(MCTSAi::new(BigBoard::new(), mark))
+            }
+            (SavedAi::Imitator(mark), GameVariant::Classic) => {
+                AI::Imitator(ImitatorAi::new(mark, imitator_memory.clone()))
+            }
+            (SavedAi::Imitator(_), GameVariant::Ultimate) => {
+                return Err(SaveError::Invalid(
+                    "the imitator AI cannot play Ultimate".to_string(),
+                ));
+            }
+            (SavedAi::Minimax(mark), GameVariant::Classic) => AI::Minimax(MinimaxAi::new(mark)),
+            (SavedAi::Minimax(_), GameVariant::Ultimate) => {
+                return Err(SaveError::Invalid(
+                    "the minimax AI cannot play Ultimate".to_string(),
+                ));
+            }
+        })
+    }
+}
+
+impl SavedGame {
+    /// Checks the indices that the game would otherwise panic on.
+    fn validate(&self) -> Result<(), SaveError> {
+        let positions = match self {
+            SavedGame::Classic(game) => vec![game.selected],
+            SavedGame::Ultimate(game) => {
+                let mut positions = vec![game.selected_board];
+                positions.extend(game.selected_cell);
+                if let Some((row, col)) = game.big_board.active_board {
+                    positions.push(Position { row, col });
+                }
+                positions
+            }
+        };
+        match positions
+            .iter()
+            .find(|position| position.row >= 3 || position.col >= 3)
+        {
+            Some(position) => Err(SaveError::Invalid(format!(
+                "position ({}, {}) is out of bounds",
+                position.row, position.col
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes `game` to `path`, creating the parent directories if needed.
+///
+/// # Errors
+/// Returns a [`SaveError`] if the file cannot be written.
+pub fn write_save(path: &Path, game: &SavedGame) -> Result<(), SaveError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| SaveError::Io(error.to_string()))?;
+    }
+    let json =
+        serde_json::to_string(game).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+    fs::write(path, json).map_err(|error| SaveError::Io(error.to_string()))
+}
+
+/// Appends `record` as one notation line to the game log at `path`.
+///
+/// The log and its directory are created if needed.
+///
+/// # Errors
+/// Returns a [`SaveError`] if the log cannot be written.
+pub fn append_game_log(path: &Path, record: &GameRecord) -> Result<(), SaveError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| SaveError::Io(error.to_string()))?;
+    }
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| SaveError::Io(error.to_string()))?;
+    writeln!(log, "{record}").map_err(|error| SaveError::Io(error.to_string()))
+}
+
+/// Reads the game saved at `path`.
+///
+/// # Errors
+/// Returns a [`SaveError`] if the file cannot be read, is not a saved game,
+/// or describes a game that cannot be played.
+pub fn read_save(path: &Path) -> Result<SavedGame, SaveError> {
+    let json = fs::read_to_string(path).map_err(|error| SaveError::Io(error.to_string()))?;
+    let game: SavedGame =
+        serde_json::from_str(&json).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+    game.validate()?;
+    Ok(game)
+}
+
+/// Failure encountered while saving or loading a game.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveError {
+    /// The save file could not be read or written.
+    Io(String),
+    /// The save file does not contain a saved game.
+    Corrupt(String),
+    /// The saved game cannot be played.
+    Invalid(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(formatter, "cannot access save file: {error}"),
+            Self::Corrupt(error) => write!(formatter, "corrupt save file: {error}"),
+            Self::Invalid(reason) => write!(formatter, "invalid saved game: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("tic-tac-foe-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn ultimate_save() -> SavedUltimate {
+        let mut big_board = BigBoard::new();
+        big_board.make_move(1, 1, 0, 2, Mark::X);
+        SavedUltimate {
+            big_board,
+            active_player: Mark::O,
+            starting_player: Mark::X,
+            turn: 1,
+            mode: SavedMode::PvE(SavedAi::Strong(Mark::O)),
+            selected_board: Position { row: 0, col: 2 },
+            selected_cell: Some(Position { row: 0, col: 0 }),
+        }
+    }
+
+    #[test]
+    fn test_big_board_round_trips_with_active_board() {
+        let path = temp_path("round_trip.json");
+        let saved = SavedGame::Ultimate(ultimate_save());
+
+        write_save(&path, &saved).unwrap();
+        let loaded = read_save(&path).unwrap();
+        assert_eq!(loaded, saved);
+        let SavedGame::Ultimate(game) = loaded else {
+            panic!("expected Ultimate save");
+        };
+        assert_eq!(game.big_board.active_board, Some((0, 2)));
+        assert_eq!(game.big_board.state, GameState::Playing);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_and_invalid_saves_are_errors() {
+        let path = temp_path("corrupt.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        fs::write(&path, "{\"classic\": {\"board\"").unwrap();
+        assert!(matches!(read_save(&path), Err(SaveError::Corrupt(_))));
+
+        let mut game = ultimate_save();
+        game.selected_board = Position { row: 3, col: 0 };
+        fs::write(
+            &path,
+            serde_json::to_string(&SavedGame::Ultimate(game)).unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(read_save(&path), Err(SaveError::Invalid(_))));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(read_save(&path), Err(SaveError::Io(_))));
+    }
+
+    #[test]
+    fn test_imitator_cannot_play_ultimate() {
+        let memory = Rc::default();
+        let mode = SavedMode::PvE(SavedAi::Imitator(Mark::O));
+        assert!(mode.to_mode(GameVariant::Classic, &memory).is_ok());
+        assert!(mode.to_mode(GameVariant::Ultimate, &memory).is_err());
+    }
+
+    #[test]
+    fn test_minimax_ai_round_trips_in_classic_only() {
+        let memory = Rc::default();
+        let saved = SavedAi::from_ai(&AI::Minimax(MinimaxAi::new(Mark::O)));
+        assert_eq!(saved, SavedAi::Minimax(Mark::O));
+        let mode = SavedMode::PvE(saved);
+        let Ok(GameMode::PvE(ai)) = mode.to_mode(GameVariant::Classic, &memory) else {
+            panic!("expected a classic PvE mode");
+        };
+        assert!(matches!(ai, AI::Minimax(MinimaxAi { ai_mark: Mark::O })));
+        assert!(mode.to_mode(GameVariant::Ultimate, &memory).is_err());
+    }
+}
@@ -1,47 +1,210 @@
 //! Menu, input, and gameplay state for each application scene.
 
-use crate::ai::{AI, Game};
+use crate::ai::Move;
+use crate::ai::mcts::MCTSAi;
+use crate::ai::minimax::MinimaxAi;
+use crate::ai::rng::{new_seed, seed_rng};
+use crate::ai::simple::SimpleAi;
+use crate::ai::solver::is_dead_draw;
+use crate::ai::{AI, Game, MAX_MOVES_CLASSIC, MAX_MOVES_ULTIMATE, MoveCapExceeded};
+use crate::coach::{
+    HintStrength, Tip, classic_hint, evaluate, missed_move, select_tip, ultimate_hint,
+};
 use crate::game::base::SmallBoard;
+use crate::game::generic::GenericBoard;
+use crate::game::notation::{GameRecord, NotationError, RecordBoard, write_transcript};
 use crate::game::ultimate::BigBoard;
-use crate::game::{Board, GameState, GameVariant, Mark};
+use crate::game::{Board, GameState, GameVariant, Mark, MoveError, RuleSet};
+use crate::puzzle::{Puzzle, generate_puzzle};
+use crate::save::{SavedClassic, SavedGame, SavedMode, SavedUltimate};
+use crate::stats::GameResult;
 use crate::utils::{
     Position, move_selection_down_playable, move_selection_left_playable,
     move_selection_right_playable, move_selection_up_playable, reset_position,
 };
+use std::time::{Duration, Instant};
 
 /// Options displayed by the top-level game-selection menu.
-pub const MAIN_MENU_OPTIONS: [&'static str; 3] = ["Ultimate Tic Tac Toe", "Tic Tac Toe", "Quit"];
+pub const MAIN_MENU_OPTIONS: [&str; 8] = [
+    "Ultimate Tic Tac Toe",
+    "Tic Tac Toe",
+    "Gomoku 5x5",
+    "Tic Tac Toe 4x4",
+    "Watch Replay",
+    "Statistics",
+    "Reset Session",
+    "Quit",
+];
 /// Modes available for classic tic-tac-toe.
-pub const TTT_MENU_OPTIONS: [&'static str; 5] =
-    ["Online PvP", "Local PvP", "Play vs AI", "AI vs AI", "Back"];
+///
+/// The rules option is shown as [`STANDARD_RULES_OPTION`] or
+/// [`MISERE_RULES_OPTION`] depending on the rules new games use, and
+/// [`PLAY_VS_AI_OPTION`] as the [`Opponent::menu_option`] of the chosen opponent.
+pub const TTT_MENU_OPTIONS: [&str; 8] = [
+    "Online PvP",
+    "Local PvP",
+    "Hot Seat",
+    PLAY_VS_AI_OPTION,
+    "AI vs AI",
+    "Solitaire",
+    STANDARD_RULES_OPTION,
+    "Back",
+];
+/// Classic menu option switching new games to misère rules.
+pub const STANDARD_RULES_OPTION: &str = "Rules: Standard";
+/// Classic menu option switching new games back to standard rules.
+pub const MISERE_RULES_OPTION: &str = "Rules: Misère";
+
+/// Returns the classic menu option showing the given rules.
+pub fn rules_option(rules: RuleSet) -> &'static str {
+    match rules {
+        RuleSet::Standard => STANDARD_RULES_OPTION,
+        RuleSet::Misere => MISERE_RULES_OPTION,
+    }
+}
+/// Placeholder for the menu option starting a game against the chosen [`Opponent`].
+pub const PLAY_VS_AI_OPTION: &str = "Play vs AI";
 /// Modes available for Ultimate tic-tac-toe.
-pub const UTT_MENU_OPTIONS: [&'static str; 5] =
-    ["Online PvP", "Local PvP", "Play vs AI", "AI vs AI", "Back"];
+///
+/// [`PLAY_VS_AI_OPTION`] is shown as the [`Opponent::menu_option`] of the chosen opponent.
+pub const UTT_MENU_OPTIONS: [&str; 6] = [
+    "Online PvP",
+    "Local PvP",
+    "Hot Seat",
+    PLAY_VS_AI_OPTION,
+    "AI vs AI",
+    "Back",
+];
 /// AI strengths available from AI-selection menus.
-pub const AI_MENU_OPTIONS: [&'static str; 4] = ["Weak", "Medium", "Strong", "Back"];
+pub const AI_MENU_OPTIONS: [&str; 4] = ["Weak", "Medium", "Strong", "Back"];
+/// AI opponents available when playing classic tic-tac-toe against the AI.
+pub const CLASSIC_PVE_AI_MENU_OPTIONS: [&str; 5] = ["Weak", "Medium", "Strong", "Imitator", "Back"];
 /// Actions available while setting up an online match.
-pub const ONLINE_MENU_OPTIONS: [&'static str; 3] = ["Host Match", "Join Match", "Back"];
+pub const ONLINE_MENU_OPTIONS: [&str; 3] = ["Host Match", "Join Match", "Back"];
+/// Choices of the mark moving first in a local game.
+pub const FIRST_PLAYER_MENU_OPTIONS: [&str; 3] = ["X Moves First", "O Moves First", "Back"];
+/// Difficulties of the Medium AI available in classic games against the AI.
+pub const DIFFICULTY_MENU_OPTIONS: [&str; 5] = ["Easy", "Medium", "Tactical", "Hard", "Back"];
+/// Marks the human can play in a classic game against the AI.
+pub const SIDE_MENU_OPTIONS: [&str; 4] = ["Play as X", "Play as O", "Random", "Back"];
+/// Number of rows and columns of the Gomoku board.
+pub const GOMOKU_SIZE: usize = 5;
+/// Number of marks in a row needed to win a Gomoku game.
+pub const GOMOKU_WIN_LEN: usize = 4;
+/// Number of rows and columns of the 4x4 tic-tac-toe board, which is won with a full line.
+pub const FOUR_BY_FOUR_SIZE: usize = 4;
+/// Number of ticks a just-placed mark stays highlighted.
+pub const PLACEMENT_ANIMATION_FRAMES: u8 = 6;
+/// Delay between two replayed moves when a replay starts.
+pub const DEFAULT_REPLAY_DELAY: Duration = Duration::from_millis(800);
+const REPLAY_DELAY_STEP: Duration = Duration::from_millis(100);
+const MIN_REPLAY_DELAY: Duration = Duration::from_millis(100);
+
+/// Opponent a game started from "Play vs AI" in the classic and Ultimate menus is played against.
+///
+/// The left and right keys cycle through the opponents while the option is selected.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Opponent {
+    /// Moves at random, as the Weak AI.
+    Random,
+    /// Takes wins and blocks losses, as the Medium AI.
+    #[default]
+    Simple,
+    /// Monte Carlo tree search, as the Strong AI.
+    Mcts,
+    /// Searches the full game tree, as the Medium AI on Hard; classic games only.
+    Minimax,
+}
+
+impl Opponent {
+    /// Returns the opponents available in `game`.
+    ///
+    /// Minimax is left out of Ultimate games, whose game tree is far too large to search.
+    fn available(game: GameVariant) -> &'static [Opponent] {
+        match game {
+            GameVariant::Classic => &[Self::Random, Self::Simple, Self::Mcts, Self::Minimax],
+            GameVariant::Ultimate => &[Self::Random, Self::Simple, Self::Mcts],
+        }
+    }
+
+    /// Returns the opponent after this one in `game`, wrapping to the first.
+    pub fn next(self, game: GameVariant) -> Self {
+        let opponents = Self::available(game);
+        let index = opponents.iter().position(|opponent| *opponent == self);
+        match index {
+            Some(index) => opponents[(index + 1) % opponents.len()],
+            None => opponents[0],
+        }
+    }
+
+    /// Returns the opponent before this one in `game`, wrapping to the last.
+    pub fn previous(self, game: GameVariant) -> Self {
+        let opponents = Self::available(game);
+        let index = opponents.iter().position(|opponent| *opponent == self);
+        match index {
+            Some(0) | None => opponents[opponents.len() - 1],
+            Some(index) => opponents[index - 1],
+        }
+    }
+
+    /// Returns the "Play vs AI" menu option showing this opponent.
+    pub fn menu_option(self) -> &'static str {
+        match self {
+            Self::Random => "Play vs AI ◂ Random ▸",
+            Self::Simple => "Play vs AI ◂ Simple ▸",
+            Self::Mcts => "Play vs AI ◂ MCTS ▸",
+            Self::Minimax => "Play vs AI ◂ Minimax ▸",
+        }
+    }
+
+    /// Builds this opponent playing `mark` in a `game`.
+    pub fn ai(self, game: GameVariant, mark: Mark) -> AI {
+        match (self, game) {
+            (Self::Random, _) => AI::Weak(mark),
+            (Self::Simple, _) => AI::Medium(SimpleAi::new(mark)),
+            (Self::Mcts, GameVariant::Classic) => {
+                AI::StrongTTT(MCTSAi::new(SmallBoard::new(), mark))
+            }
+            (Self::Mcts, GameVariant::Ultimate) => {
+                AI::StrongUTT(MCTSAi::new(BigBoard::new(), mark))
+            }
+            (Self::Minimax, _) => AI::Minimax(MinimaxAi::new(mark)),
+        }
+    }
+}
 
 /// Represents all the possible scenes.
 pub enum Scene {
     /// Top-level game-selection menu.
     MainMenu(Menu),
-    /// Classic tic-tac-toe mode menu.
-    TTTMenu(Menu),
+    /// Classic tic-tac-toe mode menu and the opponent chosen for "Play vs AI".
+    TTTMenu(Menu, Opponent),
     /// Online setup menu for the selected game variant.
     OnlineMenu(Menu, GameVariant),
     /// Host screen displaying a shareable endpoint ticket.
     HostingOnline(GameVariant),
     /// Join screen accepting an endpoint ticket.
     JoiningOnline(TicketInput, GameVariant),
-    /// Ultimate tic-tac-toe mode menu.
-    UTTMenu(Menu),
+    /// Ultimate tic-tac-toe mode menu and the opponent chosen for "Play vs AI".
+    UTTMenu(Menu, Opponent),
+    /// Menu choosing which mark moves first in a local game of the selected variant.
+    FirstPlayerMenu(Menu, GameVariant),
+    /// Menu choosing the difficulty of the Medium AI before a classic game against it.
+    DifficultyMenu(Menu),
+    /// Menu choosing the mark of the human in a game of the variant against the chosen AI.
+    SideMenu(Menu, AI, GameVariant),
     /// AI strength menu and its originating context.
     AIMenu(Menu, AIMenuStatus),
     /// Active classic tic-tac-toe game.
     PlayingTTT(GamePlayTTT),
     /// Active Ultimate tic-tac-toe game.
     PlayingUTT(GamePlayUTT),
+    /// Active local game of n-in-a-row on a larger board.
+    PlayingGomoku(GamePlayGomoku),
+    /// Automatic playback of logged games.
+    Replay(Replay),
+    /// Statistics of the games finished in this session.
+    Statistics,
 }
 
 /// Editable iroh ticket text used by the join screen.
@@ -87,6 +250,8 @@ pub enum GameMode {
     LocalPvP,
     /// A peer-to-peer match storing the local player's mark.
     OnlinePvP(Mark),
+    /// A single player solving classic puzzles, one after another.
+    Solitaire,
 }
 
 /// Menu scene with selectable options.
@@ -126,6 +291,215 @@ impl Menu {
     }
 }
 
+/// Replay scene that plays back logged games one move at a time.
+pub struct Replay {
+    /// Valid games loaded from the log.
+    pub records: Vec<GameRecord>,
+    /// Index of the game currently being replayed.
+    pub current: usize,
+    /// Number of moves of the current game shown on the board.
+    pub ply: usize,
+    /// Board after `ply` moves, or `None` if no game could be loaded.
+    pub board: Option<RecordBoard>,
+    /// Delay between two replayed moves.
+    pub delay: Duration,
+    /// Notice about skipped lines or loading failures.
+    pub message: Option<String>,
+    last_step: Instant,
+}
+
+impl Replay {
+    /// Loads every valid game from the contents of a game log.
+    ///
+    /// Blank lines and lines starting with `#` are ignored. Lines that cannot
+    /// be parsed or contain illegal moves are skipped and reported in `message`.
+    pub fn from_log(contents: &str) -> Self {
+        let mut records = Vec::new();
+        let mut skipped = 0;
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match GameRecord::parse(line) {
+                Ok(record) if record.board_at(record.moves.len()).is_ok() => records.push(record),
+                _ => skipped += 1,
+            }
+        }
+
+        let message = match (skipped, records.is_empty()) {
+            (0, false) => None,
+            (0, true) => Some("No games found in the log".to_string()),
+            (skipped, _) => Some(format!("Skipped {skipped} malformed line(s)")),
+        };
+        let mut replay = Self {
+            records,
+            current: 0,
+            ply: 0,
+            board: None,
+            delay: DEFAULT_REPLAY_DELAY,
+            message,
+            last_step: Instant::now(),
+        };
+        replay.load_current();
+        replay
+    }
+
+    /// Creates an empty replay that only displays an error message.
+    pub fn failed(message: impl Into<String>) -> Self {
+        Self {
+            records: Vec::new(),
+            current: 0,
+            ply: 0,
+            board: None,
+            delay: DEFAULT_REPLAY_DELAY,
+            message: Some(message.into()),
+            last_step: Instant::now(),
+        }
+    }
+
+    /// Returns the game currently being replayed.
+    pub fn record(&self) -> Option<&GameRecord> {
+        self.records.get(self.current)
+    }
+
+    /// Returns whether every move of the current game has been shown.
+    pub fn is_finished(&self) -> bool {
+        self.record()
+            .is_none_or(|record| self.ply >= record.moves.len())
+    }
+
+    /// Advances the replay by one move once `delay` has elapsed since the last one.
+    ///
+    /// Returns `true` if a move was replayed.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.is_finished() || now.duration_since(self.last_step) < self.delay {
+            return false;
+        }
+        self.last_step = now;
+        self.step()
+    }
+
+    /// Shows the next move of the current game.
+    ///
+    /// Returns `false` if the game has already been fully replayed.
+    pub fn step(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.ply += 1;
+        self.load_current();
+        true
+    }
+
+    /// Starts replaying the next game, wrapping to the first one.
+    pub fn next_game(&mut self) {
+        if self.records.is_empty() {
+            return;
+        }
+        self.current = (self.current + 1) % self.records.len();
+        self.ply = 0;
+        self.last_step = Instant::now();
+        self.load_current();
+    }
+
+    /// Shortens the delay between moves.
+    pub fn faster(&mut self) {
+        self.delay = self
+            .delay
+            .saturating_sub(REPLAY_DELAY_STEP)
+            .max(MIN_REPLAY_DELAY);
+    }
+
+    /// Lengthens the delay between moves.
+    pub fn slower(&mut self) {
+        self.delay += REPLAY_DELAY_STEP;
+    }
+
+    fn load_current(&mut self) {
+        self.board = self
+            .record()
+            .and_then(|record| record.board_at(self.ply).ok());
+    }
+}
+
+/// Chess-style clock holding the time each player has left for the whole game.
+///
+/// Only the time between two ticks of the same turn is charged, so moves
+/// played while the input is handled, such as the AI reply, cost nothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameClock {
+    /// Time each player starts the game with.
+    pub budget: Duration,
+    remaining_x: Duration,
+    remaining_o: Duration,
+    last_tick: Option<(u32, Instant)>,
+}
+
+impl GameClock {
+    /// Creates a clock giving both players `budget`.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            remaining_x: budget,
+            remaining_o: budget,
+            last_tick: None,
+        }
+    }
+
+    /// Returns the time `mark` has left.
+    pub fn remaining(&self, mark: Mark) -> Duration {
+        match mark {
+            Mark::X => self.remaining_x,
+            Mark::O => self.remaining_o,
+        }
+    }
+
+    /// Charges `mark` for the time since the previous tick of the same turn.
+    ///
+    /// # Arguments
+    /// * `mark` - Player whose turn it is
+    /// * `turn` - Number of moves played so far
+    /// * `now` - Current time
+    ///
+    /// # Returns
+    /// True if `mark` has no time left.
+    pub fn tick(&mut self, mark: Mark, turn: u32, now: Instant) -> bool {
+        if let Some((last_turn, last)) = self.last_tick
+            && last_turn == turn
+        {
+            let elapsed = now.saturating_duration_since(last);
+            let remaining = match mark {
+                Mark::X => &mut self.remaining_x,
+                Mark::O => &mut self.remaining_o,
+            };
+            *remaining = remaining.saturating_sub(elapsed);
+        }
+        self.last_tick = Some((turn, now));
+        self.remaining(mark).is_zero()
+    }
+}
+
+/// Highlight of the cell a mark was just placed in, fading over a few ticks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlacementAnimation {
+    /// Cell of the placed mark.
+    pub cell: Position,
+    /// Ticks left before the highlight disappears, from
+    /// [`PLACEMENT_ANIMATION_FRAMES`] down to 1.
+    pub frames_left: u8,
+}
+
+/// Position of a classic game saved when analysis mode is entered.
+#[derive(Clone, Debug)]
+struct AnalysisSnapshot {
+    board: SmallBoard,
+    active_player: Mark,
+    turn: u32,
+    selected: Position,
+    history: Vec<Position>,
+    redo_stack: Vec<Position>,
+}
+
 /// Main tic-tac-toe gameplay scene containing the board state and game logic.
 pub struct GamePlayTTT {
     /// Current classic board state.
@@ -138,24 +512,230 @@ pub struct GamePlayTTT {
     pub mode: GameMode,
     /// Currently selected classic board position.
     pub selected: Position,
+    /// Whether each local player has their own keys, X on WASD and O on the arrows.
+    pub split_keys: bool,
+    /// Whether the optimal move is shown after a mistake against the AI.
+    pub show_mistakes: bool,
+    /// Optimal move the player missed with their last move, if it was a mistake.
+    pub missed_move: Option<Move>,
+    /// Whether coaching tips are displayed.
+    pub show_tips: bool,
+    /// Strength of the move suggested alongside the tips.
+    pub hint_strength: HintStrength,
+    hint: Option<(u32, Move)>,
+    ranking: Option<(u32, Vec<(Move, f32)>)>,
+    /// Suggested cell highlighted on the board until the next move.
+    pub hint_cell: Option<Position>,
+    /// Fading highlight of the last placed mark, purely cosmetic.
+    pub placement: Option<PlacementAnimation>,
+    /// Whether a position neither player can win any more ends as a draw right away.
+    ///
+    /// Ignored in online games, where both peers must agree on the result.
+    pub auto_draw: bool,
+    /// Puzzle being solved in Solitaire mode.
+    pub puzzle: Option<Puzzle>,
+    /// Number of puzzles solved since the Solitaire game started.
+    pub puzzles_solved: u32,
+    /// Whether the last answer to the current puzzle was wrong.
+    pub puzzle_missed: bool,
+    /// Whether the evaluation graph is displayed.
+    pub show_graph: bool,
+    /// Whether the analysis bar with the evaluation of the position is displayed.
+    pub show_analysis: bool,
+    /// Whether coordinate labels are drawn around the board.
+    pub show_coordinates: bool,
+    /// Whether the game is only watched: player moves are ignored while the
+    /// selection can still move, and AI vs AI games still advance.
+    pub read_only: bool,
+    /// Player whose draw offer awaits the opponent's answer, until the next move.
+    pub draw_offer: Option<Mark>,
+    /// Estimated winning chance of X after each evaluated AI move.
+    pub evaluations: Vec<f32>,
+    /// Set when an AI-vs-AI game is stopped for exceeding its move cap.
+    pub aborted: Option<MoveCapExceeded>,
+    /// Time each player has left, in timed games.
+    pub clock: Option<GameClock>,
+    /// Player who lost the game by running out of time.
+    pub flagged: Option<Mark>,
+    /// Whether the first local player controls O, after a rematch with sides swapped.
+    ///
+    /// In hot-seat games the WASD keys then play O and the arrow keys play X.
+    pub players_swapped: bool,
+    /// Seed the AI random number generator was started from for this game.
+    pub seed: u64,
+    result_taken: bool,
+    ai_moves: u32,
     starting_player: Mark,
     local_rematch_ready: bool,
     remote_rematch_ready: bool,
+    history: Vec<Position>,
+    redo_stack: Vec<Position>,
+    analysis: Option<Box<AnalysisSnapshot>>,
 }
 
 impl GamePlayTTT {
     /// Creates a new game with the specified mode.
+    ///
+    /// X always moves first, so an AI opponent playing X opens right away.
     pub fn new(mode: GameMode) -> Self {
-        Self {
+        Self::with_rules(mode, RuleSet::Standard)
+    }
+
+    /// Creates a new game with the specified mode, played with the given rules.
+    ///
+    /// Solitaire puzzles keep the standard rules they were built for.
+    pub fn with_rules(mode: GameMode, rules: RuleSet) -> Self {
+        let mut game = Self::setup(mode);
+        if game.puzzle.is_none() {
+            game.board = SmallBoard::with_rules(rules);
+        }
+        game.play_ai_opening();
+        game
+    }
+
+    /// Creates a game with the specified mode without letting any AI move.
+    fn setup(mut mode: GameMode) -> Self {
+        // Rebuild any AI state from the seed so a restart can reproduce it.
+        let seed = new_seed();
+        seed_rng(seed);
+        reset_ais(&mut mode);
+        let mut game = Self {
             board: SmallBoard::new(),
             active_player: Mark::X,
             turn: 0,
             mode,
             selected: Position { row: 0, col: 0 },
+            split_keys: false,
+            show_mistakes: false,
+            missed_move: None,
+            show_tips: false,
+            hint_strength: HintStrength::default(),
+            hint: None,
+            ranking: None,
+            placement: None,
+            hint_cell: None,
+            auto_draw: false,
+            puzzle: None,
+            puzzles_solved: 0,
+            puzzle_missed: false,
+            show_graph: false,
+            show_analysis: false,
+            show_coordinates: false,
+            read_only: false,
+            draw_offer: None,
+            evaluations: Vec::new(),
+            aborted: None,
+            clock: None,
+            flagged: None,
+            players_swapped: false,
+            ai_moves: 0,
+            seed,
+            result_taken: false,
             starting_player: Mark::X,
             local_rematch_ready: false,
             remote_rematch_ready: false,
+            history: Vec::new(),
+            redo_stack: Vec::new(),
+            analysis: None,
+        };
+        if game.mode == GameMode::Solitaire {
+            game.load_puzzle(generate_puzzle());
         }
+        game
+    }
+
+    /// Creates a new game with the specified mode and mark moving first.
+    pub fn with_first_player(mode: GameMode, first_player: Mark) -> Self {
+        let mut game = Self::new(mode);
+        if first_player == Mark::O {
+            game.play_second();
+        }
+        game
+    }
+
+    /// Resumes a saved game with the given players.
+    ///
+    /// # Arguments
+    /// * `saved` - The saved game state
+    /// * `mode` - Players rebuilt from the saved mode
+    pub fn resume(saved: SavedClassic, mode: GameMode) -> Self {
+        let mut game = Self::setup(mode);
+        game.board = saved.board;
+        game.active_player = saved.active_player;
+        game.starting_player = saved.starting_player;
+        game.turn = saved.turn;
+        game.selected = saved.selected;
+        game
+    }
+
+    /// Returns the moves of the game as a transcript such as `X:b2 O:a1 X:c3`.
+    ///
+    /// Moves played before the game was resumed from a save are not included.
+    pub fn transcript(&self) -> String {
+        let moves: Vec<Move> = self
+            .history
+            .iter()
+            .map(|position| Move::Base(position.row, position.col))
+            .collect();
+        write_transcript(self.starting_player, &moves)
+    }
+
+    /// Returns the moves of the game as a record for the game log.
+    ///
+    /// # Returns
+    /// The record, or None if moves were played before the game was resumed
+    /// from a save, since the record could not rebuild the board.
+    pub fn game_record(&self) -> Option<GameRecord> {
+        let marks = (0..9)
+            .filter(|index| self.board.get(index / 3, index % 3).is_some())
+            .count();
+        (marks == self.history.len()).then(|| GameRecord {
+            variant: GameVariant::Classic,
+            rules: self.board.rules(),
+            first_player: self.starting_player,
+            moves: self
+                .history
+                .iter()
+                .map(|position| Move::Base(position.row, position.col))
+                .collect(),
+        })
+    }
+
+    /// Replays a transcript written by [`GamePlayTTT::transcript`] into a fresh local game.
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if the transcript is malformed or a move is illegal.
+    pub fn from_transcript(transcript: &str) -> Result<Self, NotationError> {
+        let record = GameRecord::from_transcript(GameVariant::Classic, transcript)?;
+        record.board_at(record.moves.len())?;
+        let mut game = Self::with_first_player(GameMode::LocalPvP, record.first_player);
+        for mv in &record.moves {
+            let (row, col) = mv.unwrap_base();
+            game.apply_move(row, col);
+        }
+        reset_position(&game.board, &mut game.selected);
+        Ok(game)
+    }
+
+    /// Captures the game so it can be written to disk.
+    ///
+    /// # Returns
+    /// The saved state, or None for online and Solitaire games.
+    ///
+    /// During analysis mode the real position is saved, not the variation.
+    pub fn snapshot(&self) -> Option<SavedGame> {
+        let (board, active_player, turn, selected) = match &self.analysis {
+            Some(saved) => (saved.board, saved.active_player, saved.turn, saved.selected),
+            None => (self.board, self.active_player, self.turn, self.selected),
+        };
+        Some(SavedGame::Classic(SavedClassic {
+            board,
+            active_player,
+            starting_player: self.starting_player,
+            turn,
+            mode: SavedMode::from_mode(&self.mode)?,
+            selected,
+        }))
     }
 
     /// Moves selection left, wrapping to the rightmost column and finding
@@ -184,11 +764,15 @@ impl GamePlayTTT {
 
     /// Attempts to make a move at the currently selected position.
     ///
-    /// If the game is over or the cell is occupied, does nothing.
+    /// If the game is over, the cell is occupied or the game is
+    /// [`read_only`](Self::read_only), does nothing.
     /// After a valid move, checks for win/draw conditions and switches players.
     /// In PvE mode, triggers the AI to make its move.
     pub fn play_move(&mut self) -> bool {
-        if self.board.state != GameState::Playing {
+        if self.board.state != GameState::Playing || self.aborted.is_some() {
+            return false;
+        }
+        if self.read_only && !matches!(self.mode, GameMode::EvE(_, _)) {
             return false;
         }
         if matches!(
@@ -197,10 +781,31 @@ impl GamePlayTTT {
         ) {
             return false;
         }
+        if self.analysis.is_some() {
+            // Both sides of a variation are played by hand.
+            self.apply_move(self.selected.row, self.selected.col);
+            reset_position(&self.board, &mut self.selected);
+            return true;
+        }
 
         match self.mode {
             GameMode::EvE(_, _) => {}
+            GameMode::Solitaire => {
+                self.answer_puzzle();
+                return true;
+            }
             _ => {
+                if let GameMode::PvE(AI::Imitator(ai)) = &self.mode {
+                    let mv = Move::Base(self.selected.row, self.selected.col);
+                    ai.observe(&self.board, self.active_player, mv);
+                }
+                self.missed_move = match self.mode {
+                    GameMode::PvE(_) if self.show_mistakes => {
+                        let mv = Move::Base(self.selected.row, self.selected.col);
+                        missed_move(&self.board, self.active_player, mv)
+                    }
+                    _ => None,
+                };
                 self.apply_move(self.selected.row, self.selected.col);
                 if self.board.state != GameState::Playing {
                     return true;
@@ -213,6 +818,40 @@ impl GamePlayTTT {
         true
     }
 
+    /// Checks the selected cell against the solution of the current puzzle.
+    ///
+    /// A correct answer moves on to a new puzzle, a wrong one is flagged and
+    /// leaves the puzzle unchanged.
+    fn answer_puzzle(&mut self) {
+        let Some(puzzle) = self.puzzle else {
+            return;
+        };
+        if !self.board.is_playable(self.selected.row, self.selected.col) {
+            return;
+        }
+        if Move::Base(self.selected.row, self.selected.col) == puzzle.solution {
+            self.puzzles_solved += 1;
+            self.load_puzzle(generate_puzzle());
+        } else {
+            self.puzzle_missed = true;
+        }
+    }
+
+    /// Sets up the board of `puzzle` for the player to solve.
+    fn load_puzzle(&mut self, puzzle: Puzzle) {
+        self.board = puzzle.board;
+        self.active_player = puzzle.mark;
+        self.puzzle = Some(puzzle);
+        self.puzzle_missed = false;
+        self.hint = None;
+        self.ranking = None;
+        self.placement = None;
+        self.hint_cell = None;
+        self.history.clear();
+        self.redo_stack.clear();
+        reset_position(&self.board, &mut self.selected);
+    }
+
     /// Applies a valid move received from the remote player.
     ///
     /// Returns `false` when the move violates turn or board constraints.
@@ -234,6 +873,62 @@ impl GamePlayTTT {
         true
     }
 
+    /// Resigns a local game on behalf of the active player, awarding the opponent the win.
+    ///
+    /// Online games are conceded with [`Self::concede_online`] instead, and
+    /// AI-vs-AI and Solitaire games have no player to resign.
+    ///
+    /// # Returns
+    /// True if the game was resigned, false if it was already over.
+    pub fn resign(&mut self) -> bool {
+        if !matches!(self.mode, GameMode::LocalPvP | GameMode::PvE(_))
+            || self.board.state != GameState::Playing
+        {
+            return false;
+        }
+
+        self.board.state = GameState::Won(self.active_player.switch());
+        true
+    }
+
+    /// Offers a draw on behalf of the active player in a Local PvP game.
+    ///
+    /// The offer stands until the opponent answers it or the next move is played.
+    ///
+    /// # Returns
+    /// True if the offer was made, false if the game is over, not a Local PvP
+    /// game, or an offer is already pending.
+    pub fn offer_draw(&mut self) -> bool {
+        if self.mode != GameMode::LocalPvP
+            || self.board.state != GameState::Playing
+            || self.draw_offer.is_some()
+        {
+            return false;
+        }
+        self.draw_offer = Some(self.active_player);
+        true
+    }
+
+    /// Accepts the pending draw offer, ending the game as a draw.
+    ///
+    /// # Returns
+    /// True if an offer was pending.
+    pub fn accept_draw(&mut self) -> bool {
+        if self.draw_offer.take().is_none() {
+            return false;
+        }
+        self.board.state = GameState::Draw;
+        true
+    }
+
+    /// Declines the pending draw offer and lets the game go on.
+    ///
+    /// # Returns
+    /// True if an offer was pending.
+    pub fn decline_draw(&mut self) -> bool {
+        self.draw_offer.take().is_some()
+    }
+
     /// Concedes an active online round and awards the opponent the win.
     pub fn concede_online(&mut self) -> bool {
         let GameMode::OnlinePvP(local_mark) = self.mode else {
@@ -325,6 +1020,198 @@ impl GamePlayTTT {
         self.local_rematch_ready
     }
 
+    /// Returns whether a local human player is expected to move next.
+    pub fn is_local_turn(&self) -> bool {
+        match self.mode {
+            GameMode::EvE(_, _) => false,
+            GameMode::OnlinePvP(local_mark) => local_mark == self.active_player,
+            GameMode::PvE(_) | GameMode::LocalPvP | GameMode::Solitaire => true,
+        }
+    }
+
+    /// Gives each player `budget` for the whole game, or removes the clock.
+    ///
+    /// Ignored in online, AI-vs-AI and Solitaire games, which have no local
+    /// opponent to lose the time to.
+    pub fn set_time_budget(&mut self, budget: Option<Duration>) {
+        if matches!(self.mode, GameMode::PvE(_) | GameMode::LocalPvP) {
+            self.clock = budget.map(GameClock::new);
+        }
+    }
+
+    /// Advances the placement highlight by one frame, clearing it once it has faded.
+    pub fn tick_animation(&mut self) {
+        if let Some(animation) = &mut self.placement {
+            animation.frames_left -= 1;
+            if animation.frames_left == 0 {
+                self.placement = None;
+            }
+        }
+    }
+
+    /// Runs the clock of the player to move, who loses once their time is up.
+    ///
+    /// The clocks are paused during analysis mode.
+    pub fn tick_clock(&mut self, now: Instant) {
+        if self.board.state != GameState::Playing
+            || !self.is_local_turn()
+            || self.analysis.is_some()
+        {
+            return;
+        }
+        let Some(clock) = &mut self.clock else {
+            return;
+        };
+        if clock.tick(self.active_player, self.turn, now) {
+            self.flagged = Some(self.active_player);
+            self.board.state = GameState::Won(self.active_player.switch());
+        }
+    }
+
+    /// Toggles the display of coaching tips.
+    pub fn toggle_tips(&mut self) {
+        self.show_tips = !self.show_tips;
+    }
+
+    /// Switches between weak and perfect hints.
+    pub fn toggle_hint_strength(&mut self) {
+        self.hint_strength = self.hint_strength.toggle();
+        self.hint = None;
+    }
+
+    /// Computes the suggested move for the current turn if it is missing.
+    ///
+    /// Hints are only computed while tips are shown on a local turn.
+    pub fn update_hint(&mut self) {
+        if !self.show_tips || self.board.state != GameState::Playing || !self.is_local_turn() {
+            return;
+        }
+        if self.hint.is_none_or(|(turn, _)| turn != self.turn) {
+            let mv = classic_hint(&self.board, self.active_player, self.hint_strength);
+            self.hint = Some((self.turn, mv));
+        }
+    }
+
+    /// Returns the suggested move for the current turn, if it was computed.
+    pub fn hint(&self) -> Option<Move> {
+        if !self.show_tips || self.board.state != GameState::Playing || !self.is_local_turn() {
+            return None;
+        }
+        self.hint
+            .filter(|(turn, _)| *turn == self.turn)
+            .map(|(_, mv)| mv)
+    }
+
+    /// Toggles showing the optimal move after a mistake.
+    pub fn toggle_mistakes(&mut self) {
+        self.show_mistakes = !self.show_mistakes;
+        self.missed_move = None;
+    }
+
+    /// Suggests a cell for the active player, using the configured hint strength.
+    ///
+    /// # Returns
+    /// The suggested cell, or None if the game is over or it is not a local turn.
+    pub fn suggested_cell(&self) -> Option<Position> {
+        if self.board.state != GameState::Playing || !self.is_local_turn() {
+            return None;
+        }
+        let (row, col) =
+            classic_hint(&self.board, self.active_player, self.hint_strength).unwrap_base();
+        Some(Position { row, col })
+    }
+
+    /// Shows or hides the suggested cell on the board.
+    pub fn toggle_hint_cell(&mut self) {
+        self.hint_cell = match self.hint_cell {
+            Some(_) => None,
+            None => self.suggested_cell(),
+        };
+    }
+
+    /// Toggles the display of the evaluation graph.
+    pub fn toggle_graph(&mut self) {
+        self.show_graph = !self.show_graph;
+    }
+
+    /// Toggles the display of the analysis bar.
+    pub fn toggle_analysis(&mut self) {
+        self.show_analysis = !self.show_analysis;
+    }
+
+    /// Toggles the coordinate labels around the board.
+    pub fn toggle_coordinates(&mut self) {
+        self.show_coordinates = !self.show_coordinates;
+    }
+
+    /// Toggles [`Self::read_only`].
+    pub fn toggle_read_only(&mut self) {
+        self.read_only = !self.read_only;
+    }
+
+    /// Evaluates the position from X's point of view, between -1.0 and 1.0.
+    ///
+    /// While the game is played, the estimate of the MCTS AI that moved last
+    /// is used when there is one, else the board is scored by [`evaluate`].
+    pub fn analysis(&self) -> f32 {
+        match self.board.state {
+            GameState::Playing => mcts_advantage(&self.mode, self.active_player)
+                .unwrap_or_else(|| evaluate(&self.board)),
+            _ => evaluate(&self.board),
+        }
+    }
+
+    /// Ranks the moves of the current turn with an MCTS search, if the
+    /// analysis bar is displayed and they were not ranked yet.
+    pub fn update_ranking(&mut self) {
+        if !self.show_analysis || self.board.state != GameState::Playing {
+            return;
+        }
+        if self
+            .ranking
+            .as_ref()
+            .is_none_or(|(turn, _)| *turn != self.turn)
+        {
+            let mut ai = MCTSAi::new(self.board, self.active_player);
+            self.ranking = Some((self.turn, ai.evaluate_moves(&self.board)));
+        }
+    }
+
+    /// Returns the legal moves of the current turn ranked by their winning
+    /// chance for the active player, best first, if they were ranked.
+    pub fn ranked_moves(&self) -> Option<&[(Move, f32)]> {
+        if !self.show_analysis || self.board.state != GameState::Playing {
+            return None;
+        }
+        self.ranking
+            .as_ref()
+            .filter(|(turn, _)| *turn == self.turn)
+            .map(|(_, ranking)| ranking.as_slice())
+    }
+
+    /// Returns the result of the finished game, only the first time it is called.
+    ///
+    /// Returns None while the game is in progress or once the result was taken.
+    pub fn take_result(&mut self) -> Option<GameResult> {
+        if self.board.state == GameState::Playing || self.result_taken || self.analysis.is_some() {
+            return None;
+        }
+        self.result_taken = true;
+        Some(GameResult {
+            variant: GameVariant::Classic,
+            first_player: self.starting_player,
+            outcome: self.board.state,
+        })
+    }
+
+    /// Returns the coaching tip for the active player, if tips are enabled.
+    pub fn tip(&self) -> Option<Tip> {
+        if !self.show_tips || self.board.state != GameState::Playing || !self.is_local_turn() {
+            return None;
+        }
+        select_tip(&self.board, self.active_player)
+    }
+
     fn start_rematch_if_ready(&mut self) {
         if self.local_rematch_ready && self.remote_rematch_ready {
             self.start_online_round(self.starting_player.switch());
@@ -332,87 +1219,517 @@ impl GamePlayTTT {
     }
 
     fn start_online_round(&mut self, starting_player: Mark) {
-        self.board = SmallBoard::new();
+        self.board = SmallBoard::with_rules(self.board.rules());
         self.active_player = starting_player;
         self.turn = 0;
         self.selected = Position { row: 0, col: 0 };
         self.starting_player = starting_player;
+        self.result_taken = false;
+        self.hint = None;
+        self.ranking = None;
+        self.placement = None;
+        self.hint_cell = None;
+        self.history.clear();
+        self.redo_stack.clear();
         self.local_rematch_ready = false;
         self.remote_rematch_ready = false;
     }
 
     fn apply_move(&mut self, row: usize, col: usize) {
         self.board.make_move(row, col, self.active_player);
+        self.record_move(row, col);
+        self.hint_cell = None;
+        self.draw_offer = None;
+        self.placement = Some(PlacementAnimation {
+            cell: Position { row, col },
+            frames_left: PLACEMENT_ANIMATION_FRAMES,
+        });
         self.turn += 1;
         self.active_player = self.active_player.switch();
+        self.check_auto_draw();
     }
 
-    /// Executes the AI's turn in PvE and EvE modes.
-    fn ai_play(&mut self) {
-        match &mut self.mode {
-            GameMode::LocalPvP | GameMode::OnlinePvP(_) => return,
-            GameMode::PvE(ai) => {
-                let (ai_row, ai_col) = ai.choose_move_ttt(&self.board).unwrap_base();
-                self.board.make_move(ai_row, ai_col, ai.get_mark());
-
-                self.turn += 1;
-                self.active_player = self.active_player.switch();
-
-                reset_position(&self.board, &mut self.selected);
-            }
-            GameMode::EvE(ai_x, ai_o) => {
-                let (ai_row, ai_col) = match self.active_player {
-                    Mark::X => ai_x.choose_move_ttt(&self.board).unwrap_base(),
-                    Mark::O => ai_o.choose_move_ttt(&self.board).unwrap_base(),
-                };
-                self.board.make_move(ai_row, ai_col, self.active_player);
+    /// Adds a fresh move to the history, which invalidates the moves that could be redone.
+    fn record_move(&mut self, row: usize, col: usize) {
+        self.history.push(Position { row, col });
+        self.redo_stack.clear();
+    }
 
-                self.turn += 1;
-                self.active_player = self.active_player.switch()
-            }
-        }
+    /// Takes back the last move.
+    ///
+    /// Against the AI, its reply is taken back too so the human is on turn
+    /// again. Only local games against a human or the AI can be undone.
+    ///
+    /// # Returns
+    /// True if a move was taken back.
+    pub fn undo(&mut self) -> bool {
+        let Some(kept_moves) = self.undo_target() else {
+            return false;
+        };
+        let undone = self.history.split_off(kept_moves);
+        self.redo_stack.extend(undone.into_iter().rev());
+        self.rebuild_board();
+        true
     }
 
-    /// Allows the O player to play first if the game just started.
-    pub fn play_second(&mut self) {
-        if matches!(self.mode, GameMode::OnlinePvP(_)) {
+    /// Plays again the last move taken back by [`GamePlayTTT::undo`].
+    ///
+    /// Against the AI, its recorded reply is replayed too.
+    ///
+    /// # Returns
+    /// True if a move was replayed.
+    pub fn redo(&mut self) -> bool {
+        if !self.can_take_back() {
+            return false;
+        }
+        let Some(position) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.history.push(position);
+        if let GameMode::PvE(ai) = &self.mode
+            && self.mover(self.history.len()) == ai.get_mark()
+            && let Some(reply) = self.redo_stack.pop()
+        {
+            self.history.push(reply);
+        }
+        self.rebuild_board();
+        true
+    }
+
+    /// Enters analysis mode, saving the position so variations can be played from it.
+    ///
+    /// While analyzing, every move is played by hand for whichever side is on
+    /// turn, the AI does not answer, and finished variations are not scored.
+    ///
+    /// # Returns
+    /// True if analysis mode was entered, false if it is already active or
+    /// the game is not a local game against a human or the AI.
+    pub fn enter_analysis(&mut self) -> bool {
+        if self.analysis.is_some() || !self.can_take_back() {
+            return false;
+        }
+        self.analysis = Some(Box::new(AnalysisSnapshot {
+            board: self.board,
+            active_player: self.active_player,
+            turn: self.turn,
+            selected: self.selected,
+            history: self.history.clone(),
+            redo_stack: self.redo_stack.clone(),
+        }));
+        self.draw_offer = None;
+        true
+    }
+
+    /// Leaves analysis mode, restoring the position saved by [`Self::enter_analysis`].
+    ///
+    /// # Returns
+    /// True if analysis mode was active.
+    pub fn exit_analysis(&mut self) -> bool {
+        let Some(saved) = self.analysis.take().map(|saved| *saved) else {
+            return false;
+        };
+        self.board = saved.board;
+        self.active_player = saved.active_player;
+        self.turn = saved.turn;
+        self.selected = saved.selected;
+        self.history = saved.history;
+        self.redo_stack = saved.redo_stack;
+        self.hint = None;
+        self.ranking = None;
+        self.placement = None;
+        self.hint_cell = None;
+        self.missed_move = None;
+        self.draw_offer = None;
+        true
+    }
+
+    /// Returns whether analysis mode is active.
+    pub fn is_analyzing(&self) -> bool {
+        self.analysis.is_some()
+    }
+
+    /// Returns whether moves can be taken back and replayed in this mode.
+    fn can_take_back(&self) -> bool {
+        !self.read_only
+            && self.aborted.is_none()
+            && self.flagged.is_none()
+            && matches!(self.mode, GameMode::LocalPvP | GameMode::PvE(_))
+    }
+
+    /// Returns the mark played at this terminal when the opponent is remote or the AI.
+    pub fn human_mark(&self) -> Option<Mark> {
+        match &self.mode {
+            GameMode::PvE(ai) => Some(ai.get_mark().switch()),
+            GameMode::OnlinePvP(local_mark) => Some(*local_mark),
+            GameMode::LocalPvP | GameMode::EvE(_, _) | GameMode::Solitaire => None,
+        }
+    }
+
+    /// Returns how many moves of the history remain after an undo, if one is possible.
+    fn undo_target(&self) -> Option<usize> {
+        if !self.can_take_back() {
+            return None;
+        }
+        match self.mode {
+            GameMode::PvE(_) => {
+                let human = self.human_mark()?;
+                (0..self.history.len())
+                    .rev()
+                    .find(|index| self.mover(*index) == human)
+            }
+            _ => self.history.len().checked_sub(1),
+        }
+    }
+
+    /// Returns the mark playing the move at `index` in the history.
+    fn mover(&self, index: usize) -> Mark {
+        if index.is_multiple_of(2) {
+            self.starting_player
+        } else {
+            self.starting_player.switch()
+        }
+    }
+
+    /// Replays the history on an empty board.
+    fn rebuild_board(&mut self) {
+        self.board = SmallBoard::with_rules(self.board.rules());
+        self.active_player = self.starting_player;
+        self.turn = 0;
+        for position in &self.history {
+            self.board
+                .make_move(position.row, position.col, self.active_player);
+            self.turn += 1;
+            self.active_player = self.active_player.switch();
+        }
+        self.check_auto_draw();
+        self.hint = None;
+        self.ranking = None;
+        self.placement = None;
+        self.hint_cell = None;
+        self.missed_move = None;
+        self.draw_offer = None;
+        reset_position(&self.board, &mut self.selected);
+    }
+
+    /// Ends the game as a draw if auto-draw is enabled and nobody can win any more.
+    fn check_auto_draw(&mut self) {
+        if self.auto_draw
+            && !matches!(self.mode, GameMode::OnlinePvP(_))
+            && is_dead_draw(&self.board, self.active_player)
+        {
+            self.board.state = GameState::Draw;
+        }
+    }
+
+    /// Executes the AI's turn in PvE and EvE modes.
+    fn ai_play(&mut self) {
+        match &mut self.mode {
+            GameMode::LocalPvP | GameMode::OnlinePvP(_) | GameMode::Solitaire => (),
+            GameMode::PvE(ai) => {
+                let (ai_row, ai_col) = ai.choose_move_ttt(&self.board).unwrap_base();
+                self.board.make_move(ai_row, ai_col, ai.get_mark());
+                self.record_move(ai_row, ai_col);
+
+                self.turn += 1;
+                self.active_player = self.active_player.switch();
+                self.check_auto_draw();
+
+                reset_position(&self.board, &mut self.selected);
+            }
+            GameMode::EvE(ai_x, ai_o) => {
+                if self.ai_moves >= MAX_MOVES_CLASSIC {
+                    self.aborted = Some(MoveCapExceeded {
+                        max_moves: MAX_MOVES_CLASSIC,
+                    });
+                    return;
+                }
+                self.ai_moves += 1;
+
+                let mv = match self.active_player {
+                    Mark::X => ai_x.choose_move_ttt(&self.board),
+                    Mark::O => ai_o.choose_move_ttt(&self.board),
+                };
+                if !self.board.get_possible_moves().contains(&mv) {
+                    return;
+                }
+                self.board.play(&mv, self.active_player);
+                let moving_ai = match self.active_player {
+                    Mark::X => ai_x,
+                    Mark::O => ai_o,
+                };
+                record_evaluation(&mut self.evaluations, moving_ai);
+                let (row, col) = mv.unwrap_base();
+                self.record_move(row, col);
+
+                self.turn += 1;
+                self.active_player = self.active_player.switch();
+                self.check_auto_draw();
+            }
+        }
+    }
+
+    /// Lets the AI play X move first against a human playing O.
+    fn play_ai_opening(&mut self) {
+        if let GameMode::PvE(ai) = &self.mode
+            && ai.get_mark() == self.active_player
+            && self.turn == 0
+        {
+            self.ai_play();
+        }
+    }
+
+    /// Allows the O player to play first if the game just started.
+    ///
+    /// Against the AI, the human picks their mark before the game instead.
+    pub fn play_second(&mut self) {
+        if matches!(
+            self.mode,
+            GameMode::OnlinePvP(_) | GameMode::PvE(_) | GameMode::Solitaire
+        ) {
             return;
         }
         if self.board.state == GameState::Playing && self.turn == 0 {
             self.active_player = Mark::O;
-            match &mut self.mode {
-                GameMode::LocalPvP | GameMode::OnlinePvP(_) => return,
-                GameMode::PvE(ai) => ai.switch_starting_mark(),
-                GameMode::EvE(ai_x, ai_o) => {
-                    ai_x.switch_starting_mark();
-                    ai_o.switch_starting_mark();
-                }
+            self.starting_player = Mark::O;
+            // Stateful AIs derive the starting mark from the board they are given.
+            if !matches!(self.mode, GameMode::LocalPvP) {
+                self.ai_play();
             }
-            self.ai_play();
         }
     }
 
     /// Resets the game to initial state while keeping the same mode.
+    ///
+    /// Local PvP rounds alternate the mark moving first; other modes start with X.
     pub fn reset_game(&mut self) {
+        let first_player = match self.mode {
+            GameMode::LocalPvP => self.starting_player.switch(),
+            _ => Mark::X,
+        };
+        self.reset_game_with_first_player(first_player);
+    }
+
+    /// Resets the game to initial state with `first_player` moving first.
+    ///
+    /// Only Local PvP games can start with O this way; players against the AI
+    /// let it move first with [`Self::play_second`] instead.
+    pub fn reset_game_with_first_player(&mut self, first_player: Mark) {
+        self.restart(new_seed(), first_player);
+    }
+
+    /// Resets the game and reuses the seed and first player of the game just
+    /// played, so AI players repeat their moves exactly.
+    pub fn restart_same_seed(&mut self) {
+        self.restart(self.seed, self.starting_player);
+    }
+
+    /// Starts a Local PvP rematch in which the two players trade marks.
+    ///
+    /// X moves first as usual, so the player who had O now opens.
+    ///
+    /// # Returns
+    /// True if the rematch was started, false in any other mode.
+    pub fn rematch_swap_sides(&mut self) -> bool {
+        if self.mode != GameMode::LocalPvP {
+            return false;
+        }
+        self.players_swapped = !self.players_swapped;
+        self.restart(new_seed(), Mark::X);
+        true
+    }
+
+    fn restart(&mut self, seed: u64, first_player: Mark) {
         if matches!(self.mode, GameMode::OnlinePvP(_)) {
             return;
         }
-        self.board = SmallBoard::new();
-        self.active_player = Mark::X;
+        let first_player = match self.mode {
+            GameMode::LocalPvP => first_player,
+            _ => Mark::X,
+        };
+        self.seed = seed;
+        seed_rng(seed);
+        self.board = SmallBoard::with_rules(self.board.rules());
+        self.active_player = first_player;
         self.turn = 0;
+        self.draw_offer = None;
         self.selected.row = 0;
         self.selected.col = 0;
-        match &mut self.mode {
-            GameMode::LocalPvP | GameMode::OnlinePvP(_) => return,
-            GameMode::PvE(ai) => ai.reset(),
-            GameMode::EvE(ai_x, ai_o) => {
-                ai_x.reset();
-                ai_o.reset();
+        self.evaluations.clear();
+        self.aborted = None;
+        self.clock = self.clock.map(|clock| GameClock::new(clock.budget));
+        self.flagged = None;
+        self.ai_moves = 0;
+        self.result_taken = false;
+        self.hint = None;
+        self.ranking = None;
+        self.placement = None;
+        self.missed_move = None;
+        self.hint_cell = None;
+        self.starting_player = first_player;
+        self.history.clear();
+        self.redo_stack.clear();
+        self.analysis = None;
+        reset_ais(&mut self.mode);
+        if self.mode == GameMode::Solitaire {
+            self.load_puzzle(generate_puzzle());
+        }
+        self.play_ai_opening();
+    }
+}
+
+/// Game of n-in-a-row played on a [`GenericBoard`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LargeBoard {
+    /// Four in a row on a [`GOMOKU_SIZE`] board, with a freely moving selection.
+    Gomoku,
+    /// Four in a row on a [`FOUR_BY_FOUR_SIZE`] board, with the selection
+    /// skipping taken cells like on the classic board.
+    FourByFour,
+}
+
+impl LargeBoard {
+    /// Returns the name shown in the mode line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LargeBoard::Gomoku => "Gomoku",
+            LargeBoard::FourByFour => "Tic Tac Toe 4x4",
+        }
+    }
+
+    /// Returns an empty board of this game.
+    fn new_board(&self) -> GenericBoard {
+        match self {
+            LargeBoard::Gomoku => GenericBoard::new(GOMOKU_SIZE, GOMOKU_WIN_LEN),
+            LargeBoard::FourByFour => GenericBoard::new(FOUR_BY_FOUR_SIZE, FOUR_BY_FOUR_SIZE),
+        }
+    }
+}
+
+/// Local two-player game of n-in-a-row on a board larger than the classic one.
+pub struct GamePlayGomoku {
+    /// Which n-in-a-row game is played.
+    pub kind: LargeBoard,
+    /// Current board state.
+    pub board: GenericBoard,
+    /// Mark whose turn is currently active.
+    pub active_player: Mark,
+    /// Number of moves played in the current round.
+    pub turn: u32,
+    /// Currently selected board position.
+    pub selected: Position,
+}
+
+impl Default for GamePlayGomoku {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GamePlayGomoku {
+    /// Creates a new game on an empty [`GOMOKU_SIZE`] board.
+    pub fn new() -> Self {
+        Self::with_kind(LargeBoard::Gomoku)
+    }
+
+    /// Creates a new game of `kind` on an empty board.
+    ///
+    /// Gomoku starts with the center selected, while the 4x4 board starts
+    /// with its first cell selected like the classic board.
+    pub fn with_kind(kind: LargeBoard) -> Self {
+        let board = kind.new_board();
+        let selected = match kind {
+            LargeBoard::Gomoku => Position {
+                row: board.size / 2,
+                col: board.size / 2,
+            },
+            LargeBoard::FourByFour => Position { row: 0, col: 0 },
+        };
+        Self {
+            kind,
+            board,
+            active_player: Mark::X,
+            turn: 0,
+            selected,
+        }
+    }
+
+    /// Moves selection left, wrapping to the rightmost column.
+    pub fn input_left(&mut self) {
+        match self.kind {
+            LargeBoard::Gomoku => {
+                self.selected.col = (self.selected.col + self.board.size - 1) % self.board.size;
+            }
+            LargeBoard::FourByFour => move_selection_left_playable(&self.board, &mut self.selected),
+        }
+    }
+
+    /// Moves selection right, wrapping to the leftmost column.
+    pub fn input_right(&mut self) {
+        match self.kind {
+            LargeBoard::Gomoku => {
+                self.selected.col = (self.selected.col + 1) % self.board.size;
+            }
+            LargeBoard::FourByFour => {
+                move_selection_right_playable(&self.board, &mut self.selected)
+            }
+        }
+    }
+
+    /// Moves selection up, wrapping to the bottom row.
+    pub fn input_up(&mut self) {
+        match self.kind {
+            LargeBoard::Gomoku => {
+                self.selected.row = (self.selected.row + self.board.size - 1) % self.board.size;
+            }
+            LargeBoard::FourByFour => move_selection_up_playable(&self.board, &mut self.selected),
+        }
+    }
+
+    /// Moves selection down, wrapping to the top row.
+    pub fn input_down(&mut self) {
+        match self.kind {
+            LargeBoard::Gomoku => {
+                self.selected.row = (self.selected.row + 1) % self.board.size;
             }
+            LargeBoard::FourByFour => move_selection_down_playable(&self.board, &mut self.selected),
+        }
+    }
+
+    /// Attempts to make a move at the currently selected position.
+    ///
+    /// If the game is over or the cell is occupied, does nothing.
+    ///
+    /// # Returns
+    /// True if a move was played.
+    pub fn play_move(&mut self) -> bool {
+        if !self.board.is_playable(self.selected.row, self.selected.col) {
+            return false;
+        }
+        self.board
+            .make_move(self.selected.row, self.selected.col, self.active_player);
+        self.turn += 1;
+        self.active_player = self.active_player.switch();
+        if self.kind == LargeBoard::FourByFour && self.board.state == GameState::Playing {
+            reset_position(&self.board, &mut self.selected);
         }
+        true
+    }
+
+    /// Resets the game to an empty board of the same kind.
+    pub fn reset_game(&mut self) {
+        *self = Self::with_kind(self.kind);
     }
 }
 
+/// Where a move in a cell sends the opponent on an Ultimate board.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Destination {
+    /// The matching small board is still open and the opponent must play there.
+    Open,
+    /// The matching small board is closed and the opponent may play anywhere.
+    Closed,
+}
+
 /// Main tic-tac-toe gameplay scene containing the board state and game logic.
 pub struct GamePlayUTT {
     /// Current Ultimate board state.
@@ -427,27 +1744,171 @@ pub struct GamePlayUTT {
     pub selected_board: Position,
     /// Selected cell, or `None` while choosing a small board.
     pub selected_cell: Option<Position>,
+    /// Whether each local player has their own keys, X on WASD and O on the arrows.
+    pub split_keys: bool,
+    /// Whether coaching tips are displayed.
+    pub show_tips: bool,
+    /// Strength of the move suggested alongside the tips.
+    pub hint_strength: HintStrength,
+    hint: Option<(u32, Move)>,
+    /// Whether the evaluation graph is displayed.
+    pub show_graph: bool,
+    /// Whether the analysis bar with the evaluation of the position is displayed.
+    pub show_analysis: bool,
+    /// Whether coordinate labels are drawn around the board.
+    pub show_coordinates: bool,
+    /// Whether the game is only watched: player moves are ignored while the
+    /// selection can still move, and AI vs AI games still advance.
+    pub read_only: bool,
+    /// Player whose draw offer awaits the opponent's answer, until the next move.
+    pub draw_offer: Option<Mark>,
+    /// Whether the last move sent the player on turn to a finished board, so
+    /// they may choose any board. Cleared by the next move.
+    pub free_move: bool,
+    /// Whether cells are colored by the [`Destination`] they send the opponent to.
+    pub show_destinations: bool,
+    /// Whether won small boards are drawn as one large mark instead of their cells.
+    pub show_big_marks: bool,
+    /// Estimated winning chance of X after each evaluated AI move.
+    pub evaluations: Vec<f32>,
+    /// Set when an AI-vs-AI game is stopped for exceeding its move cap.
+    pub aborted: Option<MoveCapExceeded>,
+    /// Seed the AI random number generator was started from for this game.
+    pub seed: u64,
+    result_taken: bool,
+    ai_moves: u32,
     starting_player: Mark,
     local_rematch_ready: bool,
     remote_rematch_ready: bool,
+    history: Vec<Move>,
 }
 
 impl GamePlayUTT {
     /// Creates a new game with the specified mode.
     ///
-    /// For PvE mode, initializes an AI opponent playing as O.
-    pub fn new(mode: GameMode) -> Self {
-        Self {
+    /// In PvE mode, an AI playing X makes the opening move right away.
+    pub fn new(mut mode: GameMode) -> Self {
+        // Rebuild any AI state from the seed so a restart can reproduce it.
+        let seed = new_seed();
+        seed_rng(seed);
+        reset_ais(&mut mode);
+        let mut game = Self {
             big_board: BigBoard::new(),
             active_player: Mark::X,
             turn: 0,
             mode,
             selected_board: Position { row: 0, col: 0 },
             selected_cell: None,
+            split_keys: false,
+            show_tips: false,
+            hint_strength: HintStrength::default(),
+            hint: None,
+            show_graph: false,
+            show_analysis: false,
+            show_coordinates: false,
+            read_only: false,
+            draw_offer: None,
+            free_move: false,
+            show_destinations: false,
+            show_big_marks: false,
+            evaluations: Vec::new(),
+            aborted: None,
+            ai_moves: 0,
+            seed,
+            result_taken: false,
             starting_player: Mark::X,
             local_rematch_ready: false,
             remote_rematch_ready: false,
+            history: Vec::new(),
+        };
+        game.play_ai_opening();
+        game
+    }
+
+    /// Creates a new game with the specified mode and mark moving first.
+    pub fn with_first_player(mode: GameMode, first_player: Mark) -> Self {
+        let mut game = Self::new(mode);
+        if first_player == Mark::O {
+            game.play_second();
+        }
+        game
+    }
+
+    /// Resumes a saved game with the given players.
+    ///
+    /// # Arguments
+    /// * `saved` - The saved game state
+    /// * `mode` - Players rebuilt from the saved mode
+    pub fn resume(saved: SavedUltimate, mode: GameMode) -> Self {
+        let mut game = Self::new(mode);
+        game.big_board = saved.big_board;
+        game.active_player = saved.active_player;
+        game.starting_player = saved.starting_player;
+        game.turn = saved.turn;
+        game.selected_board = saved.selected_board;
+        game.selected_cell = saved.selected_cell;
+        game
+    }
+
+    /// Returns the moves of the game as a transcript such as `X:0,0/1,1 O:1,1/0,2`.
+    ///
+    /// Moves played before the game was resumed from a save are not included.
+    pub fn transcript(&self) -> String {
+        write_transcript(self.starting_player, &self.history)
+    }
+
+    /// Returns the moves of the game as a record for the game log.
+    ///
+    /// # Returns
+    /// The record, or None if moves were played before the game was resumed
+    /// from a save, since the record could not rebuild the board.
+    pub fn game_record(&self) -> Option<GameRecord> {
+        let marks: usize = (0..9)
+            .map(|board| {
+                let board = self.big_board.get_board(board / 3, board % 3);
+                (0..9)
+                    .filter(|cell| board.get(cell / 3, cell % 3).is_some())
+                    .count()
+            })
+            .sum();
+        (marks == self.history.len()).then(|| GameRecord {
+            variant: GameVariant::Ultimate,
+            rules: RuleSet::Standard,
+            first_player: self.starting_player,
+            moves: self.history.clone(),
+        })
+    }
+
+    /// Replays a transcript written by [`GamePlayUTT::transcript`] into a fresh local game.
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if the transcript is malformed or a move is illegal.
+    pub fn from_transcript(transcript: &str) -> Result<Self, NotationError> {
+        let record = GameRecord::from_transcript(GameVariant::Ultimate, transcript)?;
+        record.board_at(record.moves.len())?;
+        let mut game = Self::with_first_player(GameMode::LocalPvP, record.first_player);
+        for mv in &record.moves {
+            let (board_row, board_col, cell_row, cell_col) = mv.unwrap_ultimate();
+            game.apply_move(board_row, board_col, cell_row, cell_col)?;
         }
+        game.reset_selection();
+        Ok(game)
+    }
+
+    /// Captures the game so it can be written to disk.
+    ///
+    /// # Returns
+    /// The saved state, or None for online games.
+    pub fn snapshot(&self) -> Option<SavedGame> {
+        Some(SavedGame::Ultimate(SavedUltimate {
+            big_board: self.big_board,
+            active_player: self.active_player,
+            starting_player: self.starting_player,
+            turn: self.turn,
+            mode: SavedMode::from_mode(&self.mode)?,
+            selected_board: self.selected_board,
+            selected_cell: self.selected_cell,
+        }))
     }
 
     /// Apply move function to selected cell if it exists, else apply it to selected board
@@ -520,11 +1981,15 @@ impl GamePlayUTT {
 
     /// Attempts to make a move at the currently selected board and cell.
     ///
-    /// Returns false if the game is over, no cell is selected, or it is the remote player's turn.
+    /// Returns false if the game is over or [`read_only`](Self::read_only), no
+    /// cell is selected, or it is the remote player's turn.
     /// After a valid move, checks for win/draw conditions and switches players.
     /// In PvE mode, triggers the AI to make its move.
     pub fn play_move(&mut self) -> bool {
-        if self.big_board.state != GameState::Playing {
+        if self.big_board.state != GameState::Playing || self.aborted.is_some() {
+            return false;
+        }
+        if self.read_only && !matches!(self.mode, GameMode::EvE(_, _)) {
             return false;
         }
 
@@ -535,12 +2000,15 @@ impl GamePlayUTT {
                 let Some(selected_cell) = self.selected_cell else {
                     return false;
                 };
-                self.apply_move(
+                let played = self.apply_move(
                     self.selected_board.row,
                     self.selected_board.col,
                     selected_cell.row,
                     selected_cell.col,
                 );
+                if played.is_err() {
+                    return false;
+                }
 
                 if self.big_board.state != GameState::Playing {
                     return true;
@@ -568,32 +2036,105 @@ impl GamePlayUTT {
         };
         if self.big_board.state != GameState::Playing
             || self.active_player == local_mark
-            || board_row >= 3
-            || board_col >= 3
-            || cell_row >= 3
-            || cell_col >= 3
             || self
-                .big_board
-                .active_board
-                .is_some_and(|active_board| active_board != (board_row, board_col))
-            || !self
-                .big_board
-                .get_board(board_row, board_col)
-                .is_playable(cell_row, cell_col)
+                .apply_move(board_row, board_col, cell_row, cell_col)
+                .is_err()
         {
             return false;
         }
 
-        self.apply_move(board_row, board_col, cell_row, cell_col);
         self.reset_selection();
         true
     }
 
-    fn apply_move(&mut self, board_row: usize, board_col: usize, cell_row: usize, cell_col: usize) {
-        self.big_board
-            .make_move(board_row, board_col, cell_row, cell_col, self.active_player);
+    /// Plays a move for the active player and records it.
+    ///
+    /// # Errors
+    /// Returns a [`MoveError`], leaving the game unchanged, if the move is illegal.
+    fn apply_move(
+        &mut self,
+        board_row: usize,
+        board_col: usize,
+        cell_row: usize,
+        cell_col: usize,
+    ) -> Result<(), MoveError> {
+        self.big_board.try_make_move(
+            board_row,
+            board_col,
+            cell_row,
+            cell_col,
+            self.active_player,
+        )?;
+        self.history
+            .push(Move::Ultimate(board_row, board_col, cell_row, cell_col));
         self.turn += 1;
         self.active_player = self.active_player.switch();
+        self.draw_offer = None;
+        self.update_free_move(cell_row, cell_col);
+        Ok(())
+    }
+
+    /// Records whether the move in cell (`cell_row`, `cell_col`) sent the
+    /// next player to a finished board while the game goes on.
+    fn update_free_move(&mut self, cell_row: usize, cell_col: usize) {
+        self.free_move = self.big_board.state == GameState::Playing
+            && self.big_board.sub_state(cell_row, cell_col) != GameState::Playing;
+    }
+
+    /// Resigns a local game on behalf of the active player, awarding the opponent the win.
+    ///
+    /// Online games are conceded with [`Self::concede_online`] instead, and
+    /// AI-vs-AI and Solitaire games have no player to resign.
+    ///
+    /// # Returns
+    /// True if the game was resigned, false if it was already over.
+    pub fn resign(&mut self) -> bool {
+        if !matches!(self.mode, GameMode::LocalPvP | GameMode::PvE(_))
+            || self.big_board.state != GameState::Playing
+        {
+            return false;
+        }
+
+        self.big_board.state = GameState::Won(self.active_player.switch());
+        true
+    }
+
+    /// Offers a draw on behalf of the active player in a Local PvP game.
+    ///
+    /// The offer stands until the opponent answers it or the next move is played.
+    ///
+    /// # Returns
+    /// True if the offer was made, false if the game is over, not a Local PvP
+    /// game, or an offer is already pending.
+    pub fn offer_draw(&mut self) -> bool {
+        if self.mode != GameMode::LocalPvP
+            || self.big_board.state != GameState::Playing
+            || self.draw_offer.is_some()
+        {
+            return false;
+        }
+        self.draw_offer = Some(self.active_player);
+        true
+    }
+
+    /// Accepts the pending draw offer, ending the game as a draw.
+    ///
+    /// # Returns
+    /// True if an offer was pending.
+    pub fn accept_draw(&mut self) -> bool {
+        if self.draw_offer.take().is_none() {
+            return false;
+        }
+        self.big_board.state = GameState::Draw;
+        true
+    }
+
+    /// Declines the pending draw offer and lets the game go on.
+    ///
+    /// # Returns
+    /// True if an offer was pending.
+    pub fn decline_draw(&mut self) -> bool {
+        self.draw_offer.take().is_some()
     }
 
     /// Concedes an active online round and awards the opponent the win.
@@ -689,32 +2230,184 @@ impl GamePlayUTT {
         self.local_rematch_ready
     }
 
-    fn start_rematch_if_ready(&mut self) {
-        if self.local_rematch_ready && self.remote_rematch_ready {
-            self.start_online_round(self.starting_player.switch());
+    /// Returns whether a local human player is expected to move next.
+    pub fn is_local_turn(&self) -> bool {
+        match self.mode {
+            GameMode::EvE(_, _) => false,
+            GameMode::OnlinePvP(local_mark) => local_mark == self.active_player,
+            GameMode::PvE(_) | GameMode::LocalPvP | GameMode::Solitaire => true,
         }
     }
 
-    fn start_online_round(&mut self, starting_player: Mark) {
-        self.big_board = BigBoard::new();
-        self.active_player = starting_player;
-        self.turn = 0;
-        self.selected_board = Position { row: 0, col: 0 };
-        self.selected_cell = None;
-        self.starting_player = starting_player;
-        self.local_rematch_ready = false;
-        self.remote_rematch_ready = false;
+    /// Toggles the display of coaching tips.
+    pub fn toggle_tips(&mut self) {
+        self.show_tips = !self.show_tips;
     }
 
-    fn reset_selection(&mut self) {
-        // If next action is constrained to active board, select it by default
-        if let Some((active_row, active_col)) = self.big_board.active_board {
-            self.selected_board = Position {
-                row: active_row,
-                col: active_col,
-            };
-            self.selected_cell = Some(Position { row: 0, col: 0 });
-            reset_position(
+    /// Switches between weak and perfect hints.
+    pub fn toggle_hint_strength(&mut self) {
+        self.hint_strength = self.hint_strength.toggle();
+        self.hint = None;
+    }
+
+    /// Computes the suggested move for the current turn if it is missing.
+    ///
+    /// Hints are only computed while tips are shown on a local turn.
+    pub fn update_hint(&mut self) {
+        if !self.show_tips || self.big_board.state != GameState::Playing || !self.is_local_turn() {
+            return;
+        }
+        if self.hint.is_none_or(|(turn, _)| turn != self.turn) {
+            let mv = ultimate_hint(&self.big_board, self.active_player, self.hint_strength);
+            self.hint = Some((self.turn, mv));
+        }
+    }
+
+    /// Returns the suggested move for the current turn, if it was computed.
+    pub fn hint(&self) -> Option<Move> {
+        if !self.show_tips || self.big_board.state != GameState::Playing || !self.is_local_turn() {
+            return None;
+        }
+        self.hint
+            .filter(|(turn, _)| *turn == self.turn)
+            .map(|(_, mv)| mv)
+    }
+
+    /// Toggles the display of the evaluation graph.
+    pub fn toggle_graph(&mut self) {
+        self.show_graph = !self.show_graph;
+    }
+
+    /// Toggles the display of the analysis bar.
+    pub fn toggle_analysis(&mut self) {
+        self.show_analysis = !self.show_analysis;
+    }
+
+    /// Toggles the coordinate labels around the board.
+    pub fn toggle_coordinates(&mut self) {
+        self.show_coordinates = !self.show_coordinates;
+    }
+
+    /// Toggles [`Self::read_only`].
+    pub fn toggle_read_only(&mut self) {
+        self.read_only = !self.read_only;
+    }
+
+    /// Evaluates the position from X's point of view, between -1.0 and 1.0.
+    ///
+    /// While the game is played, the estimate of the MCTS AI that moved last
+    /// is used when there is one, else the board is scored by [`evaluate`].
+    pub fn analysis(&self) -> f32 {
+        match self.big_board.state {
+            GameState::Playing => mcts_advantage(&self.mode, self.active_player)
+                .unwrap_or_else(|| evaluate(&self.big_board)),
+            _ => evaluate(&self.big_board),
+        }
+    }
+
+    /// Toggles coloring cells by the board they send the opponent to.
+    pub fn toggle_destinations(&mut self) {
+        self.show_destinations = !self.show_destinations;
+    }
+
+    /// Toggles drawing won small boards as one large mark.
+    pub fn toggle_big_marks(&mut self) {
+        self.show_big_marks = !self.show_big_marks;
+    }
+
+    /// Classifies where a move in a cell of the selected board sends the opponent.
+    ///
+    /// The move is played on a copy of the board, so a move that closes its own
+    /// small board is reported as [`Destination::Closed`].
+    ///
+    /// # Returns
+    /// The destination, or None if the cell cannot be played.
+    pub fn destination(&self, cell_row: usize, cell_col: usize) -> Option<Destination> {
+        let Position { row, col } = self.selected_board;
+        let mv = Move::Ultimate(row, col, cell_row, cell_col);
+        if !self.big_board.get_possible_moves().contains(&mv) {
+            return None;
+        }
+        let mut board = self.big_board;
+        board.play(&mv, self.active_player);
+        match board.sub_state(cell_row, cell_col) {
+            GameState::Playing => Some(Destination::Open),
+            _ => Some(Destination::Closed),
+        }
+    }
+
+    /// Returns the small board the selected cell would send the opponent to.
+    ///
+    /// # Returns
+    /// The position of the board, or None if no cell is selected, the cell
+    /// cannot be played or the board is closed and the opponent may play anywhere.
+    pub fn target_board(&self) -> Option<Position> {
+        let cell = self.selected_cell?;
+        match self.destination(cell.row, cell.col)? {
+            Destination::Open => Some(cell),
+            Destination::Closed => None,
+        }
+    }
+
+    /// Returns the result of the finished game, only the first time it is called.
+    ///
+    /// Returns None while the game is in progress or once the result was taken.
+    pub fn take_result(&mut self) -> Option<GameResult> {
+        if self.big_board.state == GameState::Playing || self.result_taken {
+            return None;
+        }
+        self.result_taken = true;
+        Some(GameResult {
+            variant: GameVariant::Ultimate,
+            first_player: self.starting_player,
+            outcome: self.big_board.state,
+        })
+    }
+
+    /// Returns the coaching tip for the active player on the selected small board.
+    ///
+    /// Tips are only given once a small board has been chosen and tips are enabled.
+    pub fn tip(&self) -> Option<Tip> {
+        if !self.show_tips || self.big_board.state != GameState::Playing || !self.is_local_turn() {
+            return None;
+        }
+        self.selected_cell?;
+        let small_board = self
+            .big_board
+            .get_board(self.selected_board.row, self.selected_board.col);
+        select_tip(small_board, self.active_player)
+    }
+
+    fn start_rematch_if_ready(&mut self) {
+        if self.local_rematch_ready && self.remote_rematch_ready {
+            self.start_online_round(self.starting_player.switch());
+        }
+    }
+
+    fn start_online_round(&mut self, starting_player: Mark) {
+        self.big_board = BigBoard::new();
+        self.free_move = false;
+        self.active_player = starting_player;
+        self.turn = 0;
+        self.selected_board = Position { row: 0, col: 0 };
+        self.selected_cell = None;
+        self.starting_player = starting_player;
+        self.result_taken = false;
+        self.hint = None;
+        self.history.clear();
+        self.local_rematch_ready = false;
+        self.remote_rematch_ready = false;
+    }
+
+    fn reset_selection(&mut self) {
+        // If next action is constrained to active board, select it by default
+        if let Some((active_row, active_col)) = self.big_board.active_board {
+            self.selected_board = Position {
+                row: active_row,
+                col: active_col,
+            };
+            self.selected_cell = Some(Position { row: 0, col: 0 });
+            reset_position(
                 self.big_board.get_board(active_row, active_col),
                 self.selected_cell.as_mut().unwrap(),
             );
@@ -725,32 +2418,78 @@ impl GamePlayUTT {
     }
 
     /// Resets the game to initial state while keeping the same mode.
+    ///
+    /// Local PvP rounds alternate the mark moving first; other modes start with X.
     pub fn reset_game(&mut self) {
+        let first_player = match self.mode {
+            GameMode::LocalPvP => self.starting_player.switch(),
+            _ => Mark::X,
+        };
+        self.reset_game_with_first_player(first_player);
+    }
+
+    /// Resets the game to initial state with `first_player` moving first.
+    ///
+    /// Only Local PvP games can start with O this way; players against the AI
+    /// let it move first with [`Self::play_second`] instead.
+    pub fn reset_game_with_first_player(&mut self, first_player: Mark) {
+        self.restart(new_seed(), first_player);
+    }
+
+    /// Resets the game and reuses the seed and first player of the game just
+    /// played, so AI players repeat their moves exactly.
+    pub fn restart_same_seed(&mut self) {
+        self.restart(self.seed, self.starting_player);
+    }
+
+    fn restart(&mut self, seed: u64, first_player: Mark) {
         if matches!(self.mode, GameMode::OnlinePvP(_)) {
             return;
         }
+        let first_player = match self.mode {
+            GameMode::LocalPvP => first_player,
+            _ => Mark::X,
+        };
+        self.seed = seed;
+        seed_rng(seed);
         self.big_board = BigBoard::new();
-        self.active_player = Mark::X;
+        self.active_player = first_player;
         self.turn = 0;
+        self.draw_offer = None;
+        self.free_move = false;
         self.selected_board = Position { row: 0, col: 0 };
         self.selected_cell = None;
-        match &mut self.mode {
-            GameMode::LocalPvP | GameMode::OnlinePvP(_) => return,
-            GameMode::PvE(ai) => ai.reset(),
-            GameMode::EvE(ai_x, ai_o) => {
-                ai_x.reset();
-                ai_o.reset();
-            }
+        self.evaluations.clear();
+        self.aborted = None;
+        self.ai_moves = 0;
+        self.result_taken = false;
+        self.hint = None;
+        self.starting_player = first_player;
+        self.history.clear();
+        reset_ais(&mut self.mode);
+        self.play_ai_opening();
+    }
+
+    /// Lets the AI play X move first against a human playing O.
+    fn play_ai_opening(&mut self) {
+        if let GameMode::PvE(ai) = &self.mode
+            && ai.get_mark() == self.active_player
+            && self.turn == 0
+        {
+            self.ai_play();
         }
     }
 
     /// Executes the AI's turn in PvE and EvE modes.
     fn ai_play(&mut self) {
         match &mut self.mode {
-            GameMode::LocalPvP | GameMode::OnlinePvP(_) => return,
+            GameMode::LocalPvP | GameMode::OnlinePvP(_) | GameMode::Solitaire => (),
             GameMode::PvE(ai) => {
                 let mv = ai.choose_move_utt(&self.big_board);
                 self.big_board.play(&mv, ai.get_mark());
+                self.history.push(mv);
+                let (_, _, cell_row, cell_col) = mv.unwrap_ultimate();
+                self.update_free_move(cell_row, cell_col);
 
                 self.turn += 1;
                 self.active_player = self.active_player.switch();
@@ -758,11 +2497,30 @@ impl GamePlayUTT {
                 self.reset_selection();
             }
             GameMode::EvE(ai_x, ai_o) => {
+                if self.ai_moves >= MAX_MOVES_ULTIMATE {
+                    self.aborted = Some(MoveCapExceeded {
+                        max_moves: MAX_MOVES_ULTIMATE,
+                    });
+                    return;
+                }
+                self.ai_moves += 1;
+
                 let mv = match self.active_player {
                     Mark::X => ai_x.choose_move_utt(&self.big_board),
                     Mark::O => ai_o.choose_move_utt(&self.big_board),
                 };
+                if !self.big_board.get_possible_moves().contains(&mv) {
+                    return;
+                }
                 self.big_board.play(&mv, self.active_player);
+                self.history.push(mv);
+                let moving_ai = match self.active_player {
+                    Mark::X => ai_x,
+                    Mark::O => ai_o,
+                };
+                record_evaluation(&mut self.evaluations, moving_ai);
+                let (_, _, cell_row, cell_col) = mv.unwrap_ultimate();
+                self.update_free_move(cell_row, cell_col);
 
                 self.turn += 1;
                 self.active_player = self.active_player.switch();
@@ -777,19 +2535,57 @@ impl GamePlayUTT {
         }
         if self.big_board.state == GameState::Playing && self.turn == 0 {
             self.active_player = Mark::O;
-            match &mut self.mode {
-                GameMode::LocalPvP | GameMode::OnlinePvP(_) => return,
-                GameMode::PvE(ai) => ai.switch_starting_mark(),
-                GameMode::EvE(ai_x, ai_o) => {
-                    ai_x.switch_starting_mark();
-                    ai_o.switch_starting_mark();
-                }
+            self.starting_player = Mark::O;
+            // Stateful AIs derive the starting mark from the board they are given.
+            if !matches!(self.mode, GameMode::LocalPvP) {
+                self.ai_play();
             }
-            self.ai_play();
         }
     }
 }
 
+/// Resets every AI player of `mode`.
+fn reset_ais(mode: &mut GameMode) {
+    match mode {
+        GameMode::LocalPvP | GameMode::OnlinePvP(_) | GameMode::Solitaire => (),
+        GameMode::PvE(ai) => ai.reset(),
+        GameMode::EvE(ai_x, ai_o) => {
+            ai_x.reset();
+            ai_o.reset();
+        }
+    }
+}
+
+/// Stores the winning chance of X estimated by the AI that just moved, if any.
+fn record_evaluation(evaluations: &mut Vec<f32>, ai: &AI) {
+    if let Some(chance_x) = chance_for_x(ai) {
+        evaluations.push(chance_x);
+    }
+}
+
+/// Returns the winning chance of X estimated by the last move of `ai`, if any.
+fn chance_for_x(ai: &AI) -> Option<f32> {
+    let chance = ai.evaluation()?;
+    Some(match ai.get_mark() {
+        Mark::X => chance,
+        Mark::O => 1.0 - chance,
+    })
+}
+
+/// Returns X's advantage, between -1.0 and 1.0, estimated by the AI that
+/// moved last, or None if no AI of the game has made an estimate.
+fn mcts_advantage(mode: &GameMode, active_player: Mark) -> Option<f32> {
+    let ai = match mode {
+        GameMode::PvE(ai) => ai,
+        GameMode::EvE(ai_x, ai_o) => match active_player {
+            Mark::X => ai_o,
+            Mark::O => ai_x,
+        },
+        GameMode::LocalPvP | GameMode::OnlinePvP(_) | GameMode::Solitaire => return None,
+    };
+    chance_for_x(ai).map(|chance| 2.0 * chance - 1.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -803,6 +2599,14 @@ mod tests {
         assert_eq!(menu.get_selected(), "Online PvP");
     }
 
+    #[test]
+    fn test_minimax_opponent_plays_with_minimax_ai() {
+        let ai = Opponent::Minimax.ai(GameVariant::Classic, Mark::O);
+        assert!(matches!(ai, AI::Minimax(_)));
+        assert_eq!(ai.name(), "Minimax");
+        assert_eq!(ai.get_mark(), Mark::O);
+    }
+
     #[test]
     fn test_menu_move_down() {
         let mut menu = Menu::new(TTT_MENU_OPTIONS.to_vec());
@@ -815,7 +2619,7 @@ mod tests {
     fn test_menu_move_up_wraps() {
         let mut menu = Menu::new(MAIN_MENU_OPTIONS.to_vec());
         menu.move_up();
-        assert_eq!(menu.selected_option, 2);
+        assert_eq!(menu.selected_option, 7);
         assert_eq!(menu.get_selected(), "Quit");
     }
 
@@ -852,6 +2656,136 @@ mod tests {
         assert_eq!(game.turn, 1);
     }
 
+    fn play_at(game: &mut GamePlayTTT, row: usize, col: usize) {
+        game.selected = Position { row, col };
+        assert!(game.play_move());
+    }
+
+    #[test]
+    fn test_undo_after_win_reopens_game() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            play_at(&mut game, row, col);
+        }
+        assert_eq!(game.board.state, GameState::Won(Mark::X));
+
+        assert!(game.undo());
+        assert_eq!(game.board.state, GameState::Playing);
+        assert_eq!(game.board.get(0, 2), None);
+        assert_eq!(game.active_player, Mark::X);
+        assert_eq!(game.turn, 4);
+
+        assert!(game.redo());
+        assert_eq!(game.board.state, GameState::Won(Mark::X));
+        assert_eq!(game.turn, 5);
+        assert!(!game.redo());
+    }
+
+    #[test]
+    fn test_fresh_move_invalidates_redo() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        play_at(&mut game, 1, 1);
+        play_at(&mut game, 0, 0);
+        assert!(game.undo());
+        assert!(game.undo());
+        assert!(!game.undo());
+        assert_eq!(game.turn, 0);
+
+        play_at(&mut game, 2, 2);
+        assert!(!game.redo());
+        assert_eq!(game.board.get(1, 1), None);
+        assert_eq!(game.board.get(2, 2), Some(Mark::X));
+    }
+
+    #[test]
+    fn test_undo_against_ai_takes_back_its_reply() {
+        use crate::ai::simple::SimpleAi;
+
+        let mut game = GamePlayTTT::new(GameMode::PvE(AI::Medium(SimpleAi::new(Mark::O))));
+        play_at(&mut game, 1, 1);
+        assert_eq!(game.turn, 2);
+
+        assert!(game.undo());
+        assert_eq!(game.board, SmallBoard::new());
+        assert_eq!(game.active_player, Mark::X);
+        assert_eq!(game.turn, 0);
+        assert!(!game.undo());
+
+        assert!(game.redo());
+        assert_eq!(game.turn, 2);
+        assert_eq!(game.active_player, Mark::X);
+        assert_eq!(game.board.get(1, 1), Some(Mark::X));
+    }
+
+    #[test]
+    fn test_leaving_analysis_restores_the_real_position() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        play_at(&mut game, 1, 1);
+        play_at(&mut game, 0, 0);
+        let board = game.board;
+
+        assert!(game.enter_analysis());
+        assert!(!game.enter_analysis());
+        for (row, col) in [(0, 1), (2, 2), (2, 1)] {
+            play_at(&mut game, row, col);
+        }
+        assert_eq!(game.board.state, GameState::Won(Mark::X));
+        assert_eq!(game.take_result(), None);
+
+        assert!(game.exit_analysis());
+        assert!(!game.is_analyzing());
+        assert_eq!(game.board, board);
+        assert_eq!(game.active_player, Mark::X);
+        assert_eq!(game.turn, 2);
+        assert!(!game.exit_analysis());
+
+        // The real game goes on, with its undo history intact.
+        assert!(game.undo());
+        assert_eq!(game.board.get(0, 0), None);
+        assert_eq!(game.board.get(1, 1), Some(Mark::X));
+    }
+
+    #[test]
+    fn test_ai_does_not_answer_during_analysis() {
+        use crate::ai::simple::SimpleAi;
+
+        let mut game = GamePlayTTT::new(GameMode::PvE(AI::Medium(SimpleAi::new(Mark::O))));
+        play_at(&mut game, 1, 1);
+        let board = game.board;
+        // The AI replies at random, so the analysis plays on cells left free.
+        let free: Vec<(usize, usize)> = (0..9)
+            .map(|index| (index / 3, index % 3))
+            .filter(|&(row, col)| board.get(row, col).is_none())
+            .collect();
+
+        assert!(game.enter_analysis());
+        play_at(&mut game, free[0].0, free[0].1);
+        assert_eq!(game.turn, 3);
+        assert_eq!(game.active_player, Mark::O);
+        play_at(&mut game, free[1].0, free[1].1);
+        assert_eq!(game.board.get(free[1].0, free[1].1), Some(Mark::O));
+
+        assert!(game.exit_analysis());
+        assert_eq!(game.board, board);
+        assert_eq!(game.active_player, Mark::X);
+        assert_eq!(game.turn, 2);
+    }
+
+    #[test]
+    fn test_analysis_is_only_for_local_games() {
+        let mut game = GamePlayTTT::new(GameMode::OnlinePvP(Mark::X));
+        assert!(!game.enter_analysis());
+        let mut game = GamePlayTTT::new(GameMode::Solitaire);
+        assert!(!game.enter_analysis());
+    }
+
+    #[test]
+    fn test_undo_is_unavailable_online() {
+        let mut game = GamePlayTTT::new(GameMode::OnlinePvP(Mark::X));
+        play_at(&mut game, 1, 1);
+        assert!(!game.undo());
+    }
+
     #[test]
     fn test_player_move_switches_player() {
         let mut game = GamePlayTTT::new(GameMode::LocalPvP);
@@ -1036,6 +2970,59 @@ mod tests {
         assert!(!remote.apply_remote_concession());
     }
 
+    #[test]
+    fn test_resigning_awards_the_opponent_the_win() {
+        let mut classic = GamePlayTTT::new(GameMode::LocalPvP);
+        assert!(classic.resign());
+        assert_eq!(classic.board.state, GameState::Won(Mark::O));
+        assert!(!classic.resign());
+        assert_eq!(classic.board.state, GameState::Won(Mark::O));
+
+        let mut ultimate = GamePlayUTT::new(GameMode::LocalPvP);
+        ultimate.big_board.state = GameState::Draw;
+        assert!(!ultimate.resign());
+        assert_eq!(ultimate.big_board.state, GameState::Draw);
+
+        let mut online = GamePlayUTT::new(GameMode::OnlinePvP(Mark::X));
+        assert!(!online.resign());
+        assert_eq!(online.big_board.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_accepted_draw_offer_ends_the_game_as_a_draw() {
+        let mut classic = GamePlayTTT::new(GameMode::LocalPvP);
+        assert!(classic.offer_draw());
+        assert_eq!(classic.draw_offer, Some(Mark::X));
+        assert!(!classic.offer_draw());
+        assert!(classic.accept_draw());
+        assert_eq!(classic.board.state, GameState::Draw);
+        assert_eq!(classic.draw_offer, None);
+        assert!(!classic.offer_draw());
+
+        let mut ultimate = GamePlayUTT::new(GameMode::LocalPvP);
+        assert!(ultimate.offer_draw());
+        assert!(ultimate.accept_draw());
+        assert_eq!(ultimate.big_board.state, GameState::Draw);
+
+        let mut against_ai = GamePlayTTT::new(GameMode::PvE(AI::Weak(Mark::O)));
+        assert!(!against_ai.offer_draw());
+    }
+
+    #[test]
+    fn test_declined_or_outplayed_draw_offer_keeps_the_game_going() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        assert!(game.offer_draw());
+        assert!(game.decline_draw());
+        assert_eq!(game.board.state, GameState::Playing);
+        assert!(!game.accept_draw());
+
+        assert!(game.offer_draw());
+        assert!(game.play_move());
+        assert_eq!(game.draw_offer, None);
+        assert!(!game.accept_draw());
+        assert_eq!(game.board.state, GameState::Playing);
+    }
+
     #[test]
     fn test_online_ultimate_rematch_waits_for_both_players() {
         let mut game = GamePlayUTT::new(GameMode::OnlinePvP(Mark::X));
@@ -1086,11 +3073,187 @@ mod tests {
         game.reset_game();
 
         assert_eq!(game.turn, 0);
-        assert_eq!(game.active_player, Mark::X);
+        // Local rounds alternate the first player
+        assert_eq!(game.active_player, Mark::O);
         assert_eq!(game.board.state, GameState::Playing);
         assert!(game.board.get(0, 0).is_none());
     }
 
+    #[test]
+    fn test_read_only_games_ignore_moves_but_not_navigation() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        game.toggle_read_only();
+        game.input_right();
+        assert_eq!(game.selected, Position { row: 0, col: 1 });
+        assert!(!game.play_move());
+        assert_eq!(game.board, SmallBoard::new());
+        assert_eq!(game.turn, 0);
+
+        let mut ultimate = GamePlayUTT::new(GameMode::LocalPvP);
+        ultimate.toggle_read_only();
+        assert!(!ultimate.input_enter());
+        assert!(!ultimate.input_enter());
+        assert_eq!(ultimate.big_board, BigBoard::new());
+
+        ultimate.toggle_read_only();
+        assert!(ultimate.input_enter());
+    }
+
+    #[test]
+    fn test_swapped_rematches_alternate_sides_and_clear_the_board() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        for swapped in [true, false, true] {
+            game.play_move();
+            game.input_right();
+            game.play_move();
+
+            assert!(game.rematch_swap_sides());
+            assert_eq!(game.players_swapped, swapped);
+            assert_eq!(game.turn, 0);
+            assert_eq!(game.active_player, Mark::X);
+            assert_eq!(game.board, SmallBoard::new());
+        }
+
+        let mut against_ai = GamePlayTTT::new(GameMode::PvE(AI::Weak(Mark::O)));
+        assert!(!against_ai.rematch_swap_sides());
+        assert!(!against_ai.players_swapped);
+    }
+
+    #[test]
+    fn test_running_out_of_time_loses_the_game() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        game.set_time_budget(Some(Duration::from_secs(10)));
+        let start = Instant::now();
+
+        game.tick_clock(start);
+        game.tick_clock(start + Duration::from_secs(4));
+        let clock = game.clock.unwrap();
+        assert_eq!(clock.remaining(Mark::X), Duration::from_secs(6));
+        assert_eq!(clock.remaining(Mark::O), Duration::from_secs(10));
+        assert_eq!(game.board.state, GameState::Playing);
+
+        game.tick_clock(start + Duration::from_secs(10));
+        assert_eq!(game.flagged, Some(Mark::X));
+        assert_eq!(game.board.state, GameState::Won(Mark::O));
+        assert!(!game.undo());
+
+        game.reset_game();
+        assert_eq!(game.flagged, None);
+        assert_eq!(game.clock, Some(GameClock::new(Duration::from_secs(10))));
+    }
+
+    #[test]
+    fn test_clock_does_not_charge_across_moves() {
+        let mut game = GamePlayTTT::new(GameMode::PvE(AI::Weak(Mark::O)));
+        game.set_time_budget(Some(Duration::from_secs(10)));
+        let start = Instant::now();
+
+        game.tick_clock(start);
+        game.tick_clock(start + Duration::from_secs(1));
+        // The AI replies while the move is handled, before the next tick.
+        assert!(game.play_move());
+        assert_eq!(game.active_player, Mark::X);
+        game.tick_clock(start + Duration::from_secs(5));
+        game.tick_clock(start + Duration::from_secs(6));
+
+        let clock = game.clock.unwrap();
+        assert_eq!(clock.remaining(Mark::X), Duration::from_secs(8));
+        assert_eq!(clock.remaining(Mark::O), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_local_rounds_alternate_the_first_player() {
+        let mut game = GamePlayUTT::with_first_player(GameMode::LocalPvP, Mark::O);
+        let mut first_players = Vec::new();
+        for _ in 0..3 {
+            game.reset_game();
+            first_players.push(game.active_player);
+        }
+        assert_eq!(first_players, [Mark::X, Mark::O, Mark::X]);
+
+        game.reset_game_with_first_player(Mark::X);
+        assert_eq!(game.active_player, Mark::X);
+        game.restart_same_seed();
+        assert_eq!(game.active_player, Mark::X);
+
+        let mut game = GamePlayTTT::new(GameMode::EvE(AI::Weak(Mark::X), AI::Weak(Mark::O)));
+        game.reset_game();
+        game.reset_game_with_first_player(Mark::O);
+        assert_eq!(game.starting_player, Mark::X);
+    }
+
+    #[test]
+    fn test_wins_are_detected_when_o_moves_first() {
+        // O takes the left column while X answers in the middle one.
+        let mut game = GamePlayTTT::with_first_player(GameMode::LocalPvP, Mark::O);
+        for (row, col) in [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)] {
+            game.selected = Position { row, col };
+            assert!(game.play_move());
+        }
+        assert_eq!(game.board.state, GameState::Won(Mark::O));
+
+        // Against O's perfect play, X's first reply on an edge loses: O forks.
+        let mut game = GamePlayTTT::with_first_player(GameMode::LocalPvP, Mark::O);
+        for (row, col) in [(1, 1), (0, 1), (0, 0), (2, 2), (2, 0), (1, 0), (0, 2)] {
+            game.selected = Position { row, col };
+            assert!(game.play_move());
+        }
+        assert_eq!(game.board.state, GameState::Won(Mark::O));
+
+        // X wins a round started by O once O stops blocking.
+        let mut game = GamePlayTTT::with_first_player(GameMode::LocalPvP, Mark::O);
+        for (row, col) in [(0, 0), (1, 1), (0, 1), (0, 2), (2, 2), (2, 0)] {
+            game.selected = Position { row, col };
+            assert!(game.play_move());
+        }
+        assert_eq!(game.board.state, GameState::Won(Mark::X));
+        assert_eq!(
+            game.board.winning_line().unwrap()[0],
+            Position { row: 0, col: 2 }
+        );
+    }
+
+    #[test]
+    fn test_solitaire_advances_on_correct_answer() {
+        let mut game = GamePlayTTT::new(GameMode::Solitaire);
+        let puzzle = game.puzzle.unwrap();
+        assert_eq!(game.board, puzzle.board);
+        assert_eq!(game.active_player, Mark::X);
+
+        let wrong = puzzle
+            .board
+            .get_possible_moves()
+            .into_iter()
+            .find(|mv| *mv != puzzle.solution)
+            .unwrap();
+        let (row, col) = wrong.unwrap_base();
+        game.selected = Position { row, col };
+        game.play_move();
+        assert!(game.puzzle_missed);
+        assert_eq!(game.puzzles_solved, 0);
+        assert_eq!(game.board, puzzle.board);
+
+        let (row, col) = puzzle.solution.unwrap_base();
+        game.selected = Position { row, col };
+        game.play_move();
+        assert!(!game.puzzle_missed);
+        assert_eq!(game.puzzles_solved, 1);
+        assert_eq!(game.board.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_local_game_with_o_first() {
+        let game = GamePlayTTT::with_first_player(GameMode::LocalPvP, Mark::O);
+        assert_eq!(game.active_player, Mark::O);
+        assert_eq!(game.turn, 0);
+
+        let game = GamePlayUTT::with_first_player(GameMode::LocalPvP, Mark::O);
+        assert_eq!(game.active_player, Mark::O);
+
+        let game = GamePlayUTT::with_first_player(GameMode::LocalPvP, Mark::X);
+        assert_eq!(game.active_player, Mark::X);
+    }
+
     #[test]
     fn test_selection_wraps_horizontally() {
         let mut game = GamePlayTTT::new(GameMode::LocalPvP);
@@ -1114,4 +3277,507 @@ mod tests {
         game.input_up();
         assert_eq!(game.selected.row, 2);
     }
+
+    #[test]
+    fn test_tips_are_toggleable() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        assert_eq!(game.tip(), None);
+
+        game.toggle_tips();
+        assert_eq!(game.tip(), Some(Tip::TakeCenter));
+
+        game.toggle_tips();
+        assert_eq!(game.tip(), None);
+    }
+
+    #[test]
+    fn test_ultimate_pve_ai_playing_x_opens_with_a_legal_move() {
+        use crate::ai::Game;
+        use crate::ai::mcts::MCTSAi;
+
+        seed_rng(11);
+        let ai = AI::StrongUTT(MCTSAi::with_rounds(BigBoard::new(), Mark::X, 200));
+        let mut game = GamePlayUTT::new(GameMode::PvE(ai));
+        assert_eq!(game.turn, 1);
+        assert_eq!(game.active_player, Mark::O);
+        assert_eq!(game.history.len(), 1);
+        assert!(
+            BigBoard::new()
+                .get_possible_moves()
+                .contains(&game.history[0])
+        );
+
+        // The human answers in the forced board and the AI replies in the
+        // board that answer sends it to.
+        let forced = game.big_board.active_board.unwrap();
+        let cell = game
+            .big_board
+            .get_board(forced.0, forced.1)
+            .get_possible_moves()[0];
+        let (cell_row, cell_col) = cell.unwrap_base();
+        game.selected_board = Position {
+            row: forced.0,
+            col: forced.1,
+        };
+        game.selected_cell = Some(Position {
+            row: cell_row,
+            col: cell_col,
+        });
+        let mut expected = game.big_board;
+        expected.make_move(forced.0, forced.1, cell_row, cell_col, Mark::O);
+        assert!(game.play_move());
+        assert_eq!(game.history.len(), 3);
+        assert!(expected.get_possible_moves().contains(&game.history[2]));
+
+        game.reset_game();
+        assert_eq!(game.turn, 1);
+        assert_eq!(game.active_player, Mark::O);
+    }
+
+    #[test]
+    fn test_move_into_a_finished_board_grants_a_free_move() {
+        let mut game = GamePlayUTT::new(GameMode::LocalPvP);
+        let moves = [(1, 1, 0, 0), (0, 0, 1, 1), (1, 1, 2, 2), (2, 2, 1, 1)];
+        for (board_row, board_col, cell_row, cell_col) in moves {
+            game.big_board
+                .make_move(board_row, board_col, cell_row, cell_col, game.active_player);
+            game.active_player = game.active_player.switch();
+        }
+
+        // Completing the diagonal wins the center board and sends O back to it.
+        game.selected_board = Position { row: 1, col: 1 };
+        game.selected_cell = Some(Position { row: 1, col: 1 });
+        assert!(game.play_move());
+        assert!(game.free_move);
+        assert_eq!(game.big_board.active_board, None);
+
+        game.selected_board = Position { row: 0, col: 1 };
+        game.selected_cell = Some(Position { row: 0, col: 0 });
+        assert!(game.play_move());
+        assert!(!game.free_move);
+        assert_eq!(game.big_board.active_board, Some((0, 0)));
+    }
+
+    #[test]
+    fn test_ultimate_pve_ai_answers_in_active_board() {
+        use crate::ai::mcts::MCTSAi;
+
+        seed_rng(5);
+        let mut game = GamePlayUTT::new(GameMode::PvE(AI::StrongUTT(MCTSAi::new(
+            BigBoard::new(),
+            Mark::O,
+        ))));
+        game.selected_board = Position { row: 1, col: 1 };
+
+        for _ in 0..3 {
+            let board = game.selected_board;
+            let board_cells = *game.big_board.get_board(board.row, board.col);
+            let cell = board_cells.get_possible_moves()[0];
+            let (cell_row, cell_col) = cell.unwrap_base();
+            game.selected_cell = Some(Position {
+                row: cell_row,
+                col: cell_col,
+            });
+            let mut expected = game.big_board;
+            expected.make_move(board.row, board.col, cell_row, cell_col, Mark::X);
+            let forced_board = expected.active_board;
+
+            assert!(game.play_move());
+
+            let ai_move = expected
+                .get_possible_moves()
+                .into_iter()
+                .find(|mv| {
+                    let (board_row, board_col, cell_row, cell_col) = mv.unwrap_ultimate();
+                    game.big_board
+                        .get_board(board_row, board_col)
+                        .get(cell_row, cell_col)
+                        == Some(Mark::O)
+                })
+                .expect("AI should answer with a legal move");
+            let (board_row, board_col, _, _) = ai_move.unwrap_ultimate();
+            if let Some(forced_board) = forced_board {
+                assert_eq!((board_row, board_col), forced_board);
+            }
+            assert_eq!(game.active_player, Mark::X);
+            assert_eq!(
+                game.selected_cell.is_some(),
+                game.big_board.active_board.is_some()
+            );
+            if let Some((row, col)) = game.big_board.active_board {
+                assert_eq!(
+                    (game.selected_board.row, game.selected_board.col),
+                    (row, col)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_ultimate_tip_uses_selected_board() {
+        let mut game = GamePlayUTT::new(GameMode::LocalPvP);
+        game.toggle_tips();
+        assert_eq!(game.tip(), None);
+
+        game.input_enter();
+        assert_eq!(game.tip(), Some(Tip::TakeCenter));
+    }
+
+    #[test]
+    fn test_replay_skips_malformed_lines() {
+        let replay =
+            Replay::from_log("# games\nclassic X 0 3 1 4 2\nnot a game\nclassic X 0 0\n\n");
+        assert_eq!(replay.records.len(), 1);
+        assert_eq!(
+            replay.message,
+            Some("Skipped 2 malformed line(s)".to_string())
+        );
+        assert_eq!(replay.ply, 0);
+    }
+
+    #[test]
+    fn test_replay_steps_to_final_state() {
+        let mut replay = Replay::from_log("classic X 0 3 1 4 2");
+        while replay.step() {}
+
+        assert!(replay.is_finished());
+        assert_eq!(replay.ply, 5);
+        assert_eq!(
+            replay.board.map(|board| board.state()),
+            Some(GameState::Won(Mark::X))
+        );
+    }
+
+    #[test]
+    fn test_replay_tick_waits_for_delay() {
+        let mut replay = Replay::from_log("classic X 0 3");
+        let start = replay.last_step;
+
+        assert!(!replay.tick(start));
+        assert!(replay.tick(start + replay.delay));
+        assert_eq!(replay.ply, 1);
+
+        replay.faster();
+        assert_eq!(replay.delay, DEFAULT_REPLAY_DELAY - REPLAY_DELAY_STEP);
+        replay.next_game();
+        assert_eq!(replay.ply, 0);
+    }
+
+    #[test]
+    fn test_eve_records_evaluations_for_strong_ai() {
+        use crate::ai::mcts::MCTSAi;
+
+        let ai_x = AI::StrongTTT(MCTSAi::new(SmallBoard::new(), Mark::X));
+        let mut game = GamePlayTTT::new(GameMode::EvE(ai_x, AI::Weak(Mark::O)));
+        game.play_move();
+        game.play_move();
+
+        assert_eq!(game.evaluations.len(), 1);
+        assert!((0.0..=1.0).contains(&game.evaluations[0]));
+
+        game.reset_game();
+        assert!(game.evaluations.is_empty());
+    }
+
+    #[test]
+    fn test_analysis_uses_the_mcts_estimate_of_the_last_move() {
+        use crate::ai::mcts::MCTSAi;
+
+        let ai_x = AI::StrongTTT(MCTSAi::new(SmallBoard::new(), Mark::X));
+        let mut game = GamePlayTTT::new(GameMode::EvE(ai_x, AI::Weak(Mark::O)));
+        assert_eq!(game.analysis(), 0.0);
+
+        game.play_move();
+        let GameMode::EvE(ai_x, _) = &game.mode else {
+            panic!("expected AI-vs-AI mode");
+        };
+        let chance = ai_x.evaluation().unwrap();
+        assert!((game.analysis() - (2.0 * chance - 1.0)).abs() < 1e-6);
+
+        // The weak AI makes no estimate, so the AI that moved last has none.
+        game.play_move();
+        assert_eq!(game.analysis(), evaluate(&game.board));
+    }
+
+    #[test]
+    fn test_eve_games_run_to_completion() {
+        use crate::ai::mcts::MCTSAi;
+        use crate::ai::simple::SimpleAi;
+
+        let ais = |mark| {
+            [
+                AI::Weak(mark),
+                AI::Medium(SimpleAi::new(mark)),
+                AI::StrongTTT(MCTSAi::new(SmallBoard::new(), mark)),
+            ]
+        };
+        for ai_x in ais(Mark::X) {
+            for ai_o in ais(Mark::O) {
+                let mut game = GamePlayTTT::new(GameMode::EvE(ai_x.clone(), ai_o));
+                for _ in 0..MAX_MOVES_CLASSIC {
+                    if game.board.state != GameState::Playing {
+                        break;
+                    }
+                    assert!(game.play_move());
+                }
+                assert_ne!(game.board.state, GameState::Playing);
+                assert_eq!(game.aborted, None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eve_aborts_when_move_cap_is_reached() {
+        let mode = GameMode::EvE(AI::Weak(Mark::X), AI::Weak(Mark::O));
+        let mut game = GamePlayTTT::new(mode);
+        game.ai_moves = MAX_MOVES_CLASSIC;
+
+        game.play_move();
+        assert_eq!(
+            game.aborted,
+            Some(MoveCapExceeded {
+                max_moves: MAX_MOVES_CLASSIC
+            })
+        );
+        assert!(!game.play_move());
+
+        game.reset_game();
+        assert_eq!(game.aborted, None);
+        assert!(game.play_move());
+    }
+
+    #[test]
+    fn test_restart_same_seed_reproduces_first_ai_move() {
+        use crate::ai::mcts::MCTSAi;
+
+        let ai_x = AI::StrongUTT(MCTSAi::new(BigBoard::new(), Mark::X));
+        let mut game = GamePlayUTT::new(GameMode::EvE(ai_x, AI::Weak(Mark::O)));
+        game.play_move();
+        let first_board = game.big_board;
+
+        game.restart_same_seed();
+        assert_eq!(game.turn, 0);
+        game.play_move();
+        assert_eq!(game.big_board, first_board);
+    }
+
+    #[test]
+    fn test_hint_cell_points_to_immediate_win_until_next_move() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            play_at(&mut game, row, col);
+        }
+        assert_eq!(game.suggested_cell(), Some(Position { row: 0, col: 2 }));
+
+        game.toggle_hint_cell();
+        assert_eq!(game.hint_cell, Some(Position { row: 0, col: 2 }));
+        play_at(&mut game, 2, 2);
+        assert_eq!(game.hint_cell, None);
+    }
+
+    #[test]
+    fn test_placement_animation_fades_after_configured_frames() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        game.selected = Position { row: 1, col: 2 };
+        assert!(game.play_move());
+        assert_eq!(
+            game.placement,
+            Some(PlacementAnimation {
+                cell: Position { row: 1, col: 2 },
+                frames_left: PLACEMENT_ANIMATION_FRAMES,
+            })
+        );
+
+        for _ in 1..PLACEMENT_ANIMATION_FRAMES {
+            game.tick_animation();
+        }
+        assert_eq!(
+            game.placement.map(|animation| animation.frames_left),
+            Some(1)
+        );
+        game.tick_animation();
+        assert_eq!(game.placement, None);
+        // The animation never holds back the next move.
+        game.selected = Position { row: 0, col: 0 };
+        assert!(game.play_move());
+        assert_eq!(game.placement.unwrap().cell, Position { row: 0, col: 0 });
+    }
+
+    #[test]
+    fn test_hint_follows_configured_strength() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        game.board.set(0, 0, Some(Mark::X));
+        game.active_player = Mark::O;
+        game.turn = 1;
+
+        game.update_hint();
+        assert_eq!(game.hint(), None);
+
+        game.toggle_tips();
+        game.toggle_hint_strength();
+        game.update_hint();
+        assert_eq!(game.hint(), Some(Move::Base(1, 1)));
+
+        game.toggle_hint_strength();
+        assert_eq!(game.hint(), None);
+        game.update_hint();
+        assert!(game.hint().is_some());
+    }
+
+    #[test]
+    fn test_mistake_shows_missed_optimal_move() {
+        let mut game = GamePlayTTT::new(GameMode::PvE(AI::Weak(Mark::O)));
+        game.toggle_mistakes();
+
+        game.selected = Position { row: 1, col: 1 };
+        game.play_move();
+        assert_eq!(game.missed_move, None);
+
+        game.reset_game();
+        game.board.set(0, 0, Some(Mark::X));
+        game.board.set(0, 1, Some(Mark::X));
+        game.board.set(1, 1, Some(Mark::O));
+        game.board.set(2, 2, Some(Mark::O));
+        game.selected = Position { row: 1, col: 2 };
+        game.play_move();
+        assert_eq!(game.missed_move, Some(Move::Base(0, 2)));
+    }
+
+    #[test]
+    fn test_destination_distinguishes_open_and_closed_boards() {
+        let mut game = GamePlayUTT::new(GameMode::LocalPvP);
+        let moves = [
+            (1, 1, 1, 0),
+            (1, 0, 0, 0),
+            (0, 0, 0, 0),
+            (0, 0, 1, 1),
+            (1, 1, 2, 0),
+            (2, 0, 0, 0),
+            (0, 0, 0, 1),
+            (0, 1, 0, 0),
+            (0, 0, 0, 2),
+        ];
+        let mut mark = Mark::X;
+        for (board_row, board_col, cell_row, cell_col) in moves {
+            game.big_board
+                .make_move(board_row, board_col, cell_row, cell_col, mark);
+            mark = mark.switch();
+        }
+        assert_eq!(game.big_board.sub_state(0, 0), GameState::Won(Mark::X));
+
+        game.active_player = mark;
+        game.selected_board = Position { row: 0, col: 2 };
+        assert_eq!(game.destination(0, 0), Some(Destination::Closed));
+        assert_eq!(game.destination(1, 1), Some(Destination::Open));
+
+        game.selected_board = Position { row: 1, col: 1 };
+        assert_eq!(game.destination(1, 1), None);
+    }
+
+    #[test]
+    fn test_target_board_follows_the_selected_cell() {
+        let mut game = GamePlayUTT::new(GameMode::LocalPvP);
+        assert_eq!(game.target_board(), None);
+
+        game.selected_board = Position { row: 1, col: 1 };
+        game.selected_cell = Some(Position { row: 0, col: 2 });
+        assert_eq!(game.target_board(), Some(Position { row: 0, col: 2 }));
+    }
+
+    #[test]
+    fn test_target_board_is_none_when_sending_to_a_finished_board() {
+        let mut game = GamePlayUTT::new(GameMode::LocalPvP);
+        let moves = [
+            (1, 1, 0, 0),
+            (0, 0, 1, 1),
+            (1, 1, 0, 1),
+            (0, 1, 1, 1),
+            (1, 1, 0, 2),
+        ];
+        let mut mark = Mark::X;
+        for (board_row, board_col, cell_row, cell_col) in moves {
+            game.big_board
+                .make_move(board_row, board_col, cell_row, cell_col, mark);
+            mark = mark.switch();
+        }
+        assert_eq!(game.big_board.sub_state(1, 1), GameState::Won(Mark::X));
+
+        game.active_player = mark;
+        game.selected_board = Position { row: 0, col: 2 };
+        game.selected_cell = Some(Position { row: 1, col: 1 });
+        assert_eq!(game.destination(1, 1), Some(Destination::Closed));
+        assert_eq!(game.target_board(), None);
+    }
+
+    #[test]
+    fn test_auto_draw_resolves_dead_position() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        game.board
+            .set_row(0, [Some(Mark::X), Some(Mark::O), Some(Mark::X)]);
+        game.board.set(2, 0, Some(Mark::O));
+        game.board.set(2, 1, Some(Mark::X));
+        game.active_player = Mark::O;
+
+        let mut enabled = GamePlayTTT::new(GameMode::LocalPvP);
+        enabled.board = game.board;
+        enabled.active_player = Mark::O;
+        enabled.auto_draw = true;
+
+        for game in [&mut game, &mut enabled] {
+            game.selected = Position { row: 2, col: 2 };
+            game.play_move();
+        }
+        assert_eq!(game.board.state, GameState::Playing);
+        assert_eq!(enabled.board.state, GameState::Draw);
+        assert!(enabled.board.get_possible_moves().is_empty());
+        assert!(enabled.board.get(1, 1).is_none());
+    }
+
+    #[test]
+    fn test_classic_transcript_round_trips() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        for (row, col) in [(1, 1), (0, 0), (2, 2)] {
+            game.selected = Position { row, col };
+            assert!(game.play_move());
+        }
+        let transcript = game.transcript();
+        assert_eq!(transcript, "X:b2 O:a1 X:c3");
+        assert_eq!(transcript.split_whitespace().count(), game.turn as usize);
+
+        let replayed = GamePlayTTT::from_transcript(&transcript).unwrap();
+        assert_eq!(replayed.board, game.board);
+        assert_eq!(replayed.turn, game.turn);
+        assert_eq!(replayed.active_player, Mark::O);
+        assert_eq!(replayed.transcript(), transcript);
+
+        assert!(GamePlayTTT::from_transcript("X:a1 O:a1").is_err());
+    }
+
+    #[test]
+    fn test_ultimate_transcript_round_trips() {
+        let mut game = GamePlayUTT::with_first_player(GameMode::LocalPvP, Mark::O);
+        for (board, cell) in [((1, 1), (0, 2)), ((0, 2), (1, 1)), ((1, 1), (2, 0))] {
+            game.selected_board = Position {
+                row: board.0,
+                col: board.1,
+            };
+            game.selected_cell = Some(Position {
+                row: cell.0,
+                col: cell.1,
+            });
+            assert!(game.play_move());
+        }
+        let transcript = game.transcript();
+        assert_eq!(transcript, "O:1,1/0,2 X:0,2/1,1 O:1,1/2,0");
+        assert_eq!(transcript.split_whitespace().count(), game.turn as usize);
+
+        let replayed = GamePlayUTT::from_transcript(&transcript).unwrap();
+        assert_eq!(replayed.big_board, game.big_board);
+        assert_eq!(replayed.active_player, Mark::X);
+        assert_eq!(replayed.selected_board, Position { row: 2, col: 0 });
+        assert_eq!(replayed.transcript(), transcript);
+
+        // The second move must be played on the top-right board.
+        assert!(GamePlayUTT::from_transcript("X:1,1/0,2 O:0,0/1,1").is_err());
+    }
 }
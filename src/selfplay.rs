@@ -0,0 +1,231 @@
+use crate::ai::rng::seed_rng;
+use crate::ai::{Engine, Game, MAX_MOVES_CLASSIC, Move, MoveCapExceeded, play_ai_game};
+use crate::arena::EngineKind;
+use crate::game::base::SmallBoard;
+use crate::game::notation::GameRecord;
+use crate::game::{Board, GameVariant, Mark};
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+/// Header line of the CSV written by [`write_csv`].
+pub const CSV_HEADER: &str = "board,mark,move,outcome";
+
+/// A classic position together with the move chosen in it and how the game ended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    /// Position before the move.
+    pub board: SmallBoard,
+    /// Mark that played the move.
+    pub mark: Mark,
+    /// Move chosen by the engine.
+    pub mv: Move,
+    /// Final result for `mark`: 1 for a win, 0 for a draw and -1 for a loss.
+    pub outcome: i8,
+}
+
+/// Games played by [`self_play`] and the samples extracted from them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfPlayData {
+    /// Seed the games were played with.
+    pub seed: u64,
+    /// Every game played, in notation form.
+    pub games: Vec<GameRecord>,
+    /// One sample per move, or eight with symmetry augmentation.
+    pub samples: Vec<Sample>,
+}
+
+/// Engine wrapper remembering every position it was asked to move in.
+struct Recorder {
+    engine: Box<dyn Engine<SmallBoard>>,
+    mark: Mark,
+    moves: Vec<(SmallBoard, Mark, Move)>,
+}
+
+impl Engine<SmallBoard> for Recorder {
+    fn choose_move(&mut self, board: &SmallBoard) -> Move {
+        let mv = self.engine.choose_move(board);
+        self.moves.push((*board, self.mark, mv));
+        mv
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        self.engine.name()
+    }
+}
+
+/// Plays classic games of `engine` against itself and collects training samples.
+///
+/// The engine randomness is reseeded from `seed` before each game, so the
+/// same arguments always produce the same data.
+///
+/// # Arguments
+/// * `engine` - The engine playing both sides
+/// * `games` - Number of games to play
+/// * `seed` - Master seed for all engine randomness
+/// * `augment` - Whether to add the seven symmetric copies of every sample
+///
+/// # Returns
+/// The played games and their samples, or a [`MoveCapExceeded`] error if a
+/// game did not finish.
+pub fn self_play(
+    engine: EngineKind,
+    games: u32,
+    seed: u64,
+    augment: bool,
+) -> Result<SelfPlayData, MoveCapExceeded> {
+    let mut records = Vec::new();
+    let mut samples = Vec::new();
+    for game in 0..games {
+        seed_rng(seed.wrapping_add(u64::from(game)));
+        let mut recorder_x = Recorder {
+            engine: engine.build(Mark::X),
+            mark: Mark::X,
+            moves: Vec::new(),
+        };
+        let mut recorder_o = Recorder {
+            engine: engine.build(Mark::O),
+            mark: Mark::O,
+            moves: Vec::new(),
+        };
+        let mut board = SmallBoard::new();
+        play_ai_game(
+            &mut board,
+            Mark::X,
+            &mut recorder_x,
+            &mut recorder_o,
+            MAX_MOVES_CLASSIC,
+        )?;
+
+        // Both engines always answer legally, so the moves alternate X, O, X, ...
+        let mut moves = Vec::new();
+        let mut moves_o = recorder_o.moves.into_iter();
+        for move_x in recorder_x.moves {
+            moves.push(move_x);
+            moves.extend(moves_o.next());
+        }
+
+        let mut record = GameRecord::new(GameVariant::Classic, Mark::X);
+        for (position, mark, mv) in moves {
+            record.moves.push(mv);
+            let sample = Sample {
+                board: position,
+                mark,
+                mv,
+                outcome: board.score(mark),
+            };
+            if augment {
+                samples.extend(symmetries(&sample));
+            } else {
+                samples.push(sample);
+            }
+        }
+        records.push(record);
+    }
+
+    Ok(SelfPlayData {
+        seed,
+        games: records,
+        samples,
+    })
+}
+
+/// Returns the eight rotations and reflections of a sample.
+fn symmetries(sample: &Sample) -> [Sample; 8] {
+    std::array::from_fn(|index| {
+        let (rotations, mirrored) = (index / 2, index % 2 == 1);
+        let (row, col) = sample.mv.unwrap_base();
+        let (row, col) = transform_cell(row, col, rotations, mirrored);
+        Sample {
+            board: sample.board.transform(rotations, mirrored),
+            mv: Move::Base(row, col),
+            ..*sample
+        }
+    })
+}
+
+/// Maps a cell the same way [`SmallBoard::transform`] maps the board.
+fn transform_cell(row: usize, col: usize, rotations: usize, mirrored: bool) -> (usize, usize) {
+    let (mut row, mut col) = (row, col);
+    for _ in 0..rotations % 4 {
+        (row, col) = (col, 2 - row);
+    }
+    if mirrored {
+        col = 2 - col;
+    }
+    (row, col)
+}
+
+/// Writes samples as CSV, one line per sample after a [`CSV_HEADER`] line.
+///
+/// Boards are written as nine characters in row-major order, with `.` for
+/// empty cells, and moves as their cell index 0-8, e.g. `X...O....,X,8,0`.
+///
+/// # Errors
+/// Returns any error raised by `writer`.
+pub fn write_csv(samples: &[Sample], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "{CSV_HEADER}")?;
+    for sample in samples {
+        let board: String = (0..9)
+            .map(|index| match sample.board.get(index / 3, index % 3) {
+                Some(Mark::X) => 'X',
+                Some(Mark::O) => 'O',
+                None => '.',
+            })
+            .collect();
+        let (row, col) = sample.mv.unwrap_base();
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            board,
+            sample.mark,
+            row * 3 + col,
+            sample.outcome
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_count_matches_played_positions() {
+        let data = self_play(EngineKind::Simple, 5, 11, false).unwrap();
+        let positions: usize = data.games.iter().map(|game| game.moves.len()).sum();
+        assert_eq!(data.games.len(), 5);
+        assert_eq!(data.samples.len(), positions);
+
+        let augmented = self_play(EngineKind::Simple, 5, 11, true).unwrap();
+        assert_eq!(augmented.games, data.games);
+        assert_eq!(augmented.samples.len(), positions * 8);
+
+        let mut csv = Vec::new();
+        write_csv(&data.samples, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap().lines().count(),
+            positions + 1
+        );
+    }
+
+    #[test]
+    fn test_symmetric_samples_keep_move_on_same_cell() {
+        let mut board = SmallBoard::new();
+        board.set(0, 0, Some(Mark::X));
+        let sample = Sample {
+            board,
+            mark: Mark::O,
+            mv: Move::Base(0, 1),
+            outcome: 0,
+        };
+        let mut played = board;
+        played.set(0, 1, Some(Mark::O));
+        for (index, symmetric) in symmetries(&sample).into_iter().enumerate() {
+            let (row, col) = symmetric.mv.unwrap_base();
+            assert!(symmetric.board.get(row, col).is_none());
+            let mut expected = symmetric.board;
+            expected.set(row, col, Some(Mark::O));
+            assert_eq!(expected, played.transform(index / 2, index % 2 == 1));
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::game::{GameState, GameVariant, Mark};
+use std::fmt;
+
+/// Outcome of a finished game, kept for the session statistics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameResult {
+    /// Rule set the game was played with.
+    pub variant: GameVariant,
+    /// Mark that played the first move.
+    pub first_player: Mark,
+    /// Final state of the game.
+    pub outcome: GameState,
+}
+
+/// Outcomes of the games played by one mark in one seat.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SeatRecord {
+    /// Number of games played.
+    pub games: u32,
+    /// Number of games won.
+    pub wins: u32,
+    /// Number of games drawn.
+    pub draws: u32,
+}
+
+impl SeatRecord {
+    /// Returns the fraction of games won, or None if no games were played.
+    pub fn win_rate(&self) -> Option<f32> {
+        (self.games > 0).then(|| self.wins as f32 / self.games as f32)
+    }
+}
+
+/// Game outcomes split by mark and by whether that mark moved first.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MarkStats {
+    /// Games in which X moved first.
+    pub x_first: SeatRecord,
+    /// Games in which X moved second.
+    pub x_second: SeatRecord,
+    /// Games in which O moved first.
+    pub o_first: SeatRecord,
+    /// Games in which O moved second.
+    pub o_second: SeatRecord,
+}
+
+impl MarkStats {
+    /// Aggregates the given results, ignoring games that are still in progress.
+    pub fn from_results<'a>(results: impl IntoIterator<Item = &'a GameResult>) -> Self {
+        let mut stats = Self::default();
+        for result in results {
+            if result.outcome == GameState::Playing {
+                continue;
+            }
+            for mark in [Mark::X, Mark::O] {
+                let record = stats.record_mut(mark, result.first_player == mark);
+                record.games += 1;
+                match result.outcome {
+                    GameState::Won(winner) if winner == mark => record.wins += 1,
+                    GameState::Draw => record.draws += 1,
+                    _ => {}
+                }
+            }
+        }
+        stats
+    }
+
+    /// Returns the record of `mark` in the games it started or did not start.
+    pub fn record(&self, mark: Mark, moved_first: bool) -> &SeatRecord {
+        match (mark, moved_first) {
+            (Mark::X, true) => &self.x_first,
+            (Mark::X, false) => &self.x_second,
+            (Mark::O, true) => &self.o_first,
+            (Mark::O, false) => &self.o_second,
+        }
+    }
+
+    fn record_mut(&mut self, mark: Mark, moved_first: bool) -> &mut SeatRecord {
+        match (mark, moved_first) {
+            (Mark::X, true) => &mut self.x_first,
+            (Mark::X, false) => &mut self.x_second,
+            (Mark::O, true) => &mut self.o_first,
+            (Mark::O, false) => &mut self.o_second,
+        }
+    }
+}
+
+/// Running tally of the games finished since the last visit to the main menu.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Scoreboard {
+    /// Games won by X.
+    pub x_wins: u32,
+    /// Games won by O.
+    pub o_wins: u32,
+    /// Games drawn.
+    pub draws: u32,
+}
+
+impl Scoreboard {
+    /// Counts a finished game, ignoring games that are still in progress.
+    pub fn record(&mut self, outcome: GameState) {
+        match outcome {
+            GameState::Won(Mark::X) => self.x_wins += 1,
+            GameState::Won(Mark::O) => self.o_wins += 1,
+            GameState::Draw => self.draws += 1,
+            GameState::Playing => {}
+        }
+    }
+
+    /// Returns the number of games counted.
+    pub fn games(&self) -> u32 {
+        self.x_wins + self.o_wins + self.draws
+    }
+}
+
+impl fmt::Display for Scoreboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "X {} O {} D {}", self.x_wins, self.o_wins, self.draws)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(first_player: Mark, outcome: GameState) -> GameResult {
+        GameResult {
+            variant: GameVariant::Classic,
+            first_player,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_split_rates_by_first_mover() {
+        let results = [
+            result(Mark::X, GameState::Won(Mark::X)),
+            result(Mark::X, GameState::Won(Mark::X)),
+            result(Mark::X, GameState::Won(Mark::X)),
+            result(Mark::X, GameState::Won(Mark::O)),
+            result(Mark::X, GameState::Draw),
+            result(Mark::O, GameState::Won(Mark::X)),
+            result(Mark::O, GameState::Won(Mark::O)),
+            result(Mark::X, GameState::Playing),
+        ];
+        let stats = MarkStats::from_results(&results);
+
+        assert_eq!(
+            stats.x_first,
+            SeatRecord {
+                games: 5,
+                wins: 3,
+                draws: 1
+            }
+        );
+        assert_eq!(stats.record(Mark::X, true).win_rate(), Some(0.6));
+        assert_eq!(stats.record(Mark::X, false).win_rate(), Some(0.5));
+        assert_eq!(stats.record(Mark::O, true).win_rate(), Some(0.5));
+        assert_eq!(stats.record(Mark::O, false).win_rate(), Some(0.2));
+    }
+
+    #[test]
+    fn test_no_games_has_no_rate() {
+        let stats = MarkStats::from_results(&[]);
+        assert_eq!(stats.record(Mark::O, true).win_rate(), None);
+    }
+
+    #[test]
+    fn test_scoreboard_counts_draws_separately() {
+        let mut scoreboard = Scoreboard::default();
+        for outcome in [
+            GameState::Won(Mark::X),
+            GameState::Draw,
+            GameState::Won(Mark::O),
+            GameState::Won(Mark::X),
+            GameState::Playing,
+            GameState::Draw,
+        ] {
+            scoreboard.record(outcome);
+        }
+        assert_eq!(
+            scoreboard,
+            Scoreboard {
+                x_wins: 2,
+                o_wins: 1,
+                draws: 2,
+            }
+        );
+        assert_eq!(scoreboard.games(), 5);
+        assert_eq!(scoreboard.to_string(), "X 2 O 1 D 2");
+    }
+}
@@ -0,0 +1,189 @@
+use crate::game::Mark;
+use ratatui::style::{Color, Modifier, Style};
+
+/// Colors used by the terminal interface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Name shown when the theme is selected.
+    pub name: &'static str,
+    /// Color of X marks and of boards and games won by X.
+    pub x_color: Color,
+    /// Color of O marks and of boards and games won by O.
+    pub o_color: Color,
+    /// Color of the selected menu option, cell or board.
+    pub selection: Color,
+    /// Color of drawn boards and games.
+    pub draw: Color,
+    /// Color of the title banner and of the mode names.
+    pub title: Color,
+    /// Color of the borders around the title, menus, boards and instructions.
+    pub border: Color,
+    /// Color of the small board the selected Ultimate cell sends the opponent to.
+    pub destination: Color,
+    /// Glyph drawn for X marks on the boards.
+    pub x_glyph: &'static str,
+    /// Glyph drawn for O marks on the boards.
+    pub o_glyph: &'static str,
+    /// Whether O marks are underlined, to tell them apart without colors.
+    pub underline_o: bool,
+}
+
+impl Theme {
+    /// The original colors: red X, blue O and a cyan title.
+    pub const DEFAULT: Theme = Theme {
+        name: "Default",
+        x_color: Color::Red,
+        o_color: Color::Blue,
+        selection: Color::LightYellow,
+        draw: Color::Indexed(93),
+        title: Color::Cyan,
+        border: Color::Reset,
+        destination: Color::Magenta,
+        x_glyph: "X",
+        o_glyph: "O",
+        underline_o: false,
+    };
+
+    /// Bright colors that stand out on dark terminals.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        name: "High Contrast",
+        x_color: Color::LightRed,
+        o_color: Color::LightCyan,
+        selection: Color::Yellow,
+        draw: Color::LightMagenta,
+        title: Color::White,
+        border: Color::White,
+        destination: Color::LightGreen,
+        x_glyph: "X",
+        o_glyph: "O",
+        underline_o: false,
+    };
+
+    /// Shades of gray only, for terminals without colors.
+    pub const MONOCHROME: Theme = Theme {
+        name: "Monochrome",
+        x_color: Color::White,
+        o_color: Color::Gray,
+        selection: Color::White,
+        draw: Color::DarkGray,
+        title: Color::White,
+        border: Color::Gray,
+        destination: Color::White,
+        x_glyph: "X",
+        o_glyph: "O",
+        underline_o: false,
+    };
+
+    /// Orange and blue marks with distinct shapes, readable without telling colors apart.
+    pub const COLOR_BLIND: Theme = Theme {
+        name: "Color Blind",
+        x_color: Color::Indexed(208),
+        o_color: Color::LightBlue,
+        selection: Color::White,
+        draw: Color::Gray,
+        title: Color::LightBlue,
+        border: Color::Reset,
+        destination: Color::Yellow,
+        x_glyph: "✕",
+        o_glyph: "◯",
+        underline_o: true,
+    };
+
+    /// Every preset, in the order they are cycled through.
+    pub const PRESETS: [Theme; 4] = [
+        Theme::DEFAULT,
+        Theme::HIGH_CONTRAST,
+        Theme::MONOCHROME,
+        Theme::COLOR_BLIND,
+    ];
+
+    /// Returns the color of `mark`.
+    pub fn mark_color(&self, mark: Mark) -> Color {
+        match mark {
+            Mark::X => self.x_color,
+            Mark::O => self.o_color,
+        }
+    }
+
+    /// Returns the glyph drawn for `mark`.
+    pub fn glyph(&self, mark: Mark) -> &'static str {
+        match mark {
+            Mark::X => self.x_glyph,
+            Mark::O => self.o_glyph,
+        }
+    }
+
+    /// Returns the style of a placed `mark`: its color in bold, underlined for
+    /// O when the theme asks for it.
+    pub fn mark_style(&self, mark: Mark) -> Style {
+        let style = Style::default()
+            .fg(self.mark_color(mark))
+            .add_modifier(Modifier::BOLD);
+        if mark == Mark::O && self.underline_o {
+            style.add_modifier(Modifier::UNDERLINED)
+        } else {
+            style
+        }
+    }
+
+    /// Returns the preset following this theme, wrapping to the first one.
+    ///
+    /// A theme that is not a preset is followed by [`Theme::DEFAULT`].
+    pub fn next(&self) -> Theme {
+        let index = Theme::PRESETS
+            .iter()
+            .position(|preset| preset == self)
+            .map_or(0, |index| (index + 1) % Theme::PRESETS.len());
+        Theme::PRESETS[index]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_cycles_through_presets() {
+        let mut theme = Theme::default();
+        let names: Vec<&str> = (0..5)
+            .map(|_| {
+                theme = theme.next();
+                theme.name
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "High Contrast",
+                "Monochrome",
+                "Color Blind",
+                "Default",
+                "High Contrast"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_color_blind_marks_differ_in_shape_and_underline() {
+        let theme = Theme::COLOR_BLIND;
+        assert_ne!(theme.glyph(Mark::X), theme.glyph(Mark::O));
+        assert!(
+            !theme
+                .mark_style(Mark::X)
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+        assert!(
+            theme
+                .mark_style(Mark::O)
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+    }
+}
@@ -1,39 +1,299 @@
-use crate::app::App;
+use crate::ai::Move;
+use crate::app::{App, MoveClock};
+use crate::coach::{HintStrength, Tip};
 use crate::game::base::SmallBoard;
-use crate::game::{Board, GameState, Mark};
+use crate::game::generic::GenericBoard;
+use crate::game::notation::RecordBoard;
+use crate::game::ultimate::BigBoard;
+use crate::game::{Board, GameState, Mark, RuleSet};
 use crate::network::NetworkStatus;
-use crate::scenes::{AIMenuStatus, GameMode, GamePlayTTT, GamePlayUTT, Menu, Scene, TicketInput};
+use crate::scenes::{
+    AIMenuStatus, Destination, GameMode, GamePlayGomoku, GamePlayTTT, GamePlayUTT, Menu,
+    PLACEMENT_ANIMATION_FRAMES, Replay, Scene, TicketInput,
+};
+use crate::stats::{GameResult, MarkStats, Scoreboard, SeatRecord};
+use crate::theme::Theme;
 use crate::utils::Position;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
 };
+use std::time::Duration;
 
-const PURPLE: Color = Color::Indexed(93);
 const TICKET_GROUP_SIZE: usize = 4;
 const TICKET_GROUPS_PER_LINE: usize = 6;
 const TICKET_LINE_WIDTH: u16 =
     (TICKET_GROUP_SIZE * TICKET_GROUPS_PER_LINE + TICKET_GROUPS_PER_LINE - 1) as u16;
+const GRAPH_WIDTH: usize = 25;
+/// Number of ranked moves listed under the analysis bar.
+const RANKED_MOVES_SHOWN: usize = 3;
+/// Column letters shown above a classic board, one over each cell.
+const CLASSIC_COLUMN_LABELS: &str = " a   b   c ";
+/// Large X drawn over a won small board, one row per line of the board.
+const BIG_X: [&str; 5] = [
+    " ╲       ╱ ",
+    "   ╲   ╱   ",
+    "     ╳     ",
+    "   ╱   ╲   ",
+    " ╱       ╲ ",
+];
+/// Large O drawn over a won small board, one row per line of the board.
+const BIG_O: [&str; 5] = [
+    "  ╭─────╮  ",
+    " ╱       ╲ ",
+    " │       │ ",
+    " ╲       ╱ ",
+    "  ╰─────╯  ",
+];
+/// Board indices shown above an Ultimate board, one over each small board.
+const ULTIMATE_COLUMN_LABELS: &str = "     0             1             2     ";
+const COUNTDOWN_WIDTH: usize = 20;
 
 /// Main render function that delegates to the appropriate screen renderer.
 pub fn render(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
     match &app.current_scene {
-        Scene::MainMenu(menu) => render_menu(f, menu, "Select Game"),
-        Scene::TTTMenu(menu) | Scene::UTTMenu(menu) => render_menu(f, menu, "Select Game Mode"),
-        Scene::OnlineMenu(menu, _) => render_menu(f, menu, "Select Connection Method"),
-        Scene::HostingOnline(_) => render_hosting_ttt(f, &app.network_status),
-        Scene::JoiningOnline(input, _) => render_joining_ttt(f, input, &app.network_status),
-        Scene::AIMenu(menu, status) => render_menu(f, menu, ai_menu_title(status)),
-        Scene::PlayingTTT(game) => render_game_ttt(f, game, &app.network_status),
-        Scene::PlayingUTT(game) => render_game_utt(f, game, &app.network_status),
+        Scene::MainMenu(menu) => render_menu(f, menu, "Select Game", theme),
+        Scene::TTTMenu(menu, _) | Scene::UTTMenu(menu, _) => {
+            render_menu(f, menu, "Select Game Mode", theme)
+        }
+        Scene::OnlineMenu(menu, _) => render_menu(f, menu, "Select Connection Method", theme),
+        Scene::FirstPlayerMenu(menu, _) => render_menu(f, menu, "Select First Player", theme),
+        Scene::DifficultyMenu(menu) => render_menu(f, menu, "Select Difficulty", theme),
+        Scene::SideMenu(menu, _, _) => render_menu(f, menu, "Select Your Mark", theme),
+        Scene::HostingOnline(_) => render_hosting_ttt(f, &app.network_status, theme),
+        Scene::JoiningOnline(input, _) => render_joining_ttt(f, input, &app.network_status, theme),
+        Scene::AIMenu(menu, status) => render_menu(f, menu, ai_menu_title(status), theme),
+        Scene::PlayingTTT(game) => render_game_ttt(
+            f,
+            game,
+            &app.network_status,
+            app.move_clock(),
+            &app.scoreboard,
+            theme,
+        ),
+        Scene::PlayingUTT(game) => render_game_utt(
+            f,
+            game,
+            &app.network_status,
+            app.move_clock(),
+            &app.scoreboard,
+            theme,
+        ),
+        Scene::PlayingGomoku(game) => render_game_gomoku(f, game, theme),
+        Scene::Replay(replay) => render_replay(f, replay, theme),
+        Scene::Statistics => render_statistics(f, &app.results, theme),
+    }
+    render_game_summary(f, app);
+    if app.show_help {
+        render_help(f, theme);
+    }
+    if app.confirm_quit {
+        render_quit_confirmation(f, theme);
+    }
+    if let Some(notice) = &app.notice {
+        render_notice(f, notice);
+    }
+}
+
+/// Every key binding and its action, as shown by the help overlay.
+const KEY_BINDINGS: [(&str, &str); 27] = [
+    ("Arrows / HJKL", "Move the selection"),
+    ("Enter / Space", "Place a mark or confirm"),
+    ("0-8", "Place a classic mark on that cell"),
+    ("Esc", "Change the Ultimate board"),
+    ("S", "Let the opponent move first"),
+    ("U", "Undo the last move"),
+    ("Ctrl+R", "Redo an undone move"),
+    ("F4", "Explore variations, then restore"),
+    ("?", "Highlight the suggested cell"),
+    ("T", "Toggle tips"),
+    ("V", "Switch hint strength"),
+    ("B", "Toggle missed-move feedback"),
+    ("N", "Toggle Ultimate destinations"),
+    ("Y", "Toggle large marks on won boards"),
+    ("G", "Toggle the evaluation graph"),
+    ("I", "Toggle the analysis bar"),
+    ("P", "Toggle board coordinates"),
+    ("O", "Toggle read-only watching"),
+    ("C", "Concede an online game"),
+    ("X", "Resign a local game"),
+    ("=", "Offer a draw in a local game"),
+    ("F2", "Save the game"),
+    ("R", "Reset the game"),
+    ("E", "Replay with the same seed"),
+    ("F", "Rematch with sides swapped"),
+    ("M", "Return to the main menu"),
+    ("Q", "Quit"),
+];
+
+/// Dims the screen and draws the key bindings in a centered box.
+fn render_help(f: &mut Frame, theme: &Theme) {
+    let area = f.area();
+    f.buffer_mut()
+        .set_style(area, Style::default().add_modifier(Modifier::DIM));
+
+    let mut lines: Vec<Line> = KEY_BINDINGS
+        .iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{key:>13}  "),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(*action),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from("Press any key to continue").alignment(Alignment::Center));
+
+    let height = lines.len() as u16 + 2;
+    let popup = center_rect(area, 46, height).intersection(area);
+    f.render_widget(Clear, popup);
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border))
+            .title(Line::from("Key Bindings").alignment(Alignment::Center)),
+    );
+    f.render_widget(paragraph, popup);
+}
+
+/// Dims the screen and asks whether to quit the game in progress.
+fn render_quit_confirmation(f: &mut Frame, theme: &Theme) {
+    let area = f.area();
+    f.buffer_mut()
+        .set_style(area, Style::default().add_modifier(Modifier::DIM));
+
+    let lines = vec![
+        Line::from("The game in progress will be lost.").alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "Y",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(": Quit   "),
+            Span::styled(
+                "N",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(": Keep playing"),
+        ])
+        .alignment(Alignment::Center),
+    ];
+
+    let popup = center_rect(area, 40, lines.len() as u16 + 2).intersection(area);
+    f.render_widget(Clear, popup);
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border))
+            .title(Line::from("Quit?").alignment(Alignment::Center)),
+    );
+    f.render_widget(paragraph, popup);
+}
+
+/// Draws the end-of-game summary over a finished classic or Ultimate game.
+///
+/// The summary shows the result, the final board, the number of moves and
+/// the session scoreboard once it has recorded a game. An Ultimate board is
+/// shown as the grid of its small-board winners.
+fn render_game_summary(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let (state, moves, board) = match &app.current_scene {
+        Scene::PlayingTTT(game)
+            if game.board.state != GameState::Playing && !game.is_analyzing() =>
+        {
+            (game.board.state, game.turn, game.board)
+        }
+        Scene::PlayingUTT(game) if game.big_board.state != GameState::Playing => {
+            let mut winners = SmallBoard::new();
+            for (row, results) in game.big_board.results().into_iter().enumerate() {
+                for (col, result) in results.into_iter().enumerate() {
+                    if let Some(GameState::Won(mark)) = result {
+                        winners.set(row, col, Some(mark));
+                    }
+                }
+            }
+            (game.big_board.state, game.turn, winners)
+        }
+        _ => return,
+    };
+
+    let area = f.area();
+    f.buffer_mut()
+        .set_style(area, Style::default().add_modifier(Modifier::DIM));
+
+    let (result, result_style) = game_status(state, Mark::X, theme);
+    let mut lines = vec![
+        Line::from(Span::styled(result, result_style)),
+        Line::from(""),
+    ];
+    lines.extend(
+        (0..5).map(|y| ttt_board_line(&board, y, None, Style::default(), PLAIN_CELLS, theme)),
+    );
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!("Moves: {moves}")));
+    if app.scoreboard.games() > 0 {
+        lines.push(Line::from(format!("Session: {}", app.scoreboard)));
     }
+    lines.push(Line::from(""));
+    let key_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    lines.push(Line::from(vec![
+        Span::styled("R", key_style),
+        Span::raw(": Rematch  "),
+        Span::styled("M", key_style),
+        Span::raw(": Menu  "),
+        Span::styled("Q", key_style),
+        Span::raw(": Quit"),
+    ]));
+
+    let popup = center_rect(area, 32, lines.len() as u16 + 2).intersection(area);
+    f.render_widget(Clear, popup);
+    let paragraph = Paragraph::new(lines).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border))
+            .title(Line::from("Game Over").alignment(Alignment::Center)),
+    );
+    f.render_widget(paragraph, popup);
+}
+
+/// Renders a short notice, such as the result of saving, on the bottom line.
+fn render_notice(f: &mut Frame, notice: &str) {
+    let area = f.area();
+    if area.height == 0 {
+        return;
+    }
+    let line = Rect {
+        y: area.bottom() - 1,
+        height: 1,
+        ..area
+    };
+    let paragraph = Paragraph::new(notice)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center);
+    f.render_widget(paragraph, line);
 }
 
 /// Renders the main menu screen with game options.
-fn render_menu(f: &mut Frame, menu: &Menu, title: &str) {
+fn render_menu(f: &mut Frame, menu: &Menu, title: &str, theme: &Theme) {
     if render_size_warning(f, 14, 13) {
         return;
     }
@@ -48,12 +308,12 @@ fn render_menu(f: &mut Frame, menu: &Menu, title: &str) {
         ])
         .split(f.area());
 
-    render_title(f, chunks[0]);
-    render_menu_options(f, chunks[1], menu, title);
-    render_menu_instructions(f, chunks[2]);
+    render_title(f, chunks[0], theme);
+    render_menu_options(f, chunks[1], menu, title, theme);
+    render_menu_instructions(f, chunks[2], theme);
 }
 
-fn render_hosting_ttt(f: &mut Frame, status: &NetworkStatus) {
+fn render_hosting_ttt(f: &mut Frame, status: &NetworkStatus, theme: &Theme) {
     if render_size_warning(f, 33, 12) {
         return;
     }
@@ -66,7 +326,7 @@ fn render_hosting_ttt(f: &mut Frame, status: &NetworkStatus) {
             Constraint::Length(3),
         ])
         .split(f.area());
-    render_title(f, chunks[0]);
+    render_title(f, chunks[0], theme);
 
     let width = chunks[1].width.saturating_sub(4).min(90);
     let area = center_rect(chunks[1], width, 13);
@@ -116,6 +376,7 @@ fn render_hosting_ttt(f: &mut Frame, status: &NetworkStatus) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border))
                 .title("Host Online Match"),
         );
     f.render_widget(header, header_area);
@@ -154,10 +415,10 @@ fn render_hosting_ttt(f: &mut Frame, status: &NetworkStatus) {
         _ => {}
     }
 
-    render_instructions(f, chunks[2], &["Esc: Cancel | Q: Quit".to_string()]);
+    render_instructions(f, chunks[2], &["Esc: Cancel | Q: Quit".to_string()], theme);
 }
 
-fn render_joining_ttt(f: &mut Frame, input: &TicketInput, status: &NetworkStatus) {
+fn render_joining_ttt(f: &mut Frame, input: &TicketInput, status: &NetworkStatus, theme: &Theme) {
     if render_size_warning(f, 40, 12) {
         return;
     }
@@ -170,7 +431,7 @@ fn render_joining_ttt(f: &mut Frame, input: &TicketInput, status: &NetworkStatus
             Constraint::Length(3),
         ])
         .split(f.area());
-    render_title(f, chunks[0]);
+    render_title(f, chunks[0], theme);
 
     let width = chunks[1].width.saturating_sub(4).min(90);
     let area = center_rect(chunks[1], width, 13);
@@ -211,6 +472,7 @@ fn render_joining_ttt(f: &mut Frame, input: &TicketInput, status: &NetworkStatus
                 Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme.border))
                     .title("Join Online Match"),
             ),
         header_area,
@@ -243,6 +505,7 @@ fn render_joining_ttt(f: &mut Frame, input: &TicketInput, status: &NetworkStatus
         f,
         chunks[2],
         &["Paste ticket | Enter: Connect | Esc: Cancel".to_string()],
+        theme,
     );
 }
 
@@ -268,10 +531,10 @@ fn ai_menu_title(status: &AIMenuStatus) -> &str {
 }
 
 /// Renders the ASCII art title banner.
-fn render_title(f: &mut Frame, area: Rect) {
+fn render_title(f: &mut Frame, area: Rect, theme: &Theme) {
     let title_area = center_rect(area, 72, 7);
 
-    let ascii_art = vec![
+    let ascii_art = [
         "OOXXOO  XXOX   OXOO    XOXOXO   XOX    XOOX    OOXXO   XOO   XXOOX",
         "  OO     XO   X          XO    X   X  X        XX     O   O  OO   ",
         "  XO     OX   X          OX    XOOXO  O        OOXX   X   O  OXOO ",
@@ -285,7 +548,7 @@ fn render_title(f: &mut Frame, area: Rect) {
             Line::from(Span::styled(
                 *line,
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ))
         })
@@ -293,21 +556,24 @@ fn render_title(f: &mut Frame, area: Rect) {
     let title = Paragraph::new(lines)
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_type(BorderType::Double),
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(theme.border)),
         );
     f.render_widget(title, title_area);
 }
 
 /// Renders the menu options with highlighting for the selected option.
-fn render_menu_options(f: &mut Frame, area: Rect, menu: &Menu, title: &str) {
-    let options_area = center_rect(area, 30, 13);
+fn render_menu_options(f: &mut Frame, area: Rect, menu: &Menu, title: &str, theme: &Theme) {
+    // Each option takes two lines, below a blank line and between the borders.
+    let height = (menu.options.len() as u16 * 2 + 3).max(13);
+    let options_area = center_rect(area, 30, height);
 
     let mut lines = vec![Line::from("")];
 
@@ -316,7 +582,7 @@ fn render_menu_options(f: &mut Frame, area: Rect, menu: &Menu, title: &str) {
             (
                 Style::default()
                     .add_modifier(Modifier::BOLD)
-                    .fg(Color::LightYellow),
+                    .fg(theme.selection),
                 "  🢒 ",
             )
         } else {
@@ -331,10 +597,11 @@ fn render_menu_options(f: &mut Frame, area: Rect, menu: &Menu, title: &str) {
 
     let block = Block::default()
         .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
         .title(title)
         .title_style(
             Style::default()
-                .fg(Color::LightYellow)
+                .fg(theme.selection)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -346,14 +613,22 @@ fn render_menu_options(f: &mut Frame, area: Rect, menu: &Menu, title: &str) {
 }
 
 /// Renders the instruction text for the main menu.
-fn render_menu_instructions(f: &mut Frame, area: Rect) {
-    let instructions = &["Arrow Keys: Navigate | Enter: Select | Esc: Back | Q: Quit".to_string()];
+fn render_menu_instructions(f: &mut Frame, area: Rect, theme: &Theme) {
+    let instructions =
+        &["Arrow Keys: Navigate | Enter: Select | Esc: Back | F3: Theme | Q: Quit".to_string()];
 
-    render_instructions(f, area, instructions);
+    render_instructions(f, area, instructions, theme);
 }
 
 /// Renders the game screen with board and status.
-fn render_game_ttt(f: &mut Frame, game: &GamePlayTTT, network_status: &NetworkStatus) {
+fn render_game_ttt(
+    f: &mut Frame,
+    game: &GamePlayTTT,
+    network_status: &NetworkStatus,
+    clock: Option<&MoveClock>,
+    scoreboard: &Scoreboard,
+    theme: &Theme,
+) {
     if render_size_warning(f, 10, 10) {
         return;
     }
@@ -363,24 +638,37 @@ fn render_game_ttt(f: &mut Frame, game: &GamePlayTTT, network_status: &NetworkSt
         .margin(0)
         .constraints([
             Constraint::Max(7),
-            Constraint::Min(9),
+            Constraint::Min(12),
             Constraint::Length(4),
         ])
         .split(f.area());
 
-    render_title(f, chunks[0]);
-    render_ttt_board(f, chunks[1], game, network_status);
-    render_ttt_instructions(f, chunks[2], game, network_status);
+    render_title(f, chunks[0], theme);
+    render_ttt_board(f, chunks[1], game, network_status, clock, scoreboard, theme);
+    render_ttt_instructions(f, chunks[2], game, network_status, theme);
 }
 
 /// Renders the tic-tac-toe board with current marks and selection highlight.
-fn render_ttt_board(f: &mut Frame, area: Rect, game: &GamePlayTTT, network_status: &NetworkStatus) {
-    let board_area = center_rect(area, 25, 9);
+fn render_ttt_board(
+    f: &mut Frame,
+    area: Rect,
+    game: &GamePlayTTT,
+    network_status: &NetworkStatus,
+    clock: Option<&MoveClock>,
+    scoreboard: &Scoreboard,
+    theme: &Theme,
+) {
+    let board_area = center_rect(area, 41, 12);
 
-    let mut lines = vec![Line::from("")];
+    // The labels take the place of the blank line above the board.
+    let mut lines = vec![if game.show_coordinates {
+        Line::from(Span::styled(CLASSIC_COLUMN_LABELS, label_style(theme)))
+    } else {
+        Line::from("")
+    }];
 
     // Add current player or game result
-    let (status, status_style) = ttt_game_status(game, network_status);
+    let (status, status_style) = ttt_game_status(game, network_status, theme);
 
     let selection = match (&game.mode, network_status) {
         (GameMode::OnlinePvP(_), NetworkStatus::OpponentDisconnected) => None,
@@ -391,19 +679,76 @@ fn render_ttt_board(f: &mut Frame, area: Rect, game: &GamePlayTTT, network_statu
         },
     };
 
+    let mut cell_styles = PLAIN_CELLS;
+    if let Some(cell) = game.hint_cell {
+        cell_styles[cell.row][cell.col] = Style::default().bg(Color::Cyan);
+    }
+
     // Render the board
     for y in 0..5 {
-        lines.push(ttt_board_line(&game.board, y, selection, Style::default()));
+        let mut line = ttt_board_line(
+            &game.board,
+            y,
+            selection,
+            Style::default(),
+            cell_styles,
+            theme,
+        );
+        if let Some(animation) = game
+            .placement
+            .filter(|animation| animation.cell.row * 2 == y)
+        {
+            let span = &mut line.spans[animation.cell.col * 2];
+            span.style = span.style.patch(placement_style(animation.frames_left));
+        }
+        lines.push(if game.show_coordinates {
+            let row_number = (y % 2 == 0).then(|| (y / 2 + 1).to_string());
+            labeled_line(line, row_number, theme)
+        } else {
+            line
+        });
     }
 
-    let mode_name = match game.mode {
-        GameMode::PvE(_) => "Mode: Play vs AI",
-        GameMode::EvE(_, _) => "Mode: AI vs AI",
-        GameMode::LocalPvP => "Mode: Local PvP",
-        GameMode::OnlinePvP(_) => "Mode: Online PvP",
-    };
+    if let Some(clock) = clock {
+        lines.push(countdown_line(clock));
+    }
+    if let Some(tip) = game.tip() {
+        lines.push(tip_line(tip));
+    }
+    if let Some(mv) = game.hint() {
+        lines.push(hint_line(mv, game.hint_strength));
+    }
+    if game.puzzle.is_some() {
+        lines.push(Line::from(format!("Solved: {}", game.puzzles_solved)));
+    }
+    if game.puzzle_missed {
+        lines.push(Line::from(Span::styled(
+            "Not the best move, try again",
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    if let Some(mv) = game.missed_move {
+        lines.push(Line::from(Span::styled(
+            format!("Better: {}", move_name(mv)),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    if game.show_graph {
+        lines.push(graph_line(&game.evaluations));
+    }
+    if game.show_analysis {
+        lines.push(analysis_line(game.analysis(), theme));
+    }
+    if let Some(ranking) = game.ranked_moves() {
+        lines.push(ranking_line(ranking));
+    }
 
-    let game_block = game_block(mode_name, status.as_str(), status_style);
+    let mut mode_name = mode_name(&game.mode);
+    if game.board.rules() == RuleSet::Misere {
+        mode_name = format!("{mode_name} ({})", RuleSet::Misere.name());
+    }
+    let mode_name = with_scoreboard(mode_name, scoreboard);
+    let game_block = game_block(mode_name.as_str(), status.as_str(), status_style, theme);
 
     let board = Paragraph::new(lines)
         .alignment(Alignment::Center)
@@ -412,9 +757,122 @@ fn render_ttt_board(f: &mut Frame, area: Rect, game: &GamePlayTTT, network_statu
     f.render_widget(board, board_area);
 }
 
-fn game_block<'a>(mode_name: &'a str, status: &'a str, status_style: Style) -> Block<'a> {
+/// Renders a Gomoku or 4x4 game with its board, status and instructions.
+fn render_game_gomoku(f: &mut Frame, game: &GamePlayGomoku, theme: &Theme) {
+    if render_size_warning(f, 10, 10) {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(0)
+        .constraints([
+            Constraint::Max(7),
+            Constraint::Min(13),
+            Constraint::Length(4),
+        ])
+        .split(f.area());
+
+    render_title(f, chunks[0], theme);
+
+    let size = game.board.size;
+    let board_area = center_rect(chunks[1], 41, size as u16 * 2 + 2);
+    let mut lines = vec![Line::from("")];
+    for row in 0..size {
+        if row > 0 {
+            lines.push(Line::from(vec!["───"; size].join("┼")));
+        }
+        lines.push(gomoku_board_line(
+            &game.board,
+            row,
+            game.selected,
+            game.active_player,
+            theme,
+        ));
+    }
+
+    let (status, status_style) = game_status(game.board.state, game.active_player, theme);
+    let mode_name = format!(
+        "Mode: {}, {} in a row",
+        game.kind.name(),
+        game.board.win_len
+    );
+    let board = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(game_block(
+            mode_name.as_str(),
+            status.as_str(),
+            status_style,
+            theme,
+        ));
+    f.render_widget(board, board_area);
+
+    let instructions = if game.board.state == GameState::Playing {
+        vec![
+            "Arrow Keys: Move | Enter: Place Mark".to_string(),
+            "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+        ]
+    } else {
+        vec!["R: Reset Game | M: Main Menu | Q: Quit".to_string()]
+    };
+    render_game_instructions(f, chunks[2], &instructions, theme);
+}
+
+/// Builds one row of a Gomoku board, showing the active mark on the selected empty cell.
+fn gomoku_board_line(
+    board: &GenericBoard,
+    row: usize,
+    selected: Position,
+    active_player: Mark,
+    theme: &Theme,
+) -> Line<'static> {
+    let mut spans = vec![];
+    for col in 0..board.size {
+        let selected_here = selected == Position { row, col } && board.state == GameState::Playing;
+        let (content, style) = match board.get(row, col) {
+            Some(mark) => (theme.glyph(mark), theme.mark_style(mark)),
+            None if selected_here => (
+                theme.glyph(active_player),
+                theme.mark_style(active_player).fg(theme.selection),
+            ),
+            None => (" ", Style::default()),
+        };
+        spans.push(Span::styled(format!(" {content} "), style));
+        if col + 1 < board.size {
+            spans.push(Span::raw("│"));
+        }
+    }
+    Line::from(spans)
+}
+
+/// Describes the game mode, naming the engines of any AI players.
+fn mode_name(mode: &GameMode) -> String {
+    match mode {
+        GameMode::PvE(ai) => format!("Play vs AI: {}", ai.name()),
+        GameMode::EvE(ai_x, ai_o) => format!("{} vs {}", ai_x.name(), ai_o.name()),
+        GameMode::LocalPvP => "Mode: Local PvP".to_string(),
+        GameMode::OnlinePvP(_) => "Mode: Online PvP".to_string(),
+        GameMode::Solitaire => "Mode: Solitaire".to_string(),
+    }
+}
+
+/// Appends the scoreboard to the mode name once a game has finished.
+fn with_scoreboard(mode_name: String, scoreboard: &Scoreboard) -> String {
+    if scoreboard.games() == 0 {
+        return mode_name;
+    }
+    format!("{mode_name} | {scoreboard}")
+}
+
+fn game_block<'a>(
+    mode_name: &'a str,
+    status: &'a str,
+    status_style: Style,
+    theme: &Theme,
+) -> Block<'a> {
     Block::default()
         .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
         .title(
             Line::from(status)
                 .style(status_style)
@@ -425,18 +883,136 @@ fn game_block<'a>(mode_name: &'a str, status: &'a str, status_style: Style) -> B
             Line::from(mode_name)
                 .style(
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.title)
                         .add_modifier(Modifier::BOLD),
                 )
                 .alignment(Alignment::Center),
         )
 }
 
+/// Builds the countdown bar of the current move, blinking in red when time runs low.
+fn countdown_line(clock: &MoveClock) -> Line<'static> {
+    let filled = (clock.fraction_left() * COUNTDOWN_WIDTH as f32).ceil() as usize;
+    let bar = format!(
+        "{}{}",
+        "█".repeat(filled),
+        "░".repeat(COUNTDOWN_WIDTH - filled)
+    );
+    let style = if clock.is_low() {
+        Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK)
+    } else {
+        Style::default().fg(Color::Green)
+    };
+    Line::from(vec![
+        Span::styled(bar, style),
+        Span::styled(
+            format!(" {:>3}s", clock.remaining().as_secs_f32().ceil() as u64),
+            style,
+        ),
+    ])
+}
+
+fn graph_line(evaluations: &[f32]) -> Line<'static> {
+    Line::from(Span::styled(
+        sparkline(evaluations, GRAPH_WIDTH),
+        Style::default().fg(Color::Magenta),
+    ))
+}
+
+/// Shows the evaluation of the position as the leading mark and its advantage.
+fn analysis_line(evaluation: f32, theme: &Theme) -> Line<'static> {
+    let leader = if evaluation > 0.05 {
+        Some(Mark::X)
+    } else if evaluation < -0.05 {
+        Some(Mark::O)
+    } else {
+        None
+    };
+    let (text, style) = match leader {
+        Some(mark) => (
+            format!("Eval: {mark} +{:.1}", evaluation.abs()),
+            Style::default().fg(theme.mark_color(mark)),
+        ),
+        None => ("Eval: even".to_string(), Style::default()),
+    };
+    Line::from(Span::styled(text, style))
+}
+
+/// Lists the best ranked moves with their winning chance in percent.
+fn ranking_line(ranking: &[(Move, f32)]) -> Line<'static> {
+    let moves: Vec<String> = ranking
+        .iter()
+        .take(RANKED_MOVES_SHOWN)
+        .map(|(mv, chance)| format!("{} {:.0}%", move_name(*mv), chance * 100.0))
+        .collect();
+    Line::from(format!("Best: {}", moves.join(", ")))
+}
+
+/// Draws values between 0.0 and 1.0 as a sparkline of at most `width` characters.
+///
+/// When there are more values than characters, consecutive values are
+/// averaged so the whole game still fits.
+fn sparkline(values: &[f32], width: usize) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+
+    let columns = values.len().min(width);
+    (0..columns)
+        .map(|column| {
+            let start = column * values.len() / columns;
+            let end = (column + 1) * values.len() / columns;
+            let bucket = &values[start..end];
+            let average = bucket.iter().sum::<f32>() / bucket.len() as f32;
+            let level = (average.clamp(0.0, 1.0) * (LEVELS.len() - 1) as f32).round();
+            LEVELS[level as usize]
+        })
+        .collect()
+}
+
+fn hint_line(mv: Move, strength: HintStrength) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("Hint: {} ({})", move_name(mv), strength.label()),
+        Style::default().fg(Color::Green),
+    ))
+}
+
+/// Describes a move by the positions of its board and cell.
+fn move_name(mv: Move) -> String {
+    const POSITIONS: [[&str; 3]; 3] = [
+        ["top left", "top", "top right"],
+        ["left", "center", "right"],
+        ["bottom left", "bottom", "bottom right"],
+    ];
+    match mv {
+        Move::Base(row, col) => POSITIONS[row][col].to_string(),
+        Move::Ultimate(board_row, board_col, cell_row, cell_col) => format!(
+            "{} board, {}",
+            POSITIONS[board_row][board_col], POSITIONS[cell_row][cell_col]
+        ),
+    }
+}
+
+fn tip_line(tip: Tip) -> Line<'static> {
+    Line::from(Span::styled(
+        tip.message(),
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::ITALIC),
+    ))
+}
+
 fn ttt_board_line(
     board: &SmallBoard,
     y: usize,
     selection: Option<(Position, Mark)>,
     board_style: Style,
+    empty_cell_styles: [[Style; 3]; 3],
+    theme: &Theme,
 ) -> Line<'static> {
     let row = match y {
         val if val >= 5 => {
@@ -448,43 +1024,36 @@ fn ttt_board_line(
         }
     };
     let mut row_spans = vec![];
-    for col in 0..3 {
+    for (col, empty_style) in empty_cell_styles[row].into_iter().enumerate() {
         let (cell_content, style) = match board.get(row, col) {
-            Some(Mark::X) => (
-                "X",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-            Some(Mark::O) => (
-                "O",
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Some(mark) => (theme.glyph(mark), theme.mark_style(mark)),
             None => {
                 if let Some((position, mark)) = selection {
                     if row == position.row
                         && col == position.col
                         && board.state == GameState::Playing
                     {
-                        let display = match mark {
-                            Mark::X => "X",
-                            Mark::O => "O",
-                        };
                         (
-                            display,
-                            Style::default()
-                                .fg(Color::LightYellow)
-                                .add_modifier(Modifier::BOLD),
+                            theme.glyph(mark),
+                            theme.mark_style(mark).fg(theme.selection),
                         )
                     } else {
-                        (" ", Style::default())
+                        (" ", empty_style)
                     }
                 } else {
-                    (" ", Style::default())
+                    (" ", empty_style)
                 }
             }
         };
 
+        let winning = board
+            .winning_line()
+            .is_some_and(|line| line.contains(&Position { row, col }));
+        let style = if winning {
+            style.add_modifier(Modifier::REVERSED | Modifier::SLOW_BLINK)
+        } else {
+            style
+        };
         row_spans.push(Span::styled(format!(" {} ", cell_content), style));
 
         if col < 2 {
@@ -500,6 +1069,7 @@ fn render_ttt_instructions(
     area: Rect,
     game: &GamePlayTTT,
     network_status: &NetworkStatus,
+    theme: &Theme,
 ) {
     let instructions = if matches!(game.mode, GameMode::OnlinePvP(_))
         && matches!(network_status, NetworkStatus::OpponentDisconnected)
@@ -528,39 +1098,53 @@ fn render_ttt_instructions(
                 if game.turn == 0 {
                     vec![
                         "S: Let O Move First | Enter: Let Active AI play".to_string(),
-                        "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+                        "G: Graph | R: Reset Game | E: Replay Seed | M: Main Menu | Q: Quit"
+                            .to_string(),
                     ]
                 } else {
                     vec![
                         "Enter: Let Active AI play".to_string(),
-                        "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
-                    ]
-                }
-            }
-            _ => {
-                if game.turn != 0 || game.mode == GameMode::LocalPvP {
-                    vec![
-                        "Arrow Keys: Move | Enter: Place Mark".to_string(),
-                        "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
-                    ]
-                } else {
-                    vec![
-                        "S: Play Second | Arrow Keys: Move | Enter: Place Mark".to_string(),
-                        "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+                        "G: Graph | R: Reset Game | E: Replay Seed | M: Main Menu | Q: Quit"
+                            .to_string(),
                     ]
                 }
             }
+            GameMode::Solitaire => vec![
+                "Arrow Keys: Move | Enter: Answer".to_string(),
+                "R: New Puzzle | M: Main Menu | Q: Quit".to_string(),
+            ],
+            _ if game.split_keys => vec![
+                "X: WASD + Space | O: Arrow Keys + Enter | U: Undo | Ctrl+R: Redo".to_string(),
+                "T: Tips | ?: Hint | F2: Save | R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+            ],
+            GameMode::PvE(_) => vec![
+                format!(
+                    "You: {} | Arrow Keys: Move | Enter: Place Mark | U: Undo | Ctrl+R: Redo",
+                    game.human_mark().unwrap_or(Mark::X)
+                ),
+                "T: Tips | ?: Hint | F2: Save | R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+            ],
+            _ => vec![
+                "Arrow Keys: Move | Enter: Place Mark | U: Undo | Ctrl+R: Redo".to_string(),
+                "T: Tips | ?: Hint | F2: Save | R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+            ],
         }
     } else if matches!(game.mode, GameMode::OnlinePvP(_)) {
         vec!["R: Rematch | M: Main Menu | Q: Quit".to_string()]
+    } else if matches!(game.mode, GameMode::LocalPvP | GameMode::PvE(_)) {
+        vec!["U: Undo | R: Reset Game | M: Main Menu | Q: Quit".to_string()]
     } else {
         vec!["R: Reset Game | M: Main Menu | Q: Quit".to_string()]
     };
 
-    render_instructions(f, area, &instructions);
+    render_game_instructions(f, area, &instructions, theme);
 }
 
-fn ttt_game_status(game: &GamePlayTTT, network_status: &NetworkStatus) -> (String, Style) {
+fn ttt_game_status(
+    game: &GamePlayTTT,
+    network_status: &NetworkStatus,
+    theme: &Theme,
+) -> (String, Style) {
     if matches!(game.mode, GameMode::OnlinePvP(_))
         && matches!(network_status, NetworkStatus::OpponentDisconnected)
     {
@@ -571,7 +1155,54 @@ fn ttt_game_status(game: &GamePlayTTT, network_status: &NetworkStatus) -> (Strin
                 .add_modifier(Modifier::BOLD),
         );
     }
-    let (status, style) = game_status(game.board.state, game.active_player);
+    if let (GameMode::OnlinePvP(_), NetworkStatus::Failed(error)) = (&game.mode, network_status) {
+        return (
+            format!("Connection error: {error}"),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        );
+    }
+    if game.aborted.is_some() {
+        return (
+            "Aborted: move cap reached".to_string(),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        );
+    }
+    if let Some(mark) = game.flagged {
+        return (
+            format!("Player {mark} ran out of time"),
+            Style::default()
+                .fg(theme.mark_color(mark.switch()))
+                .add_modifier(Modifier::BOLD),
+        );
+    }
+    if let Some(puzzle) = game.puzzle {
+        return (
+            puzzle.prompt(),
+            Style::default()
+                .fg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        );
+    }
+    let (mut status, style) = game_status(game.board.state, game.active_player, theme);
+    if game.players_swapped {
+        status.push_str(" | P1 O, P2 X");
+    }
+    if game.is_analyzing() {
+        status.push_str(" | Analysis");
+    }
+    if game.read_only {
+        status.push_str(" | Read-only");
+    }
+    if let Some(mark) = game.draw_offer {
+        status.push_str(&format!(" | {mark} offers a draw (Y/N)"));
+    }
+    if let Some(clock) = &game.clock {
+        status.push_str(&format!(
+            " | X {} O {}",
+            clock_time(clock.remaining(Mark::X)),
+            clock_time(clock.remaining(Mark::O))
+        ));
+    }
     if game.board.state == GameState::Playing
         && matches!(
             game.mode,
@@ -584,7 +1215,17 @@ fn ttt_game_status(game: &GamePlayTTT, network_status: &NetworkStatus) -> (Strin
     }
 }
 
-fn utt_game_status(game: &GamePlayUTT, network_status: &NetworkStatus) -> (String, Style) {
+/// Formats the time left on a game clock as minutes and seconds, rounded up.
+fn clock_time(remaining: Duration) -> String {
+    let seconds = remaining.as_secs_f32().ceil() as u64;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn utt_game_status(
+    game: &GamePlayUTT,
+    network_status: &NetworkStatus,
+    theme: &Theme,
+) -> (String, Style) {
     if matches!(game.mode, GameMode::OnlinePvP(_))
         && matches!(network_status, NetworkStatus::OpponentDisconnected)
     {
@@ -595,7 +1236,34 @@ fn utt_game_status(game: &GamePlayUTT, network_status: &NetworkStatus) -> (Strin
                 .add_modifier(Modifier::BOLD),
         );
     }
-    let (status, style) = game_status(game.big_board.state, game.active_player);
+    if game.aborted.is_some() {
+        return (
+            "Aborted: move cap reached".to_string(),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        );
+    }
+    let (mut status, style) = game_status(game.big_board.state, game.active_player, theme);
+    if game.big_board.state == GameState::Playing {
+        status.push_str(&format!(
+            " | Threats X:{} O:{}",
+            game.big_board.meta_threats(Mark::X),
+            game.big_board.meta_threats(Mark::O)
+        ));
+        let sends_anywhere = game.is_local_turn()
+            && game
+                .selected_cell
+                .and_then(|cell| game.destination(cell.row, cell.col))
+                == Some(Destination::Closed);
+        if sends_anywhere {
+            status.push_str(" | Next: free choice");
+        }
+    }
+    if game.read_only {
+        status.push_str(" | Read-only");
+    }
+    if let Some(mark) = game.draw_offer {
+        status.push_str(&format!(" | {mark} offers a draw (Y/N)"));
+    }
     if game.big_board.state == GameState::Playing
         && matches!(
             game.mode,
@@ -608,33 +1276,36 @@ fn utt_game_status(game: &GamePlayUTT, network_status: &NetworkStatus) -> (Strin
     }
 }
 
-fn game_status(game_state: GameState, current_player: Mark) -> (String, Style) {
+fn game_status(game_state: GameState, current_player: Mark, theme: &Theme) -> (String, Style) {
     match game_state {
         GameState::Playing => (
             format!("Current Player: {}", current_player),
             Style::default()
-                .fg(Color::LightYellow)
+                .fg(theme.mark_color(current_player))
                 .add_modifier(Modifier::BOLD),
         ),
-        GameState::Won(Mark::X) => (
-            "Player X WINS!".to_string(),
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        ),
-        GameState::Won(Mark::O) => (
-            "Player O WINS!".to_string(),
+        GameState::Won(mark) => (
+            format!("Player {mark} WINS!"),
             Style::default()
-                .fg(Color::Blue)
+                .fg(theme.mark_color(mark))
                 .add_modifier(Modifier::BOLD),
         ),
         GameState::Draw => (
             "DRAW!".to_string(),
-            Style::default().fg(PURPLE).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.draw).add_modifier(Modifier::BOLD),
         ),
     }
 }
 
 /// Renders the Ultimate Tic-Tac-Toe game screen.
-fn render_game_utt(f: &mut Frame, game: &GamePlayUTT, network_status: &NetworkStatus) {
+fn render_game_utt(
+    f: &mut Frame,
+    game: &GamePlayUTT,
+    network_status: &NetworkStatus,
+    clock: Option<&MoveClock>,
+    scoreboard: &Scoreboard,
+    theme: &Theme,
+) {
     if render_size_warning(f, 43, 20) {
         return;
     }
@@ -644,36 +1315,133 @@ fn render_game_utt(f: &mut Frame, game: &GamePlayUTT, network_status: &NetworkSt
         // .margin(0)
         .constraints([
             Constraint::Max(7),
-            Constraint::Min(21),
+            Constraint::Min(23),
             Constraint::Length(4),
         ])
         .split(f.area());
 
-    render_title(f, chunks[0]);
-    render_utt_board(f, chunks[1], game, network_status);
-    render_utt_instructions(f, chunks[2], game, network_status);
+    render_title(f, chunks[0], theme);
+    render_utt_board(f, chunks[1], game, network_status, clock, scoreboard, theme);
+    render_utt_instructions(f, chunks[2], game, network_status, theme);
 }
 
 /// Renders the Ultimate Tic-Tac-Toe board.
-fn render_utt_board(f: &mut Frame, area: Rect, game: &GamePlayUTT, network_status: &NetworkStatus) {
-    let board_area = center_rect(area, 47, 21);
+fn render_utt_board(
+    f: &mut Frame,
+    area: Rect,
+    game: &GamePlayUTT,
+    network_status: &NetworkStatus,
+    clock: Option<&MoveClock>,
+    scoreboard: &Scoreboard,
+    theme: &Theme,
+) {
+    let board_area = center_rect(area, 47, 23);
 
-    let mut lines = vec![Line::from("")];
+    // The labels take the place of the blank line above the board.
+    let mut lines = vec![if game.show_coordinates {
+        Line::from(Span::styled(ULTIMATE_COLUMN_LABELS, label_style(theme)))
+    } else {
+        Line::from("")
+    }];
 
     // Add current player or game result
-    let (status, status_style) = utt_game_status(game, network_status);
+    let (status, status_style) = utt_game_status(game, network_status, theme);
 
     // Render the meta-board (3x3 grid of small boards)
+    let grid_lines = utt_grid_lines(
+        &game.big_board,
+        |big_row, big_col| {
+            small_board_selection_style(game, big_row, big_col, network_status, theme)
+        },
+        |big_row, big_col| destination_styles(game, big_row, big_col),
+        game.show_big_marks,
+        theme,
+    );
+    if game.show_coordinates {
+        // Each board row spans five lines followed by a separator, and its
+        // index is shown next to the middle one.
+        lines.extend(grid_lines.into_iter().enumerate().map(|(index, line)| {
+            let board_row = (index % 6 == 2).then(|| (index / 6).to_string());
+            labeled_line(line, board_row, theme)
+        }));
+    } else {
+        lines.extend(grid_lines);
+    }
+
+    if game.free_move && game.big_board.state == GameState::Playing {
+        lines.push(Line::from(Span::styled(
+            "Free move: choose any board",
+            Style::default()
+                .fg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    if let Some(clock) = clock {
+        lines.push(countdown_line(clock));
+    }
+    if let Some(tip) = game.tip() {
+        lines.push(tip_line(tip));
+    }
+    if let Some(mv) = game.hint() {
+        lines.push(hint_line(mv, game.hint_strength));
+    }
+    if game.show_graph {
+        lines.push(graph_line(&game.evaluations));
+    }
+    if game.show_analysis {
+        lines.push(analysis_line(game.analysis(), theme));
+    }
+
+    let mode_name = with_scoreboard(mode_name(&game.mode), scoreboard);
+    let game_block = game_block(mode_name.as_str(), status.as_str(), status_style, theme);
+
+    let board = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(game_block);
+
+    f.render_widget(board, board_area);
+}
+
+/// Style of the coordinate labels drawn around the boards.
+fn label_style(theme: &Theme) -> Style {
+    Style::default().fg(theme.title)
+}
+
+/// Adds a one-character `label`, or a blank, to the left of a board line.
+///
+/// The right side is padded as much, so the centered board does not move.
+fn labeled_line(line: Line<'static>, label: Option<String>, theme: &Theme) -> Line<'static> {
+    let label = label.unwrap_or_else(|| " ".to_string());
+    let mut spans = vec![Span::styled(format!("{label} "), label_style(theme))];
+    spans.extend(line.spans);
+    spans.push(Span::raw("  "));
+    Line::from(spans)
+}
+
+/// Builds the lines of the Ultimate meta-board (3x3 grid of small boards).
+///
+/// `board_style` provides the selection and style of the small board at each position,
+/// and `empty_cell_styles` the styles of its empty cells. With `big_marks`, won
+/// small boards are drawn as one large mark instead of their cells.
+fn utt_grid_lines(
+    big_board: &BigBoard,
+    board_style: impl Fn(usize, usize) -> (Option<(Position, Mark)>, Style),
+    empty_cell_styles: impl Fn(usize, usize) -> [[Style; 3]; 3],
+    big_marks: bool,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
     for big_y in 0..5 {
         let big_row = match big_y {
             even if even % 2 == 0 => even / 2,
             _ => {
-                let mut y_spans: Vec<Span> = Vec::new();
-                y_spans.push(Span::from("━".repeat(12)));
-                y_spans.push(Span::from("╋"));
-                y_spans.push(Span::from("━".repeat(13)));
-                y_spans.push(Span::from("╋"));
-                y_spans.push(Span::from("━".repeat(12)));
+                let y_spans = vec![
+                    Span::from("━".repeat(12)),
+                    Span::from("╋"),
+                    Span::from("━".repeat(13)),
+                    Span::from("╋"),
+                    Span::from("━".repeat(12)),
+                ];
                 lines.push(Line::from(y_spans));
                 continue;
             }
@@ -688,31 +1456,79 @@ fn render_utt_board(f: &mut Frame, area: Rect, game: &GamePlayUTT, network_statu
                         continue;
                     }
                 };
-                let (selection, board_style) =
-                    small_board_selection_style(game, big_row, big_col, network_status);
-                let small_board = game.big_board.get_board(big_row, big_col);
+                let (selection, style) = board_style(big_row, big_col);
+                let small_board = big_board.get_board(big_row, big_col);
+                if big_marks && let GameState::Won(mark) = small_board.state {
+                    let rows = match mark {
+                        Mark::X => BIG_X,
+                        Mark::O => BIG_O,
+                    };
+                    y_spans.push(Span::styled(rows[small_y], theme.mark_style(mark)));
+                    continue;
+                }
 
-                y_spans
-                    .append(&mut ttt_board_line(small_board, small_y, selection, board_style).spans)
+                let cell_styles = empty_cell_styles(big_row, big_col);
+                y_spans.append(
+                    &mut ttt_board_line(small_board, small_y, selection, style, cell_styles, theme)
+                        .spans,
+                )
             }
             lines.push(Line::from(y_spans));
         }
     }
+    lines
+}
 
-    let mode_name = match game.mode {
-        GameMode::PvE(_) => "Mode: Play vs AI",
-        GameMode::EvE(_, _) => "Mode: AI vs AI",
-        GameMode::LocalPvP => "Mode: Local PvP",
-        GameMode::OnlinePvP(_) => "Mode: Online PvP",
-    };
+/// Background of a just-placed mark, from light gray fading to dark gray as
+/// `frames_left` runs down.
+fn placement_style(frames_left: u8) -> Style {
+    Style::default().bg(Color::Indexed(
+        235 + 3 * frames_left.min(PLACEMENT_ANIMATION_FRAMES),
+    ))
+}
 
-    let game_block = game_block(mode_name, status.as_str(), status_style);
+/// Styles of empty cells without any highlight.
+const PLAIN_CELLS: [[Style; 3]; 3] = [[Style::new(); 3]; 3];
 
-    let board = Paragraph::new(lines)
-        .alignment(Alignment::Center)
-        .block(game_block);
+/// Colors the empty cells of the board being played by where they send the opponent.
+///
+/// Cells leading to an open board are green and cells giving the opponent a free
+/// choice are red. Other boards, and games without a local cell selection, are plain.
+fn destination_styles(game: &GamePlayUTT, big_row: usize, big_col: usize) -> [[Style; 3]; 3] {
+    let selecting_cell = game.selected_cell.is_some()
+        && game.selected_board.row == big_row
+        && game.selected_board.col == big_col;
+    if !game.show_destinations || !selecting_cell || !game.is_local_turn() {
+        return PLAIN_CELLS;
+    }
+    let mut styles = PLAIN_CELLS;
+    for (cell_row, row_styles) in styles.iter_mut().enumerate() {
+        for (cell_col, style) in row_styles.iter_mut().enumerate() {
+            *style = match game.destination(cell_row, cell_col) {
+                Some(Destination::Open) => Style::default().bg(Color::Green),
+                Some(Destination::Closed) => Style::default().bg(Color::Red),
+                None => Style::default(),
+            };
+        }
+    }
+    styles
+}
 
-    f.render_widget(board, board_area);
+/// Styles a small board by its completion state, highlighting the active board.
+fn small_board_state_style(
+    big_board: &BigBoard,
+    big_row: usize,
+    big_col: usize,
+    theme: &Theme,
+) -> Style {
+    match big_board.sub_state(big_row, big_col) {
+        GameState::Draw => Style::default().fg(theme.draw),
+        GameState::Won(mark) => Style::default().fg(theme.mark_color(mark)),
+        GameState::Playing => match big_board.active_board {
+            Some(selected) if selected == (big_row, big_col) => Style::default().fg(Color::Green),
+            _ => Style::default(),
+        },
+    }
 }
 
 fn small_board_selection_style(
@@ -720,20 +1536,14 @@ fn small_board_selection_style(
     big_row: usize,
     big_col: usize,
     network_status: &NetworkStatus,
+    theme: &Theme,
 ) -> (Option<(Position, Mark)>, Style) {
     let small_board = game.big_board.get_board(big_row, big_col);
     if matches!(game.mode, GameMode::EvE(_, _)) {
-        match small_board.state {
-            GameState::Draw => (None, Style::default().fg(PURPLE)),
-            GameState::Won(Mark::X) => (None, Style::default().fg(Color::Red)),
-            GameState::Won(Mark::O) => (None, Style::default().fg(Color::Blue)),
-            GameState::Playing => match game.big_board.active_board {
-                Some(selected) if selected == (big_row, big_col) => {
-                    (None, Style::default().fg(Color::Green))
-                }
-                _ => (None, Style::default()),
-            },
-        }
+        (
+            None,
+            small_board_state_style(&game.big_board, big_row, big_col, theme),
+        )
     } else {
         let selection_visible = match game.mode {
             GameMode::OnlinePvP(local_mark) => {
@@ -742,9 +1552,17 @@ fn small_board_selection_style(
             }
             _ => true,
         };
-        if selection_visible
-            && game.selected_board.row == big_row
-            && game.selected_board.col == big_col
+        let board = Position {
+            row: big_row,
+            col: big_col,
+        };
+        if selection_visible && game.target_board() == Some(board) {
+            let selection = (game.selected_board == board)
+                .then(|| game.selected_cell.map(|cell| (cell, game.active_player)))
+                .flatten();
+            (selection, Style::default().fg(theme.destination))
+        } else if selection_visible
+            && game.selected_board == board
             && game.big_board.state == GameState::Playing
         {
             match game.selected_cell {
@@ -752,14 +1570,13 @@ fn small_board_selection_style(
                     Some((position, game.active_player)),
                     Style::default().fg(Color::Green),
                 ),
-                None => (None, Style::default().fg(Color::LightYellow)),
+                None => (None, Style::default().fg(theme.selection)),
             }
         } else {
             match small_board.state {
                 GameState::Playing => (None, Style::default()),
-                GameState::Draw => (None, Style::default().fg(PURPLE)),
-                GameState::Won(Mark::X) => (None, Style::default().fg(Color::Red)),
-                GameState::Won(Mark::O) => (None, Style::default().fg(Color::Blue)),
+                GameState::Draw => (None, Style::default().fg(theme.draw)),
+                GameState::Won(mark) => (None, Style::default().fg(theme.mark_color(mark))),
             }
         }
     }
@@ -771,6 +1588,7 @@ fn render_utt_instructions(
     area: Rect,
     game: &GamePlayUTT,
     network_status: &NetworkStatus,
+    theme: &Theme,
 ) {
     let instructions = if matches!(game.mode, GameMode::OnlinePvP(_))
         && matches!(network_status, NetworkStatus::OpponentDisconnected)
@@ -805,32 +1623,216 @@ fn render_utt_instructions(
             ],
             GameMode::EvE(_, _) if game.turn == 0 => vec![
                 "S: Let O Move First | Enter: Let Active AI play".to_string(),
-                "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+                "G: Graph | R: Reset Game | E: Replay Seed | M: Main Menu | Q: Quit".to_string(),
             ],
             GameMode::EvE(_, _) => vec![
                 "Enter: Let Active AI play".to_string(),
-                "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+                "G: Graph | R: Reset Game | E: Replay Seed | M: Main Menu | Q: Quit".to_string(),
+            ],
+            _ if game.split_keys => vec![
+                "X: WASD + Space | O: Arrow Keys + Enter".to_string(),
+                "Esc: Change Board | T: Tips | F2: Save | R: Reset Game | M: Main Menu | Q: Quit"
+                    .to_string(),
             ],
             _ if game.selected_cell.is_none() => vec![
                 "Arrow Keys: Select Board | Enter: Confirm Board".to_string(),
-                "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+                "T: Tips | F2: Save | R: Reset Game | M: Main Menu | Q: Quit".to_string(),
             ],
             _ if game.big_board.active_board.is_none() => vec![
                 "Arrow Keys: Select Cell | Enter: Place Mark".to_string(),
-                "Esc: Change Board | R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+                "Esc: Change Board | T: Tips | F2: Save | R: Reset Game | M: Main Menu | Q: Quit"
+                    .to_string(),
             ],
             _ => vec![
                 "Arrow Keys: Select Cell | Enter: Place Mark".to_string(),
-                "R: Reset Game | M: Main Menu | Q: Quit".to_string(),
+                "T: Tips | F2: Save | R: Reset Game | M: Main Menu | Q: Quit".to_string(),
             ],
         }
     } else if matches!(game.mode, GameMode::OnlinePvP(_)) {
         vec!["R: Rematch | M: Main Menu | Q: Quit".to_string()]
+    } else if matches!(game.mode, GameMode::LocalPvP | GameMode::PvE(_)) {
+        vec!["U: Undo | R: Reset Game | M: Main Menu | Q: Quit".to_string()]
     } else {
         vec!["R: Reset Game | M: Main Menu | Q: Quit".to_string()]
     };
 
-    render_instructions(f, area, &instructions);
+    render_game_instructions(f, area, &instructions, theme);
+}
+
+/// Renders the replay screen with the current game and playback controls.
+fn render_replay(f: &mut Frame, replay: &Replay, theme: &Theme) {
+    if render_size_warning(f, 43, 20) {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Max(7),
+            Constraint::Min(21),
+            Constraint::Length(4),
+        ])
+        .split(f.area());
+
+    render_title(f, chunks[0], theme);
+
+    let mut lines = vec![Line::from("")];
+    let board_area = match replay.board {
+        Some(RecordBoard::Classic(board)) => {
+            for y in 0..5 {
+                lines.push(ttt_board_line(
+                    &board,
+                    y,
+                    None,
+                    Style::default(),
+                    PLAIN_CELLS,
+                    theme,
+                ));
+            }
+            center_rect(chunks[1], 29, 9)
+        }
+        Some(RecordBoard::Ultimate(big_board)) => {
+            lines.extend(utt_grid_lines(
+                &big_board,
+                |big_row, big_col| {
+                    (
+                        None,
+                        small_board_state_style(&big_board, big_row, big_col, theme),
+                    )
+                },
+                |_, _| PLAIN_CELLS,
+                false,
+                theme,
+            ));
+            center_rect(chunks[1], 47, 21)
+        }
+        None => center_rect(chunks[1], 47, 5),
+    };
+    if let Some(message) = &replay.message {
+        lines.push(Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let (status, status_style) = match (replay.record(), replay.board) {
+        (Some(record), Some(board)) if replay.is_finished() => {
+            let next_player = if replay.ply.is_multiple_of(2) {
+                record.first_player
+            } else {
+                record.first_player.switch()
+            };
+            let (status, style) = game_status(board.state(), next_player, theme);
+            (
+                format!(
+                    "Game {}/{}: {}",
+                    replay.current + 1,
+                    replay.records.len(),
+                    status
+                ),
+                style,
+            )
+        }
+        (Some(record), _) => (
+            format!(
+                "Game {}/{}: Move {}/{}",
+                replay.current + 1,
+                replay.records.len(),
+                replay.ply,
+                record.moves.len()
+            ),
+            Style::default()
+                .fg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        ),
+        (None, _) => (
+            "No replay".to_string(),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+    };
+    let board = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(game_block(
+            "Mode: Replay",
+            status.as_str(),
+            status_style,
+            theme,
+        ));
+    f.render_widget(board, board_area);
+
+    render_instructions(
+        f,
+        chunks[2],
+        &[
+            format!("Up/Down: Faster/Slower ({}ms)", replay.delay.as_millis()),
+            "Enter: Next Game | Esc: Back | Q: Quit".to_string(),
+        ],
+        theme,
+    );
+}
+
+/// Renders the session statistics, split by mark and by first mover.
+fn render_statistics(f: &mut Frame, results: &[GameResult], theme: &Theme) {
+    if render_size_warning(f, 36, 17) {
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Max(7),
+            Constraint::Min(7),
+            Constraint::Length(3),
+        ])
+        .split(f.area());
+
+    render_title(f, chunks[0], theme);
+
+    let stats = MarkStats::from_results(results);
+    let lines = vec![
+        Line::from(""),
+        Line::from(format!("{:<6}{:>13}{:>13}", "", "First", "Second")),
+        Line::from(Span::styled(
+            stats_row("X", &stats.x_first, &stats.x_second),
+            Style::default().fg(theme.x_color),
+        )),
+        Line::from(Span::styled(
+            stats_row("O", &stats.o_first, &stats.o_second),
+            Style::default().fg(theme.o_color),
+        )),
+    ];
+    let status = format!(
+        "Games played: {}",
+        stats.x_first.games + stats.x_second.games
+    );
+    let table = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(game_block(
+            "Win Rate by Mark",
+            status.as_str(),
+            Style::default().add_modifier(Modifier::BOLD),
+            theme,
+        ));
+    f.render_widget(table, center_rect(chunks[1], 36, 6));
+
+    render_instructions(f, chunks[2], &["Esc: Back | Q: Quit".to_string()], theme);
+}
+
+fn stats_row(mark: &str, first: &SeatRecord, second: &SeatRecord) -> String {
+    format!(
+        "{:<6}{:>13}{:>13}",
+        mark,
+        seat_rate(first),
+        seat_rate(second)
+    )
+}
+
+/// Formats a win rate with its underlying counts, e.g. `60% (3/5)`.
+fn seat_rate(record: &SeatRecord) -> String {
+    match record.win_rate() {
+        Some(rate) => format!("{:.0}% ({}/{})", rate * 100.0, record.wins, record.games),
+        None => "-".to_string(),
+    }
 }
 
 /// Renders instruction text in a centered, bordered box.
@@ -839,7 +1841,23 @@ fn render_utt_instructions(
 /// * `f` - The frame to render to
 /// * `area` - The available area
 /// * `instructions` - Lines of instruction text to display
-fn render_instructions(f: &mut Frame, area: Rect, instructions: &[String]) {
+/// * `theme` - Colors of the border
+fn render_instructions(f: &mut Frame, area: Rect, instructions: &[String], theme: &Theme) {
+    render_commands(f, area, instructions, None, theme);
+}
+
+/// Renders the instructions of a game screen, pointing to the help overlay.
+fn render_game_instructions(f: &mut Frame, area: Rect, instructions: &[String], theme: &Theme) {
+    render_commands(f, area, instructions, Some("F1: Help"), theme);
+}
+
+fn render_commands(
+    f: &mut Frame,
+    area: Rect,
+    instructions: &[String],
+    footer: Option<&str>,
+    theme: &Theme,
+) {
     let max_width = instructions
         .iter()
         .map(|s| s.chars().count())
@@ -862,11 +1880,13 @@ fn render_instructions(f: &mut Frame, area: Rect, instructions: &[String]) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border))
                 .title(
                     Line::from("Commands")
                         .centered()
                         .style(Style::default().add_modifier(Modifier::BOLD)),
-                ),
+                )
+                .title_bottom(Line::from(footer.unwrap_or_default()).centered()),
         );
 
     f.render_widget(paragraph, area);
@@ -922,18 +1942,494 @@ fn render_size_warning(f: &mut Frame, min_width: u16, min_height: u16) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ai::AI;
+    use crate::ai::simple::SimpleAi;
+    use crate::app::App;
+    use crate::utils::Position;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    /// Renders the app into a test terminal and returns its text, one line per row.
+    fn render_to_text(app: &App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| render(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_render_main_menu() {
+        let app = App::new();
+        let text = render_to_text(&app, 80, 30);
+        assert!(text.contains("Select Game"));
+        assert!(text.contains("Ultimate Tic Tac Toe"));
+        assert!(text.contains("Quit"));
+        assert!(text.contains("Arrow Keys: Navigate"));
+        assert!(!text.contains("Terminal Too Small!"));
+    }
+
+    #[test]
+    fn test_render_menu_shows_the_chosen_opponent() {
+        let mut app = App::new();
+        app.go_to_ttt_menu();
+        for _ in 0..3 {
+            app.handle_down();
+        }
+        app.handle_left();
+        app.handle_left();
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains("Play vs AI ◂ Minimax ▸"));
+        assert!(!text.contains("Simple"));
+    }
+
+    #[test]
+    fn test_render_main_menu_too_small() {
+        let app = App::new();
+        let text = render_to_text(&app, 30, 10);
+        assert!(text.contains("Terminal Too Small!"));
+        assert!(text.contains("Minimum required: 14x13"));
+        assert!(text.contains("Current size: 30x10"));
+    }
+
+    #[test]
+    fn test_render_classic_game_in_progress() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        if let Scene::PlayingTTT(game) = &mut app.current_scene {
+            game.selected = Position { row: 1, col: 1 };
+        }
+        app.handle_enter();
+
+        let text = render_to_text(&app, 80, 30);
+        assert!(text.contains("Current Player: O"));
+        assert!(text.contains("Mode: Local PvP"));
+        assert!(text.contains("│ X │"));
+        assert!(text.contains("───┼───┼───"));
+        assert!(text.contains("Enter: Place Mark"));
+    }
+
+    #[test]
+    fn test_render_scoreboard_next_to_mode() {
+        let mut app = App::new();
+        let mode = GameMode::PvE(AI::Medium(SimpleAi::new(Mark::O)));
+        let name = mode_name(&mode);
+        app.start_ttt_game(mode);
+        let text = render_to_text(&app, 80, 30);
+        assert!(text.contains(&name));
+        assert!(!text.contains("X 0 O 0"));
+
+        app.scoreboard.record(GameState::Won(Mark::O));
+        app.scoreboard.record(GameState::Draw);
+        let text = render_to_text(&app, 80, 30);
+        assert!(text.contains(&format!("{name} | X 0 O 1 D 1")));
+    }
+
+    #[test]
+    fn test_render_help_over_game() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        app.handle_help();
+        assert!(app.show_help);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 60)).unwrap();
+        terminal.draw(|f| render(f, &app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Key Bindings"));
+        assert!(text.contains("Save the game"));
+        assert!(text.contains("Press any key to continue"));
+
+        // The game is still drawn around the help box, dimmed
+        assert!(text.contains("Ctrl+R: Redo"));
+        assert!(text.contains("F1: Help"));
+        let title_cell = buffer
+            .content()
+            .iter()
+            .find(|cell| cell.symbol() == "╔")
+            .unwrap();
+        assert!(title_cell.modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn test_swapped_sides_are_shown_in_the_status() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        assert!(!render_to_text(&app, 80, 40).contains("P1 O, P2 X"));
+
+        app.run_command(crate::app::Command::SwapSides);
+        assert!(render_to_text(&app, 80, 40).contains("Current Player: X | P1 O, P2 X"));
+    }
+
+    #[test]
+    fn test_read_only_is_shown_in_the_status() {
+        let mut app = App::new();
+        app.start_utt_game(GameMode::LocalPvP);
+        assert!(!render_to_text(&app, 100, 60).contains("Read-only"));
+
+        app.run_command(crate::app::Command::ReadOnly);
+        assert!(render_to_text(&app, 100, 60).contains("| Read-only"));
+    }
+
+    #[test]
+    fn test_draw_offer_is_shown_in_the_status() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        app.handle_offer_draw();
+        assert!(render_to_text(&app, 100, 60).contains("| X offers a draw (Y/N)"));
+    }
+
+    #[test]
+    fn test_game_summary_shows_the_winner_and_move_count() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        assert!(!render_to_text(&app, 80, 40).contains("Game Over"));
+
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.board = SmallBoard::from_str_grid("XXX|OO.|...").unwrap();
+        game.turn = 5;
+        app.handle_tick();
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains("Game Over"));
+        assert!(text.contains("│        Player X WINS!        │"));
+        assert!(text.contains("Moves: 5"));
+        assert!(text.contains("Session: X 1 O 0 D 0"));
+        assert!(text.contains("R: Rematch  M: Menu  Q: Quit"));
+    }
+
+    #[test]
+    fn test_ultimate_summary_shows_the_small_board_winners() {
+        let mut app = App::new();
+        app.start_utt_game(GameMode::LocalPvP);
+        let Scene::PlayingUTT(game) = &mut app.current_scene else {
+            panic!("expected ultimate game");
+        };
+        game.big_board.state = GameState::Draw;
+        game.turn = 81;
+        let text = render_to_text(&app, 100, 60);
+        assert!(text.contains("Game Over"));
+        assert!(text.contains("DRAW!"));
+        assert!(text.contains("Moves: 81"));
+        assert!(!text.contains("Session:"));
+    }
+
+    #[test]
+    fn test_render_quit_confirmation_over_game() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        assert!(!render_to_text(&app, 80, 40).contains("Keep playing"));
+
+        app.run_command(crate::app::Command::Quit);
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains("The game in progress will be lost."));
+        assert!(text.contains("Y: Quit   N: Keep playing"));
+    }
+
+    #[test]
+    fn test_analysis_lists_the_winning_move_first() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.board = SmallBoard::from_str_grid("XX.|OO.|...").unwrap();
+        game.turn = 4;
+        game.toggle_analysis();
+        assert!(!render_to_text(&app, 80, 40).contains("Best:"));
+
+        app.handle_tick();
+        assert!(render_to_text(&app, 80, 40).contains("Best: top right"));
+    }
+
+    #[test]
+    fn test_analysis_bar_shows_the_leading_mark() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.board = SmallBoard::from_str_grid("XX.|...|..O").unwrap();
+        assert!(!render_to_text(&app, 80, 40).contains("Eval:"));
+
+        app.run_command(crate::app::Command::Analysis);
+        assert!(render_to_text(&app, 80, 40).contains("Eval: X +0.3"));
+    }
+
+    #[test]
+    fn test_render_gomoku_game() {
+        let mut app = App::new();
+        app.start_gomoku_game();
+
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains("Current Player: X"));
+        assert!(text.contains("───┼───┼───┼───┼───"));
+        assert!(text.contains("Mode: Gomoku, 4 in a row"));
+        assert!(text.contains("Enter: Place Mark"));
+    }
+
+    #[test]
+    fn test_render_four_by_four_game() {
+        let mut app = App::new();
+        app.start_four_by_four_game();
+
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains("───┼───┼───┼───"));
+        assert!(!text.contains("───┼───┼───┼───┼───"));
+        assert!(text.contains("Mode: Tic Tac Toe 4x4, 4 in a row"));
+    }
+
+    #[test]
+    fn test_current_player_status_uses_the_mark_color() {
+        let status_color = |app: &App, status: &str| {
+            let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+            terminal.draw(|f| render(f, app)).unwrap();
+            let cells = terminal.backend().buffer().content().to_vec();
+            let start = (0..cells.len() - status.len())
+                .find(|&index| {
+                    status
+                        .chars()
+                        .zip(&cells[index..])
+                        .all(|(character, cell)| cell.symbol() == character.to_string())
+                })
+                .expect("status not rendered");
+            cells[start].fg
+        };
+
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        assert_eq!(
+            status_color(&app, "Current Player: X"),
+            Theme::DEFAULT.x_color
+        );
+
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.play_move();
+        assert_eq!(
+            status_color(&app, "Current Player: O"),
+            Theme::DEFAULT.o_color
+        );
+    }
+
+    #[test]
+    fn test_marks_use_the_theme_colors() {
+        let x_colors = |theme: Theme| {
+            let mut app = App::new();
+            app.theme = theme;
+            app.start_ttt_game(GameMode::LocalPvP);
+            let Scene::PlayingTTT(game) = &mut app.current_scene else {
+                panic!("expected classic game");
+            };
+            game.play_move();
+
+            let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+            terminal.draw(|f| render(f, &app)).unwrap();
+            let buffer = terminal.backend().buffer();
+            buffer
+                .content()
+                .iter()
+                .filter(|cell| cell.symbol() == "X")
+                .map(|cell| cell.fg)
+                .collect::<Vec<_>>()
+        };
+
+        let default_colors = x_colors(Theme::DEFAULT);
+        assert!(default_colors.contains(&Color::Red));
+        assert!(!default_colors.contains(&Color::LightRed));
+        let high_contrast_colors = x_colors(Theme::HIGH_CONTRAST);
+        assert!(high_contrast_colors.contains(&Color::LightRed));
+        assert!(!high_contrast_colors.contains(&Color::Red));
+    }
+
+    /// Returns the positions of every board cell symbol in the rendered text.
+    fn mark_positions(text: &str, symbols: &[char]) -> Vec<(usize, usize)> {
+        text.lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, symbol)| symbols.contains(symbol))
+                    .map(move |(col, _)| (row, col))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_coordinates_surround_the_boards_without_moving_them() {
+        let mut app = App::new();
+        app.start_ttt_game(GameMode::LocalPvP);
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.play_move();
+        let before = render_to_text(&app, 80, 40);
+        app.handle_coordinates();
+        let after = render_to_text(&app, 80, 40);
+
+        assert!(after.contains(" a   b   c "));
+        assert!(after.contains("1  X │ O │   "));
+        assert!(after.contains("3    │   │   "));
+        let cells = ['│', '┼', '─'];
+        assert_eq!(
+            mark_positions(&before, &cells),
+            mark_positions(&after, &cells)
+        );
+
+        app.start_utt_game(GameMode::LocalPvP);
+        let before = render_to_text(&app, 80, 40);
+        app.handle_coordinates();
+        let after = render_to_text(&app, 80, 40);
+
+        assert!(after.contains("     0             1             2     "));
+        assert_eq!(after.matches("2    │   │    ┃ ").count(), 1);
+        let cells = ['│', '┼', '─', '┃', '━', '╋'];
+        assert_eq!(
+            mark_positions(&before, &cells),
+            mark_positions(&after, &cells)
+        );
+    }
+
+    #[test]
+    fn test_won_ultimate_boards_can_be_drawn_as_a_large_mark() {
+        let mut app = App::new();
+        app.start_utt_game(GameMode::LocalPvP);
+        let Scene::PlayingUTT(game) = &mut app.current_scene else {
+            panic!("expected Ultimate game");
+        };
+        for (row, col, mark) in [
+            (0, 0, Mark::X),
+            (1, 0, Mark::O),
+            (0, 1, Mark::X),
+            (1, 1, Mark::O),
+            (0, 2, Mark::X),
+        ] {
+            game.big_board.active_board = None;
+            game.big_board.make_move(0, 0, row, col, mark);
+        }
+        let before = render_to_text(&app, 80, 40);
+        assert!(before.contains(" X │ X │ X "));
+        assert!(!before.contains('╳'));
+
+        app.handle_big_marks();
+        let after = render_to_text(&app, 80, 40);
+        assert!(!after.contains(" X │ X │ X "));
+        for row in BIG_X {
+            assert!(after.contains(&format!("{row} ┃ ")), "{row}");
+        }
+    }
+
+    #[test]
+    fn test_color_blind_theme_draws_mark_glyphs() {
+        let mut app = App::new();
+        app.theme = Theme::COLOR_BLIND;
+        app.start_ttt_game(GameMode::LocalPvP);
+        let Scene::PlayingTTT(game) = &mut app.current_scene else {
+            panic!("expected classic game");
+        };
+        game.play_move();
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains(" ✕ "));
+        // The selection preview shows the glyph of the player on turn.
+        assert!(text.contains(" ◯ "));
+
+        app.start_utt_game(GameMode::LocalPvP);
+        let Scene::PlayingUTT(game) = &mut app.current_scene else {
+            panic!("expected Ultimate game");
+        };
+        game.input_enter();
+        game.input_enter();
+        game.input_enter();
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains(" ✕ "));
+        assert!(text.contains(" ◯ "));
+        assert!(!text.contains(" X │"));
+    }
+
+    #[test]
+    fn test_render_ultimate_game() {
+        let mut app = App::new();
+        app.start_utt_game(GameMode::LocalPvP);
+
+        let text = render_to_text(&app, 80, 40);
+        assert!(text.contains("Current Player: X"));
+        assert!(text.contains("━━━━━━━━━━━━╋━━━━━━━━━━━━━╋━━━━━━━━━━━━"));
+        assert!(text.contains("Enter: Confirm Board"));
+
+        let text = render_to_text(&app, 30, 10);
+        assert!(text.contains("Terminal Too Small!"));
+        assert!(text.contains("Minimum required: 43x20"));
+        assert!(!text.contains("Current Player"));
+    }
+
+    #[test]
+    fn test_selected_cell_previews_the_destination_board() {
+        let mut game = GamePlayUTT::new(GameMode::LocalPvP);
+        let moves = [(1, 1, 0, 0), (0, 0, 1, 1), (1, 1, 2, 2), (2, 2, 1, 1)];
+        let mut mark = Mark::X;
+        for (board_row, board_col, cell_row, cell_col) in moves {
+            game.big_board
+                .make_move(board_row, board_col, cell_row, cell_col, mark);
+            mark = mark.switch();
+        }
+        game.selected_board = Position { row: 1, col: 1 };
+        game.selected_cell = Some(Position { row: 2, col: 0 });
+
+        let theme = Theme::DEFAULT;
+        let (_, target_style) =
+            small_board_selection_style(&game, 2, 0, &NetworkStatus::Idle, &theme);
+        assert_eq!(target_style.fg, Some(theme.destination));
+        let (selection, board_style) =
+            small_board_selection_style(&game, 1, 1, &NetworkStatus::Idle, &theme);
+        assert_eq!(selection, Some((Position { row: 2, col: 0 }, Mark::X)));
+        assert_eq!(board_style.fg, Some(Color::Green));
+        let (status, _) = utt_game_status(&game, &NetworkStatus::Idle, &theme);
+        assert!(!status.contains("free choice"));
+
+        // Completing the diagonal closes the center board and sends O back to it,
+        // so O may play anywhere.
+        game.selected_cell = Some(Position { row: 1, col: 1 });
+        let (status, _) = utt_game_status(&game, &NetworkStatus::Idle, &theme);
+        assert!(status.ends_with("| Next: free choice"));
+    }
+
+    #[test]
+    fn test_free_move_is_announced_under_the_board() {
+        let mut app = App::new();
+        app.start_utt_game(GameMode::LocalPvP);
+        assert!(!render_to_text(&app, 100, 60).contains("Free move"));
+
+        let Scene::PlayingUTT(game) = &mut app.current_scene else {
+            panic!("expected ultimate game");
+        };
+        game.free_move = true;
+        assert!(render_to_text(&app, 100, 60).contains("Free move: choose any board"));
+    }
 
     #[test]
     fn test_online_turn_status_uses_local_and_opponent_colors() {
         let mut game = GamePlayTTT::new(GameMode::OnlinePvP(Mark::O));
 
-        let (_, opponent_style) =
-            ttt_game_status(&game, &NetworkStatus::Connected { mark: Mark::O });
+        let (_, opponent_style) = ttt_game_status(
+            &game,
+            &NetworkStatus::Connected { mark: Mark::O },
+            &Theme::DEFAULT,
+        );
         assert_eq!(opponent_style, Style::default());
 
         game.active_player = Mark::O;
-        let (_, local_style) = ttt_game_status(&game, &NetworkStatus::Connected { mark: Mark::O });
-        assert_eq!(local_style.fg, Some(Color::LightYellow));
+        let (_, local_style) = ttt_game_status(
+            &game,
+            &NetworkStatus::Connected { mark: Mark::O },
+            &Theme::DEFAULT,
+        );
+        assert_eq!(local_style.fg, Some(Theme::DEFAULT.o_color));
     }
 
     #[test]
@@ -941,16 +2437,18 @@ mod tests {
         let mut game = GamePlayUTT::new(GameMode::OnlinePvP(Mark::O));
         let connected = NetworkStatus::Connected { mark: Mark::O };
 
-        let (_, opponent_style) = utt_game_status(&game, &connected);
+        let (_, opponent_style) = utt_game_status(&game, &connected, &Theme::DEFAULT);
         assert_eq!(opponent_style, Style::default());
-        let (selection, board_style) = small_board_selection_style(&game, 0, 0, &connected);
+        let (selection, board_style) =
+            small_board_selection_style(&game, 0, 0, &connected, &Theme::DEFAULT);
         assert!(selection.is_none());
         assert_eq!(board_style, Style::default());
 
         game.active_player = Mark::O;
-        let (_, local_style) = utt_game_status(&game, &connected);
-        assert_eq!(local_style.fg, Some(Color::LightYellow));
-        let (_, board_style) = small_board_selection_style(&game, 0, 0, &connected);
+        let (_, local_style) = utt_game_status(&game, &connected, &Theme::DEFAULT);
+        assert_eq!(local_style.fg, Some(Theme::DEFAULT.o_color));
+        let (_, board_style) =
+            small_board_selection_style(&game, 0, 0, &connected, &Theme::DEFAULT);
         assert_eq!(board_style.fg, Some(Color::LightYellow));
     }
 
@@ -958,12 +2456,18 @@ mod tests {
     fn test_disconnected_opponent_replaces_online_ultimate_status() {
         let game = GamePlayUTT::new(GameMode::OnlinePvP(Mark::X));
 
-        let (status, style) = utt_game_status(&game, &NetworkStatus::OpponentDisconnected);
+        let (status, style) =
+            utt_game_status(&game, &NetworkStatus::OpponentDisconnected, &Theme::DEFAULT);
 
         assert_eq!(status, "Opponent left the game");
         assert_eq!(style.fg, Some(Color::Magenta));
-        let (selection, _) =
-            small_board_selection_style(&game, 0, 0, &NetworkStatus::OpponentDisconnected);
+        let (selection, _) = small_board_selection_style(
+            &game,
+            0,
+            0,
+            &NetworkStatus::OpponentDisconnected,
+            &Theme::DEFAULT,
+        );
         assert!(selection.is_none());
     }
 
@@ -971,12 +2475,74 @@ mod tests {
     fn test_disconnected_opponent_replaces_online_game_status() {
         let game = GamePlayTTT::new(GameMode::OnlinePvP(Mark::X));
 
-        let (status, style) = ttt_game_status(&game, &NetworkStatus::OpponentDisconnected);
+        let (status, style) =
+            ttt_game_status(&game, &NetworkStatus::OpponentDisconnected, &Theme::DEFAULT);
 
         assert_eq!(status, "Opponent left the game");
         assert_eq!(style.fg, Some(Color::Magenta));
     }
 
+    #[test]
+    fn test_connection_error_replaces_online_game_status() {
+        let game = GamePlayTTT::new(GameMode::OnlinePvP(Mark::X));
+        let failed = NetworkStatus::Failed("the opponent disconnected".to_string());
+
+        let (status, style) = ttt_game_status(&game, &failed, &Theme::DEFAULT);
+
+        assert_eq!(status, "Connection error: the opponent disconnected");
+        assert_eq!(style.fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_game_clock_is_shown_in_the_status() {
+        let mut game = GamePlayTTT::new(GameMode::LocalPvP);
+        game.set_time_budget(Some(Duration::from_secs(90)));
+
+        let (status, _) = ttt_game_status(&game, &NetworkStatus::Idle, &Theme::DEFAULT);
+        assert!(status.ends_with(" | X 1:30 O 1:30"), "{status}");
+
+        game.flagged = Some(Mark::X);
+        let (status, style) = ttt_game_status(&game, &NetworkStatus::Idle, &Theme::DEFAULT);
+        assert_eq!(status, "Player X ran out of time");
+        assert_eq!(style.fg, Some(Theme::DEFAULT.o_color));
+    }
+
+    #[test]
+    fn test_move_names() {
+        assert_eq!(move_name(Move::Base(2, 2)), "bottom right");
+        assert_eq!(move_name(Move::Ultimate(1, 1, 0, 1)), "center board, top");
+    }
+
+    #[test]
+    fn test_seat_rate_formatting() {
+        let record = SeatRecord {
+            games: 5,
+            wins: 3,
+            draws: 1,
+        };
+        assert_eq!(seat_rate(&record), "60% (3/5)");
+        assert_eq!(seat_rate(&SeatRecord::default()), "-");
+    }
+
+    #[test]
+    fn test_sparkline_maps_evaluations_to_levels() {
+        assert_eq!(sparkline(&[], 10), "");
+        assert_eq!(sparkline(&[0.0, 0.5, 1.0], 10), "▁▅█");
+        assert_eq!(sparkline(&[0.0, 1.0, 1.0, 1.0], 2), "▅█");
+        assert_eq!(sparkline(&[0.5; 40], 25).chars().count(), 25);
+    }
+
+    #[test]
+    fn test_mode_name_labels_ai_engines() {
+        let eve = GameMode::EvE(AI::Weak(Mark::X), AI::Medium(SimpleAi::new(Mark::O)));
+        assert_eq!(mode_name(&eve), "Random vs Simple");
+        assert_eq!(
+            mode_name(&GameMode::PvE(AI::Weak(Mark::O))),
+            "Play vs AI: Random"
+        );
+        assert_eq!(mode_name(&GameMode::LocalPvP), "Mode: Local PvP");
+    }
+
     #[test]
     fn test_format_ticket_lines_groups_ticket_for_copying() {
         let lines = format_ticket_lines("abcdefghijklmnopqrstuvwxy");
@@ -1,7 +1,8 @@
 use crate::game::Board;
+use serde::{Deserialize, Serialize};
 
-/// Row and column of a selection within a 3x3 grid.
-#[derive(Clone, Copy, Debug)]
+/// Row and column of a selection within a board grid.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Position {
     /// Zero-based row index.
     pub row: usize,
@@ -12,18 +13,19 @@ pub struct Position {
 /// Moves selection left, wrapping to the rightmost column and finding
 /// the next playable position.
 pub fn move_selection_left_playable(board: &impl Board, selected: &mut Position) {
-    for _ in 0..3 {
+    let (rows, cols) = board.dims();
+    for _ in 0..cols {
         let original_row = selected.row;
-        move_selection_left(selected);
+        move_selection_left(selected, cols);
 
-        for _ in 0..3 {
+        for _ in 0..rows {
             if board.is_playable(selected.row, selected.col) {
                 return;
             }
-            match original_row {
-                0 => move_selection_down(selected),
-                2 => move_selection_up(selected),
-                _ => move_selection_down(selected),
+            if original_row == rows - 1 {
+                move_selection_up(selected, rows);
+            } else {
+                move_selection_down(selected, rows);
             }
         }
     }
@@ -32,18 +34,19 @@ pub fn move_selection_left_playable(board: &impl Board, selected: &mut Position)
 /// Moves selection right, wrapping to the leftmost column and finding
 /// the next playable position.
 pub fn move_selection_right_playable(board: &impl Board, selected: &mut Position) {
-    for _ in 0..3 {
+    let (rows, cols) = board.dims();
+    for _ in 0..cols {
         let original_row = selected.row;
-        move_selection_right(selected);
+        move_selection_right(selected, cols);
 
-        for _ in 0..3 {
+        for _ in 0..rows {
             if board.is_playable(selected.row, selected.col) {
                 return;
             }
-            match original_row {
-                0 => move_selection_down(selected),
-                2 => move_selection_up(selected),
-                _ => move_selection_down(selected),
+            if original_row == rows - 1 {
+                move_selection_up(selected, rows);
+            } else {
+                move_selection_down(selected, rows);
             }
         }
     }
@@ -52,18 +55,19 @@ pub fn move_selection_right_playable(board: &impl Board, selected: &mut Position
 /// Moves selection up, wrapping to the bottom row and finding
 /// the next playable position.
 pub fn move_selection_up_playable(board: &impl Board, selected: &mut Position) {
-    for _ in 0..3 {
+    let (rows, cols) = board.dims();
+    for _ in 0..rows {
         let original_col = selected.col;
-        move_selection_up(selected);
+        move_selection_up(selected, rows);
 
-        for _ in 0..3 {
+        for _ in 0..cols {
             if board.is_playable(selected.row, selected.col) {
                 return;
             }
-            match original_col {
-                0 => move_selection_right(selected),
-                2 => move_selection_left(selected),
-                _ => move_selection_right(selected),
+            if original_col == cols - 1 {
+                move_selection_left(selected, cols);
+            } else {
+                move_selection_right(selected, cols);
             }
         }
     }
@@ -72,18 +76,19 @@ pub fn move_selection_up_playable(board: &impl Board, selected: &mut Position) {
 /// Moves selection down, wrapping to the top row and finding
 /// the next playable position.
 pub fn move_selection_down_playable(board: &impl Board, selected: &mut Position) {
-    for _ in 0..3 {
+    let (rows, cols) = board.dims();
+    for _ in 0..rows {
         let original_col = selected.col;
-        move_selection_down(selected);
+        move_selection_down(selected, rows);
 
-        for _ in 0..3 {
+        for _ in 0..cols {
             if board.is_playable(selected.row, selected.col) {
                 return;
             }
-            match original_col {
-                0 => move_selection_right(selected),
-                2 => move_selection_left(selected),
-                _ => move_selection_right(selected),
+            if original_col == cols - 1 {
+                move_selection_left(selected, cols);
+            } else {
+                move_selection_right(selected, cols);
             }
         }
     }
@@ -91,46 +96,112 @@ pub fn move_selection_down_playable(board: &impl Board, selected: &mut Position)
 
 /// Resets the selected position to the first available cell.
 pub fn reset_position(board: &impl Board, selected: &mut Position) {
+    let (rows, cols) = board.dims();
     (selected.row, selected.col) = (0, 0);
-    for _ in 0..9 {
+    for _ in 0..rows * cols {
         if board.is_playable(selected.row, selected.col) {
             return;
         }
-        move_selection_next(selected);
+        move_selection_next(selected, rows, cols);
     }
 }
 
-fn move_selection_left(selected: &mut Position) {
+fn move_selection_left(selected: &mut Position, cols: usize) {
     if selected.col > 0 {
         selected.col -= 1;
     } else {
-        selected.col = 2;
+        selected.col = cols - 1;
     }
 }
 
-fn move_selection_right(selected: &mut Position) {
-    selected.col = (selected.col + 1) % 3;
+fn move_selection_right(selected: &mut Position, cols: usize) {
+    selected.col = (selected.col + 1) % cols;
 }
 
-fn move_selection_up(selected: &mut Position) {
+fn move_selection_up(selected: &mut Position, rows: usize) {
     if selected.row > 0 {
         selected.row -= 1;
     } else {
-        selected.row = 2;
+        selected.row = rows - 1;
     }
 }
 
-fn move_selection_down(selected: &mut Position) {
-    selected.row = (selected.row + 1) % 3;
+fn move_selection_down(selected: &mut Position, rows: usize) {
+    selected.row = (selected.row + 1) % rows;
 }
 
-fn move_selection_next(selected: &mut Position) {
+fn move_selection_next(selected: &mut Position, rows: usize, cols: usize) {
     selected.col += 1;
-    if selected.col >= 3 {
+    if selected.col >= cols {
         selected.col = 0;
         selected.row += 1;
-        if selected.row >= 3 {
+        if selected.row >= rows {
             selected.row = 0;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Mark;
+    use crate::game::generic::GenericBoard;
+
+    #[test]
+    fn test_selection_wraps_at_width_four() {
+        let mut board = GenericBoard::new(4, 4);
+        let mut selected = Position { row: 0, col: 3 };
+        move_selection_right_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 0, col: 0 });
+        move_selection_left_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 0, col: 3 });
+        move_selection_up_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 3, col: 3 });
+        move_selection_down_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 0, col: 3 });
+
+        // Taken cells are skipped, and the search continues into the fourth row.
+        board.make_move(0, 0, Mark::X);
+        board.make_move(1, 0, Mark::O);
+        board.make_move(2, 0, Mark::X);
+        move_selection_right_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 3, col: 0 });
+
+        reset_position(&board, &mut selected);
+        assert_eq!(selected, Position { row: 0, col: 1 });
+    }
+
+    #[test]
+    fn test_selection_searches_a_five_by_five_board() {
+        let mut board = GenericBoard::new(5, 4);
+        let mut mark = Mark::X;
+        for (row, col) in [(0, 0), (0, 1), (1, 4), (2, 4), (4, 2), (3, 0), (4, 0)] {
+            board.make_move(row, col, mark);
+            mark = mark.switch();
+        }
+
+        // Left from the first column wraps to the last one, skipping down
+        // past its taken cells.
+        let mut selected = Position { row: 1, col: 0 };
+        move_selection_left_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 3, col: 4 });
+
+        // From the bottom row, the search goes up instead.
+        selected = Position { row: 4, col: 1 };
+        move_selection_right_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 3, col: 2 });
+
+        // Up from the top row wraps to the bottom, then searches rightward.
+        selected = Position { row: 0, col: 0 };
+        move_selection_up_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 4, col: 1 });
+
+        // Down from the last column searches leftward.
+        selected = Position { row: 0, col: 4 };
+        move_selection_down_playable(&board, &mut selected);
+        assert_eq!(selected, Position { row: 1, col: 3 });
+
+        reset_position(&board, &mut selected);
+        assert_eq!(selected, Position { row: 0, col: 2 });
+    }
+}
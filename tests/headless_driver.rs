@@ -0,0 +1,67 @@
+use tic_tac_foe::{
+    ai::Move,
+    app::Action::{self, Confirm, Down, Left},
+    driver::GameDriver,
+    game::{
+        Board, GameState, Mark,
+        notation::{GameRecord, RecordBoard},
+    },
+    scenes::GameMode,
+};
+
+#[test]
+fn winning_action_sequence_ends_the_classic_game() {
+    let mut driver = GameDriver::classic(GameMode::LocalPvP);
+
+    // X takes the top row while O fills the middle one.
+    let actions = [
+        Confirm, Down, Left, Confirm, Confirm, Down, Left, Confirm, Confirm,
+    ];
+    assert_eq!(driver.apply_actions(actions), 5);
+
+    assert_eq!(driver.state(), GameState::Won(Mark::X));
+    assert_eq!(driver.snapshot(), "XXX\nOO.\n...");
+    assert!(!driver.apply_action(Confirm));
+}
+
+#[test]
+fn recorded_ultimate_game_replays_within_active_boards() {
+    let record = GameRecord::parse("ultimate X 4.4 4.0 0.4 4.8 8.4 4.2 2.4").unwrap();
+    let mut driver = GameDriver::ultimate(GameMode::LocalPvP);
+
+    for mv in &record.moves {
+        let actions = driver.actions_for(*mv).unwrap();
+        assert_eq!(driver.apply_actions(actions), 1);
+    }
+
+    let Ok(RecordBoard::Ultimate(expected)) = record.board_at(record.moves.len()) else {
+        panic!("expected Ultimate board");
+    };
+    let GameDriver::Ultimate(game) = &driver else {
+        panic!("expected Ultimate game");
+    };
+    assert_eq!(game.big_board, expected);
+    assert_eq!(game.big_board.active_board, Some((1, 1)));
+    assert_eq!(game.active_player, Mark::O);
+    assert_eq!(driver.state(), GameState::Playing);
+    assert_eq!(
+        driver.snapshot(),
+        "...|...|...\n.X.|...|.X.\n...|...|...\n\
+         ---+---+---\n\
+         ...|O.O|...\n...|.X.|...\n...|..O|...\n\
+         ---+---+---\n\
+         ...|...|...\n...|...|.X.\n...|...|..."
+    );
+
+    // O is bound to the center board, so a move elsewhere cannot be reached.
+    assert_eq!(driver.actions_for(Move::Ultimate(0, 0, 0, 0)), None);
+    // Neither can a move off the grid.
+    assert_eq!(driver.actions_for(Move::Ultimate(3, 1, 0, 0)), None);
+    assert_eq!(driver.actions_for(Move::Ultimate(1, 1, 0, 5)), None);
+    let moves_played = driver.apply_actions([Action::Back, Confirm]);
+    assert_eq!(moves_played, 1);
+    let GameDriver::Ultimate(game) = &driver else {
+        panic!("expected Ultimate game");
+    };
+    assert_eq!(game.big_board.get_board(1, 1).get(0, 1), Some(Mark::O));
+}
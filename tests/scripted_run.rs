@@ -0,0 +1,57 @@
+use crossterm::event::KeyCode::{self, Char, Down, Enter};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use tic_tac_foe::app::App;
+use tic_tac_foe::runner::{ScriptedInput, run_app};
+
+/// Runs the application on a test terminal until `keys` make it quit.
+///
+/// # Returns
+/// The text of the last drawn screen.
+fn run_script(keys: impl IntoIterator<Item = KeyCode>) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+    let mut app = App::new();
+    let mut input = ScriptedInput::from_keys(keys);
+    run_app(&mut terminal, &mut app, &mut input).unwrap();
+    assert!(app.should_quit);
+    terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect()
+}
+
+#[test]
+fn scripted_keys_play_a_classic_game_to_an_x_win() {
+    let screen = run_script([
+        // Main menu: Tic Tac Toe, then Local PvP with X moving first.
+        Down,
+        Enter,
+        Down,
+        Enter,
+        Enter,
+        // X takes the top row while O plays in the middle one.
+        Char('0'),
+        Char('3'),
+        Char('1'),
+        Char('4'),
+        Char('2'),
+        // A finished game quits without confirmation.
+        Char('q'),
+    ]);
+
+    assert!(screen.contains("Game Over"));
+    assert!(screen.contains("Player X WINS!"));
+    assert!(screen.contains("Moves: 5"));
+}
+
+#[test]
+fn script_ending_before_quit_is_an_error() {
+    let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+    let mut app = App::new();
+    let mut input = ScriptedInput::from_keys([Down]);
+    assert!(run_app(&mut terminal, &mut app, &mut input).is_err());
+    assert!(!app.should_quit);
+}